@@ -0,0 +1,2369 @@
+//! 命令行界面模块
+
+use bbdc_core::{Book, BookMetadata, CheckDiff, CheckResult, Confidence, SourceCheck, EnvLoader, ExtractMode, Report, WordExtractor, Result, Error};
+use bbdc_core::apkg::AnkiFields;
+use bbdc_core::{ColumnMapping, InputFormat, KnownWords, CorrectionLog, CorrectionRecord, ExtractResult, Lemmatizer, Level, LevelList, PronouncingDictionary, RunManifest, Spelling, SpellingNormalizer, Word};
+use bbdc_core::humanize::{self, Locale};
+use bbdc_core::phonetics;
+use bbdc_core::word_extractor::{self, BatchFilter, Casing, WordFilter, WordPolicy, DEFAULT_FALLBACK_PATTERN};
+use bbdc_core::report::DEFAULT_LINK_TEMPLATE;
+use bbdc_providers::{clustering, services, synonyms};
+use bbdc_providers::comparison::ModelRun;
+use bbdc_providers::{CachedEmbedder, Embedder, EmbeddingProvider, FastTextVectors, HttpEmbedder};
+use bbdc_providers::{BBDCChecker, Browser, CaptchaChallenge, CheckBackend, CheckCache, CheckEvent, CheckState, Checker, CookieStore, CorrectionResult, DatasetFormat, LLMCorrector, OfflineDictionary};
+use crate::daemon::{ApiConfig, JobQueue, JobSpec};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::io::{self, IsTerminal, Write};
+
+/// 不背单词词书制作工具
+#[derive(Parser)]
+#[command(name = "bbdc_word_tool")]
+#[command(author = "BBDC Tool Contributors")]
+#[command(version = "1.0.0")]
+#[command(about = "从 Markdown 提取单词并自动核对", long_about = None)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+    
+    /// 输入文件路径（交互模式）
+    #[arg(short, long, value_name = "FILE")]
+    pub input: Option<PathBuf>,
+    
+    /// 输出文件路径
+    #[arg(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+    
+    /// 是否去重
+    #[arg(short, long, default_value_t = true)]
+    pub unique: bool,
+    
+    /// 是否自动核对
+    #[arg(short = 'c', long, default_value_t = true)]
+    pub auto_check: bool,
+    
+    /// 是否包含短语
+    #[arg(short = 'p', long, default_value_t = false)]
+    pub include_phrases: bool,
+    
+    /// 耗时较长的命令结束后发送桌面通知（也可设置 BBDC_NOTIFY=1，需启用 notify 特性）
+    #[arg(long, global = true)]
+    pub notify: bool,
+}
+
+#[derive(Subcommand)]
+pub enum Commands {
+    /// 提取单词（别名 build），按扩展名和内容自动识别输入格式
+    #[command(visible_alias = "build")]
+    Extract(Box<ExtractArgs>),
+    
+    /// 核对单词
+    Check(CheckArgs),
+    
+    /// 输出 JSON 输出所对应的 JSON Schema
+    Schema {
+        /// 只输出指定类型，缺省时输出全部
+        #[arg(value_enum)]
+        kind: Option<SchemaKind>,
+    },
+    
+    /// 检查环境配置和外部服务状态
+    #[command(visible_alias = "doctor")]
+    Env {
+        /// 访问各服务检查是否可用并查询剩余额度
+        #[arg(long)]
+        probe: bool,
+    },
+    
+    /// 处理任务队列；加上 --daemon 时常驻运行并提供 HTTP API
+    Serve {
+        /// 常驻运行：持续处理队列并监听 HTTP API
+        #[arg(long)]
+        daemon: bool,
+        
+        /// HTTP API 监听地址
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        addr: String,
+    },
+    
+    /// 向任务队列提交提取任务
+    Submit {
+        /// 输入文件
+        input: PathBuf,
+        
+        /// 输出文件
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+        
+        /// 提取模式
+        #[arg(short, long, value_enum, default_value_t = ExtractMode::WordsOnly)]
+        mode: ExtractMode,
+        
+        /// 提取后核对（守护进程无法输入验证码，遇到验证码时任务会失败重试）
+        #[arg(long)]
+        check: bool,
+        
+        /// 同时提取列表和“单词 — 释义”形式的纯文本行
+        #[arg(long)]
+        lists: bool,
+    },
+    
+    /// 查看任务队列
+    Jobs,
+    
+    /// 核对单词文件，并用 LLM 更正识别失败的单词
+    Correct {
+        /// 单词文件或 extract --save-result 保存的提取结果（.json）
+        input: PathBuf,
+        
+        /// 用逗号分隔的多个模型分别更正并对比（如 `modelA,modelB`），不记录更正
+        #[arg(long, value_delimiter = ',', num_args = 1.., value_name = "MODELS")]
+        compare: Vec<String>,
+        
+        /// 以 JSON 输出对比结果（仅用于 --compare）
+        #[arg(long)]
+        json: bool,
+        
+        /// 核对时用其他写法重新核对识别失败的单词，识别成功的写法直接作为更正，不调用 LLM
+        #[arg(long)]
+        variants: bool,
+    },
+    
+    /// 按语义主题把单词分成多个子词书
+    Cluster {
+        /// 输入文件（与 extract 相同的格式）
+        input: PathBuf,
+        
+        /// 主题数，默认约为 sqrt(单词数 / 2)
+        #[arg(short = 'k', long)]
+        clusters: Option<usize>,
+        
+        /// 离线 fastText 词向量文件（.vec），指定后不使用嵌入接口
+        #[arg(long, value_name = "FILE")]
+        vectors: Option<PathBuf>,
+        
+        /// 嵌入接口服务商
+        #[arg(long, value_enum, default_value_t = EmbeddingProvider::SiliconFlow)]
+        provider: EmbeddingProvider,
+        
+        /// 提取模式（短语不参与聚类）
+        #[arg(short, long, value_enum, default_value_t = ExtractMode::WordsOnly)]
+        mode: ExtractMode,
+        
+        /// 同时提取列表和“单词 — 释义”形式的纯文本行
+        #[arg(long)]
+        lists: bool,
+        
+        /// 子词书输出目录，默认当前目录
+        #[arg(short, long, value_name = "DIR")]
+        output: Option<PathBuf>,
+    },
+    
+    /// 用词向量找出词书中的近义词，可合并或标注
+    Synonyms {
+        /// 输入文件（与 extract 相同的格式）
+        input: PathBuf,
+        
+        /// 余弦相似度阈值
+        #[arg(short, long, default_value_t = bbdc_providers::synonyms::DEFAULT_THRESHOLD)]
+        threshold: f32,
+        
+        /// 离线 fastText 词向量文件（.vec），指定后不使用嵌入接口
+        #[arg(long, value_name = "FILE")]
+        vectors: Option<PathBuf>,
+        
+        /// 嵌入接口服务商
+        #[arg(long, value_enum, default_value_t = EmbeddingProvider::SiliconFlow)]
+        provider: EmbeddingProvider,
+        
+        /// 处理方式
+        #[arg(short, long, value_enum, default_value_t = SynonymAction::Report)]
+        action: SynonymAction,
+        
+        /// 同时提取列表和“单词 — 释义”形式的纯文本行
+        #[arg(long)]
+        lists: bool,
+        
+        /// 输出文件（merge / annotate）
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    
+    /// 用标注数据集评测更正质量
+    Eval {
+        /// 数据集（JSONL 或 TSV：拼错<TAB>正确）
+        dataset: PathBuf,
+        
+        /// 以 JSON 输出完整评测结果
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// 比较两次运行的清单（extract --manifest 保存），检查升级或更换模型后结果是否退化
+    CompareRuns {
+        /// 前一次的运行清单
+        before: PathBuf,
+        
+        /// 后一次的运行清单
+        after: PathBuf,
+        
+        /// 以 JSON 输出差异
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// 比较两次核对结果（check --json 的输出），查看应用更正后哪些单词变为识别成功
+    CompareChecks {
+        /// 前一次的核对结果
+        before: PathBuf,
+        
+        /// 后一次的核对结果
+        after: PathBuf,
+        
+        /// 以 JSON 输出差异
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// 管理更正记录
+    Corrections {
+        #[command(subcommand)]
+        action: CorrectionsCommands,
+    },
+    
+    /// 管理不背单词登录态
+    Auth {
+        #[command(subcommand)]
+        action: AuthCommands,
+    },
+}
+
+impl Commands {
+    /// 耗时较长、结束时需要通知的命令名称
+    fn long_running_name(&self) -> Option<&'static str> {
+        match self {
+            Commands::Extract(_) => Some("extract"),
+            Commands::Check(_) => Some("check"),
+            Commands::Serve { daemon: false, .. } => Some("serve"),
+            Commands::Correct { .. } => Some("correct"),
+            Commands::Cluster { .. } => Some("cluster"),
+            Commands::Synonyms { .. } => Some("synonyms"),
+            Commands::Eval { .. } => Some("eval"),
+            _ => None,
+        }
+    }
+}
+
+/// 核对命令参数
+#[derive(Args)]
+pub struct CheckArgs {
+    /// 单词文件或 extract --save-result 保存的提取结果（.json），多个文件时分别核对，
+    /// 再汇总并列出各文件的识别率
+    #[arg(required = true)]
+    pub inputs: Vec<PathBuf>,
+    
+    /// 以 JSON 输出核对结果（结构见 `schema check-result`；多个文件时为
+    /// `{"overall": …, "by_source": […]}`）
+    #[arg(long)]
+    pub json: bool,
+    
+    /// 核对后端：bbdc 上传到不背单词，wordlist 按本地词表（--offline-wordlist 或
+    /// BBDC_OFFLINE_WORDLIST）核对；默认读取环境变量 BBDC_CHECKER，未设置时为 bbdc
+    #[arg(long, value_enum)]
+    pub backend: Option<CheckBackend>,
+    
+    /// 每次上传的单词数上限，超过时分块上传，0 表示不分块（默认 2000，也可以用环境变量
+    /// BBDC_CHUNK_SIZE 设置）
+    #[arg(long, value_name = "N")]
+    pub chunk_size: Option<usize>,
+    
+    /// 同时上传的分块数（1~3，默认 1 即逐块上传，也可以用环境变量 BBDC_CHECK_CONCURRENCY 设置）
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=3))]
+    pub concurrency: Option<usize>,
+    
+    /// 无法连接不背单词时改用的离线词表（ECDICT 的 ecdict.csv 或 SCOWL 等每行一个单词的列表），
+    /// 也可以用环境变量 BBDC_OFFLINE_WORDLIST 设置
+    #[arg(long, value_name = "FILE")]
+    pub offline_wordlist: Option<PathBuf>,
+    
+    /// 不使用核对结果缓存，所有单词都重新上传；上次中断的核对进度仍会继续，需要时配合 --no-resume
+    #[arg(long)]
+    pub no_cache: bool,
+    
+    /// 不读写核对进度：不继续上次中断的核对，也不跳过与上次成功核对时相同的单词列表
+    #[arg(long)]
+    pub no_resume: bool,
+    
+    /// 单词列表与上次成功核对时相同也重新核对（默认直接使用上次的结果）
+    #[arg(long)]
+    pub force: bool,
+    
+    /// 在每个输入文件旁保存 <文件名>_recognized.txt 和 <文件名>_unrecognized.txt（每行一个单词），
+    /// 便于处理或重新核对识别失败的单词
+    #[arg(long)]
+    pub save_lists: bool,
+    
+    /// 保存核对结果（JSON，多个文件时保存汇总结果），可用 compare-checks 与之后的核对结果比较
+    #[arg(long, value_name = "FILE")]
+    pub save_result: Option<PathBuf>,
+    
+    /// 用其他写法（小写、原形、去掉派生后缀）重新核对识别失败的单词
+    #[arg(long)]
+    pub variants: bool,
+}
+
+/// 提取命令参数
+#[derive(Args)]
+pub struct ExtractArgs {
+    /// 输入文件，`-` 表示从标准输入读取 Markdown；也可以是目录或带引号的通配符
+    /// （如 'notes/**/*.md'），批量提取后合并，或 http(s) 网址（读取网页中的表格和列表）
+    pub input: PathBuf,
+    
+    /// 输出文件，扩展名为 .json 或 .jsonl 时保存单词的全部字段（JSON 数组或每行一个单词），
+    /// 为 .apkg 时导出 Anki 牌组，为 .xlsx 时导出 Excel 工作簿；章节保留为子牌组、标签和工作表
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    
+    /// 输入为目录或通配符时只提取匹配的文件（相对于输入目录，可重复），如 '**/*.md'；
+    /// 指定后不再限于 Markdown 文件
+    #[arg(long, value_name = "GLOB")]
+    pub include_glob: Vec<String>,
+    
+    /// 输入为目录或通配符时跳过匹配的文件（可重复），如 '**/answers/**'
+    #[arg(long, value_name = "GLOB")]
+    pub exclude_glob: Vec<String>,
+    
+    /// 输入格式，默认按扩展名和文件内容自动识别
+    #[arg(long, value_enum)]
+    pub format: Option<InputFormat>,
+    
+    /// 是否去重
+    #[arg(short, long, default_value_t = true)]
+    pub unique: bool,
+    
+    /// 是否自动核对
+    #[arg(short = 'c', long, default_value_t = true)]
+    pub auto_check: bool,
+    
+    /// 提取模式
+    #[arg(short, long, value_enum, default_value_t = ExtractMode::WordsOnly)]
+    pub mode: ExtractMode,
+    
+    /// 同时提取列表和“单词 — 释义”形式的纯文本行
+    #[arg(long)]
+    pub lists: bool,
+    
+    /// 表格列映射，如 `word=1,meaning=3` 或 `word=Word,meaning=释义`（列序号从 1 开始，也可用表头名），
+    /// 可选 `phonetic=` 音标列和 `example=` 例句列
+    #[arg(long, value_name = "SPEC")]
+    pub columns: Option<ColumnMapping>,
+    
+    /// 单词和释义在同一个单元格时的分隔符，如 ` - `、`(`（`abandon (放弃)`）或 newline（换行、`<br>`）；
+    /// 分隔符之前为单词，之后为释义
+    #[arg(long, value_name = "SEP")]
+    pub split_cell: Option<String>,
+    
+    /// 每行并排的单词组数，如 2 表示 6 列的表格左右各一组「序号、单词、词义」，
+    /// 各组作为独立的条目，先左栏后右栏
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=8))]
+    pub column_groups: u8,
+    
+    /// 额外的表头标记（可重复），序号列不是数字且前三列中有一列等于标记的行视为表头；
+    /// 内置 NO.、Word、Meaning、单词、释义等，也可以用环境变量 BBDC_HEADER_MARKERS（逗号分隔）设置
+    #[arg(long, value_name = "TEXT")]
+    pub header_marker: Vec<String>,
+    
+    /// 表格中单词与短语的区分规则：hyphen（连字符词作为单词）、possessive（去掉所有格）、
+    /// slash（拆分 color/colour）、unicode（展开 ﬁ 等连字、替换弯撇号、去掉零宽字符）、
+    /// sanitize（去掉末尾标点、脚注标记和括号注释），以逗号分隔；默认 all，none 为只按空格和连字符判断短语
+    #[arg(long, value_name = "RULES", default_value = "all")]
+    pub word_policy: WordPolicy,
+    
+    /// 单词和短语的大小写：preserve（保留原文）、lowercase_all（全部小写）、
+    /// lowercase_unless_acronym（小写，USA 这类全大写缩写词除外）
+    #[arg(long, value_enum, default_value = "preserve")]
+    pub casing: Casing,
+    
+    /// 把输入当作没有结构的正文，按词频提取出现次数不少于该值的单词（跳过功能词），
+    /// 按次数从高到低排列
+    #[arg(long, value_name = "MIN_COUNT", conflicts_with_all = ["columns", "split_cell", "column_groups", "sheet", "lists"])]
+    pub frequency: Option<usize>,
+    
+    /// 标注词性：读取释义中的 n.、v.、adj. 等缩写，没有缩写时按后缀推测
+    #[arg(long)]
+    pub pos: bool,
+    
+    /// 按词性分组输出单词（with_meaning / full 模式），隐含 --pos
+    #[arg(long)]
+    pub group_by_pos: bool,
+    
+    /// 按词频或从字幕提取时，把单词首次出现的句子记录为例句（with_meaning / full 模式输出）
+    #[arg(long)]
+    pub examples: bool,
+    
+    /// 找不到表格时逐行匹配的正则，默认匹配 `1. abandon 放弃` 形式的编号行，
+    /// 也可以用环境变量 BBDC_FALLBACK_PATTERN 设置（分组见 README）
+    #[arg(long, value_name = "REGEX")]
+    pub pattern: Option<String>,
+    
+    /// 找不到表格时不使用正则兜底
+    #[arg(long, conflicts_with = "pattern")]
+    pub no_fallback: bool,
+    
+    /// 只处理 N 个单词（和 N 个短语），用于在调用付费 API 前以小样本验证配置
+    #[arg(long, value_name = "N")]
+    pub sample: Option<usize>,
+    
+    /// 随机抽样而不是取前 N 个
+    #[arg(long, requires = "sample")]
+    pub random: bool,
+    
+    /// 随机抽样的种子，相同种子抽到相同的单词；默认随机生成并打印
+    #[arg(long, requires = "random")]
+    pub seed: Option<u64>,
+    
+    /// 严格模式：有表格行被跳过、需要兜底正则或 LLM 响应无法解析时中止并报告详情，
+    /// 保证词书完整
+    #[arg(long)]
+    pub strict: bool,
+    
+    /// 跳过 the、of、and 等常见功能词
+    #[arg(long)]
+    pub stopwords: bool,
+    
+    /// 排除词表（每行一个或多个单词），其中的单词不会出现在输出中，隐含 --stopwords；
+    /// 也可以用环境变量 BBDC_STOPWORDS_FILE 设置
+    #[arg(long, value_name = "FILE", visible_alias = "exclude-list")]
+    pub exclude: Option<PathBuf>,
+    
+    /// 白名单（格式同排除词表），其中的单词总是保留，不受停用词、排除词表、
+    /// 长度和字符过滤的影响，如保留 ox 这类短词
+    #[arg(long, value_name = "FILE")]
+    pub include_list: Option<PathBuf>,
+    
+    /// 跳过少于 N 个字符的单词，如 2 可以去掉 OCR 产生的单个字母
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub min_length: usize,
+    
+    /// 跳过多于 N 个字符的单词（通常是粘连在一起的乱码）
+    #[arg(long, value_name = "N")]
+    pub max_length: Option<usize>,
+    
+    /// 只保留由 ASCII 字符组成的单词
+    #[arg(long)]
+    pub ascii_only: bool,
+    
+    /// 跳过含有数字的单词
+    #[arg(long)]
+    pub reject_digits: bool,
+    
+    /// Excel 工作表名称或序号（从 1 开始），默认第一个工作表
+    #[arg(long)]
+    pub sheet: Option<String>,
+    
+    /// Anki 牌组中作为单词的字段名或序号（从 1 开始）
+    #[arg(long, value_name = "FIELD", default_value = "1")]
+    pub anki_word: String,
+    
+    /// Anki 牌组中作为释义的字段名或序号（从 1 开始）
+    #[arg(long, value_name = "FIELD", default_value = "2")]
+    pub anki_meaning: String,
+    
+    /// Anki 牌组中作为音标的字段名或序号，默认不读取
+    #[arg(long, value_name = "FIELD")]
+    pub anki_phonetic: Option<String>,
+    
+    /// 把单词还原为原形（running、ran、runs → run）后合并重复项
+    #[arg(long)]
+    pub lemmatize: bool,
+    
+    /// 词形还原词表，每行为 `run -> ran,running,runs` 或单独的原形，默认读取环境变量 BBDC_LEMMA_FILE
+    #[arg(long, value_name = "FILE", requires = "lemmatize")]
+    pub lemma_list: Option<PathBuf>,
+    
+    /// 统一为美式（us）或英式（uk）拼写，如 colour → color、analyse → analyze
+    #[arg(long, value_enum, value_name = "VARIANT")]
+    pub spelling: Option<Spelling>,
+    
+    /// 补充的拼写映射表，每行为 `英式 -> 美式`（如 `programme -> program`）
+    #[arg(long, value_name = "FILE", requires = "spelling")]
+    pub spelling_map: Option<PathBuf>,
+    
+    /// 按分级词表标注 CEFR 等级或词频档位（with_meaning / full 模式输出），内置词表只含常见词
+    #[arg(long)]
+    pub level: bool,
+    
+    /// 分级词表，每行为单词和等级（如 `abandon B2` 或 `the,1`），隐含 --level；
+    /// 也可以用环境变量 BBDC_LEVEL_FILE 设置
+    #[arg(long, value_name = "FILE")]
+    pub level_list: Option<PathBuf>,
+    
+    /// 只保留不低于该等级的单词（如 B1 或档位 3），未标注的单词保留，隐含 --level
+    #[arg(long, value_name = "LEVEL")]
+    pub min_level: Option<Level>,
+    
+    /// 只保留不高于该等级的单词，隐含 --level
+    #[arg(long, value_name = "LEVEL")]
+    pub max_level: Option<Level>,
+    
+    /// 按等级从低到高排序，未标注的单词排在最后，隐含 --level
+    #[arg(long)]
+    pub sort_by_level: bool,
+    
+    /// 已掌握的单词（每行一个，可以是之前导出的文本或保存的 JSON 结果），只输出其中没有的新词；
+    /// 按原形匹配，也可以用环境变量 BBDC_KNOWN_FILE 设置
+    #[arg(long, value_name = "FILE")]
+    pub known: Option<PathBuf>,
+    
+    /// 用 LLM 按指定学习者水平（如 CET-4）评估单词难度，并按从易到难排序
+    #[arg(long, value_name = "LEVEL")]
+    pub difficulty: Option<String>,
+    
+    /// 另存简单、困难两册：难度不超过该值（1-5）的单词归入简单册
+    #[arg(long, value_name = "N", requires = "difficulty", value_parser = clap::value_parser!(u8).range(1..=5))]
+    pub split_difficulty: Option<u8>,
+    
+    /// 按 Markdown 章节（单词上方最近的 `#`/`##` 标题）另存每章一个文件，
+    /// 文件名为输出文件名加章节序号和标题，如 `words_01_Unit_1.txt`
+    #[arg(long)]
+    pub split_chapters: bool,
+    
+    /// 为缺少音标的单词补充 IPA：依次查询发音词典、eSpeak NG，最后由 LLM 生成
+    #[arg(long)]
+    pub ipa: bool,
+    
+    /// CMUdict 格式的发音词典，默认读取环境变量 BBDC_CMUDICT
+    #[arg(long, value_name = "FILE", requires = "ipa")]
+    pub cmudict: Option<PathBuf>,
+    
+    /// 找出拼写或发音相近的易混词，在报告和带释义的输出末尾追加「易混词」一节
+    #[arg(long)]
+    pub confusables: bool,
+    
+    /// 词书标题（支持 {name}、{file}、{date} 占位符）
+    #[arg(long, default_value = "{name}")]
+    pub title: String,
+    
+    /// 词书描述（支持占位符）
+    #[arg(long, default_value = "")]
+    pub description: String,
+    
+    /// 词书标签，可重复指定
+    #[arg(long = "tag")]
+    pub tags: Vec<String>,
+    
+    /// 词书作者
+    #[arg(long, default_value = "")]
+    pub author: String,
+    
+    /// 生成报告，扩展名为 .html 时生成网页，否则为 Markdown
+    #[arg(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+    
+    /// 完成后用默认浏览器（或关联的程序）打开报告
+    #[arg(long, requires = "report")]
+    pub open: bool,
+    
+    /// 报告中的单词渲染为链接，`{word}` 替换为单词；不带值时使用有道词典，
+    /// 也可以用环境变量 BBDC_WORD_LINK 设置
+    #[arg(long, value_name = "URL", num_args = 0..=1, default_missing_value = DEFAULT_LINK_TEMPLATE, requires = "report")]
+    pub link_template: Option<String>,
+    
+    /// 保存运行清单（JSON），可用 compare-runs 比较两次运行
+    #[arg(long, value_name = "FILE")]
+    pub manifest: Option<PathBuf>,
+    
+    /// 保存完整的提取结果（JSON），之后 check、correct 可以直接读取，不必重新解析原文
+    #[arg(long, value_name = "FILE")]
+    pub save_result: Option<PathBuf>,
+    
+    /// 追加模式：输出文件已存在时只把其中没有的单词和短语追加进去，
+    /// 反复处理不断增长的笔记文件时不会产生重复的词条
+    #[arg(long)]
+    pub append: bool,
+    
+    /// 按不背单词网页导入的格式保存：UTF-8 无 BOM、每行一个单词、不含词义，
+    /// 规范化 Unicode 并去掉空行和重复（不区分大小写），可以直接在网页上传
+    #[arg(long, conflicts_with = "append")]
+    pub bbdc_import: bool,
+    
+    /// PDF 来源时为单词标注页码，并在报告中附上识别失败单词所在页的表格截图
+    /// （读取 Mineru 输出的 content_list.json）
+    #[arg(long, requires = "report")]
+    pub thumbnails: bool,
+}
+
+impl ExtractArgs {
+    /// 按输入文件展开的词书元数据
+    fn metadata(&self) -> BookMetadata {
+        BookMetadata {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            tags: self.tags.clone(),
+            author: self.author.clone(),
+        }
+        .render(self.source_path())
+    }
+    
+    /// 用于命名输出文件和元数据的路径：网址取去掉查询和锚点后的最后一段
+    /// （`https://example.com/unit1.html?p=2` → `unit1.html`），其余为输入本身
+    fn source_path(&self) -> PathBuf {
+        let input = self.input.to_string_lossy();
+        if !word_extractor::is_url(&input) {
+            return self.input.clone();
+        }
+        let path = input.split(['?', '#']).next().unwrap_or_default();
+        let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+        PathBuf::from(path.trim_end_matches('/').rsplit('/').next().unwrap_or_default())
+    }
+}
+
+/// 可导出 JSON Schema 的公开类型
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SchemaKind {
+    Word,
+    ExtractResult,
+    CheckResult,
+    CheckDiff,
+    CorrectionResult,
+}
+
+impl SchemaKind {
+    fn schema(self) -> schemars::schema::RootSchema {
+        match self {
+            SchemaKind::Word => schemars::schema_for!(Word),
+            SchemaKind::ExtractResult => schemars::schema_for!(ExtractResult),
+            SchemaKind::CheckResult => schemars::schema_for!(CheckResult),
+            SchemaKind::CheckDiff => schemars::schema_for!(CheckDiff),
+            SchemaKind::CorrectionResult => schemars::schema_for!(CorrectionResult),
+        }
+    }
+}
+
+/// 近义词的处理方式
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SynonymAction {
+    /// 只列出近义词
+    Report,
+    /// 保留先出现的单词，把近义词的释义并入其中
+    Merge,
+    /// 在释义后互相标注近义词
+    Annotate,
+}
+
+#[derive(Subcommand)]
+pub enum CorrectionsCommands {
+    /// 把已确认的更正导出为 JSONL 微调/评测数据集
+    Export {
+        /// 数据集格式
+        #[arg(short, long, value_enum, default_value = "chat")]
+        format: DatasetFormat,
+        
+        /// 输出文件，缺省时输出到标准输出
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AuthCommands {
+    /// 从本地浏览器导入不背单词 Cookie（需启用 browser-cookies 特性）
+    ImportCookies {
+        /// 浏览器，目前只支持 Firefox（Chrome/Edge 的 Cookie 被系统加密，无法读取）
+        #[arg(short, long, value_enum, default_value = "firefox")]
+        browser: Browser,
+    },
+}
+
+impl Cli {
+    /// 运行CLI
+    pub fn run() -> Result<()> {
+        // 初始化日志
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+            .format_timestamp(None)
+            .init();
+        
+        // 加载环境变量
+        EnvLoader::init()?;
+        
+        let cli = Cli::parse();
+        let notify = cli.notify
+            || EnvLoader::get_optional("BBDC_NOTIFY").is_some_and(|v| matches!(v.as_str(), "1" | "true" | "yes"));
+        let name = cli.command.as_ref().and_then(Commands::long_running_name).filter(|_| notify);
+        
+        let started = std::time::Instant::now();
+        let result = Self::dispatch(cli);
+        if let Some(name) = name {
+            let elapsed = humanize::format_duration(started.elapsed(), Locale::current());
+            match &result {
+                Ok(()) => crate::notify::send("✅ 不背单词词书工具", &format!("{} 完成，用时 {}", name, elapsed)),
+                Err(e) => crate::notify::send("❌ 不背单词词书工具", &format!("{} 失败: {}", name, e)),
+            }
+        }
+        result
+    }
+    
+    /// 执行子命令，未指定子命令时进入交互模式
+    fn dispatch(cli: Cli) -> Result<()> {
+        match cli.command {
+            Some(Commands::Extract(args)) => {
+                Self::handle_extract(*args)?;
+            }
+            Some(Commands::Check(args)) => {
+                Self::handle_check(args)?;
+            }
+            Some(Commands::Schema { kind }) => {
+                Self::handle_schema(kind)?;
+            }
+            Some(Commands::Env { probe }) => {
+                Self::handle_env_check(probe)?;
+            }
+            Some(Commands::Serve { daemon, addr }) => {
+                Self::handle_serve(daemon, &addr)?;
+            }
+            Some(Commands::Submit { input, output, mode, check, lists }) => {
+                let job = JobQueue::open_default()?.submit(JobSpec { input, output, mode, check, lists })?;
+                println!("📥 已加入队列: {}", job.id);
+            }
+            Some(Commands::Jobs) => {
+                Self::handle_jobs()?;
+            }
+            Some(Commands::Correct { input, compare, json, variants }) => {
+                Self::handle_correct(input, compare, json, variants)?;
+            }
+            Some(Commands::Cluster { input, clusters, vectors, provider, mode, lists, output }) => {
+                Self::handle_cluster(input, clusters, vectors, provider, mode, lists, output)?;
+            }
+            Some(Commands::Synonyms { input, threshold, vectors, provider, action, lists, output }) => {
+                Self::handle_synonyms(input, threshold, vectors, provider, action, lists, output)?;
+            }
+            Some(Commands::Eval { dataset, json }) => {
+                Self::handle_eval(dataset, json)?;
+            }
+            Some(Commands::CompareRuns { before, after, json }) => {
+                Self::handle_compare_runs(before, after, json)?;
+            }
+            Some(Commands::CompareChecks { before, after, json }) => {
+                Self::handle_compare_checks(before, after, json)?;
+            }
+            Some(Commands::Corrections { action }) => {
+                Self::handle_corrections(action)?;
+            }
+            Some(Commands::Auth { action }) => {
+                Self::handle_auth(action)?;
+            }
+            None => {
+                // 交互模式
+                Self::interactive_mode(cli)?;
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 处理提取命令
+    fn handle_extract(args: ExtractArgs) -> Result<()> {
+        let started = std::time::Instant::now();
+        let metadata = args.metadata();
+        let source_path = args.source_path();
+        let ExtractArgs {
+            input,
+            output,
+            include_glob,
+            exclude_glob,
+            format,
+            unique,
+            auto_check,
+            mode,
+            lists,
+            columns,
+            split_cell,
+            column_groups,
+            header_marker,
+            word_policy,
+            casing,
+            frequency,
+            examples,
+            pos,
+            group_by_pos,
+            pattern,
+            no_fallback,
+            sample,
+            random,
+            seed,
+            strict,
+            stopwords,
+            exclude,
+            include_list,
+            min_length,
+            max_length,
+            ascii_only,
+            reject_digits,
+            sheet,
+            anki_word,
+            anki_meaning,
+            anki_phonetic,
+            lemmatize,
+            lemma_list,
+            spelling,
+            spelling_map,
+            level,
+            level_list,
+            min_level,
+            max_level,
+            sort_by_level,
+            known,
+            difficulty,
+            split_difficulty,
+            split_chapters,
+            ipa,
+            cmudict,
+            confusables,
+            report,
+            open,
+            link_template,
+            thumbnails,
+            manifest,
+            save_result,
+            append,
+            bbdc_import,
+            ..
+        } = args;
+
+        // 识别输入格式，目录、通配符和标准输入在提取时逐个识别，网址按 HTML 读取
+        let from_stdin = input.as_os_str() == "-";
+        let from_url = word_extractor::is_url(&input.to_string_lossy());
+        let is_batch = !from_stdin && !from_url && (input.is_dir() || word_extractor::is_glob(&input));
+        let format = match format {
+            Some(format) => Some(format),
+            None if from_url => Some(InputFormat::Html),
+            None if from_stdin || is_batch => None,
+            None => {
+                let detected = InputFormat::detect(&input)?;
+                log::info!("识别输入格式: {}", detected);
+                Some(detected)
+            }
+        };
+        let is_pdf = format == Some(InputFormat::Pdf);
+        
+        let markdown_file = if is_pdf {
+            println!("📄 检测到 PDF 文件，正在通过 Mineru API 处理...");
+            let client = bbdc_providers::MineruClient::new()?;
+            let out_dir = output.as_ref().and_then(|p| p.parent()).map(|p| p.to_path_buf());
+            client.process_pdf(&input, out_dir.as_ref(), true)?
+        } else {
+            input.clone()
+        };
+        
+        println!("📝 开始提取单词...");
+        
+        let include_phrases = mode.includes_phrases();
+        let fallback = if no_fallback {
+            None
+        } else {
+            let pattern = pattern
+                .or_else(|| EnvLoader::get_optional("BBDC_FALLBACK_PATTERN"))
+                .unwrap_or_else(|| DEFAULT_FALLBACK_PATTERN.to_string());
+            Some(WordExtractor::fallback_pattern(&pattern)?)
+        };
+        let extractor = WordExtractor::new(unique, include_phrases)
+            .with_lists(lists)
+            .with_columns(columns.unwrap_or_default().with_split(split_cell).with_groups(column_groups.into()))
+            .with_policy(word_policy)
+            .with_casing(casing)
+            .with_header_markers(Self::header_markers(header_marker))
+            .with_fallback(fallback)
+            .with_stopwords(Self::stopwords(stopwords, exclude.clone())?)
+            .with_filter(WordFilter { min_length, max_length, ascii_only, reject_digits })
+            .with_whitelist(Self::whitelist(include_list)?)
+            .with_strict(strict)
+            .with_examples(examples)
+            .with_pos_groups(group_by_pos)
+            .with_batch_filter(BatchFilter::new(&include_glob, &exclude_glob)?);
+        let exclude_given = exclude.is_some() || EnvLoader::get_optional("BBDC_STOPWORDS_FILE").is_some();
+        let mut result = if let Some(min_count) = frequency {
+            let content = if from_stdin { io::read_to_string(io::stdin())? } else { std::fs::read_to_string(&markdown_file)? };
+            let result = extractor.extract_by_frequency(&content, min_count);
+            let top: Vec<String> = result
+                .words
+                .iter()
+                .take(10)
+                .map(|w| format!("{}({})", w.word, w.frequency.unwrap_or_default()))
+                .collect();
+            if !top.is_empty() {
+                println!("📈 高频词: {}", top.join(", "));
+            }
+            result
+        } else if from_url {
+            println!("🌐 正在下载网页: {}", input.display());
+            extractor.extract_from_url(&input.to_string_lossy())?
+        } else if from_stdin {
+            match format {
+                Some(format) => extractor.extract_from_content(&io::read_to_string(io::stdin())?, format)?,
+                None => extractor.extract_from_reader(io::stdin().lock())?,
+            }
+        } else if is_batch {
+            let result = extractor.extract_from_dir(&input)?;
+            for (file, count) in result.words_per_file() {
+                println!("   📄 {}: {} 个单词", file, count);
+            }
+            result
+        } else {
+            match format {
+                Some(InputFormat::Xlsx) => extractor.extract_from_xlsx(&input, sheet.as_deref())?,
+                Some(InputFormat::Apkg) => {
+                    extractor.extract_from_apkg(&input, &AnkiFields { word: anki_word, meaning: anki_meaning, phonetic: anki_phonetic })?
+                }
+                Some(InputFormat::Pdf) | None => extractor.extract_as(&markdown_file, InputFormat::Markdown)?,
+                Some(format) => extractor.extract_as(&markdown_file, format)?,
+            }
+        };
+        
+        if strict {
+            result.ensure_complete()?;
+        }
+        
+        if !result.cleaned.is_empty() {
+            println!("🧹 清理了 {} 个带有标点、脚注或注释的单词", result.cleaned.len());
+            for cleanup in result.cleaned.iter().take(10) {
+                println!("   {} → {}", cleanup.original, cleanup.cleaned);
+            }
+            if result.cleaned.len() > 10 {
+                println!("   ...");
+            }
+        }
+        
+        if !result.skipped.is_empty() {
+            println!("⚠️  跳过了 {} 个可疑的表格行（报告中列出全部）", result.skipped.len());
+            for row in result.skipped.iter().take(10) {
+                println!("   {}（{}）", row.row, row.reason);
+            }
+            if result.skipped.len() > 10 {
+                println!("   ...");
+            }
+        }
+        
+        if result.words.is_empty() && (!include_phrases || result.phrases.is_empty()) {
+            println!("⚠️  没有从 {:?} 中提取到任何单词，不会写入文件或调用 API", input);
+            for hint in Self::empty_result_hints(&result, &input, frequency.is_some(), lists, stopwords || exclude_given) {
+                println!("💡 {}", hint);
+            }
+            return Err(Error::Empty(format!("{:?}", input)));
+        }
+        
+        if let Some(spelling) = spelling {
+            let mut normalizer = SpellingNormalizer::new(spelling);
+            if let Some(path) = spelling_map {
+                normalizer = normalizer.load_mapping(&path)?;
+            }
+            let converted = result.normalize_spelling(&normalizer);
+            println!("🔤 统一为{}拼写，转换了 {} 个单词", spelling, converted);
+        }
+        
+        if lemmatize {
+            let mut lemmatizer = Lemmatizer::new();
+            if let Some(path) = lemma_list.or_else(|| EnvLoader::get_optional("BBDC_LEMMA_FILE").map(PathBuf::from)) {
+                lemmatizer = lemmatizer.load_wordlist(&path)?;
+            }
+            let merged = result.lemmatize(&lemmatizer);
+            println!("🌱 词形还原合并了 {} 个屈折形式", merged);
+        }
+        
+        if let Some(path) = known.or_else(|| EnvLoader::get_optional("BBDC_KNOWN_FILE").map(PathBuf::from)) {
+            let known = KnownWords::load(&path)?;
+            let removed = result.remove_known(&known);
+            println!("📚 去掉了 {} 个已掌握的单词，剩余 {} 个新词", removed, result.total_words);
+            if result.words.is_empty() && (!include_phrases || result.phrases.is_empty()) {
+                println!("🎉 {:?} 中没有新单词", input);
+                return Err(Error::Empty(format!("{:?}", input)));
+            }
+        }
+        
+        let level_list = level_list.or_else(|| EnvLoader::get_optional("BBDC_LEVEL_FILE").map(PathBuf::from));
+        if level || level_list.is_some() || min_level.is_some() || max_level.is_some() || sort_by_level {
+            let mut list = LevelList::new();
+            if let Some(path) = &level_list {
+                list = list.load_wordlist(path)?;
+            }
+            let tagged = result.tag_levels(&list);
+            println!("🎚️  标注了 {}/{} 个单词的等级", tagged, result.total_words);
+            if min_level.is_some() || max_level.is_some() {
+                let removed = result.retain_levels(min_level, max_level);
+                println!("   按等级去掉了 {} 个单词", removed);
+            }
+            if sort_by_level {
+                result.sort_by_level();
+            }
+        }
+        
+        if let Some(n) = sample {
+            let total = result.total_words;
+            let seed = random.then(|| {
+                seed.unwrap_or_else(|| {
+                    std::time::SystemTime::now()
+                        .duration_since(std::time::UNIX_EPOCH)
+                        .map(|d| d.as_nanos() as u64)
+                        .unwrap_or_default()
+                })
+            });
+            result.sample(n, seed);
+            match seed {
+                Some(seed) => println!("🎲 随机抽取 {}/{} 个单词（--seed {}）", result.total_words, total, seed),
+                None => println!("🎲 抽取前 {}/{} 个单词", result.total_words, total),
+            }
+        }
+        
+        println!("✅ 提取完成！");
+        println!("   单词数: {}", result.total_words);
+        if include_phrases {
+            println!("   短语数: {}", result.total_phrases);
+        }
+        
+        // 确定输出文件名
+        let output_file = match output {
+            Some(output) => output,
+            None => Self::default_output(&source_path, mode.output_suffix())?,
+        };
+        
+        let export = Self::export_extension(&output_file);
+        if let (true, Some(ext)) = (append, export) {
+            return Err(Error::Other(format!("追加模式不支持 .{} 输出", ext)));
+        }
+        
+        // 追加模式：去掉输出文件中已有的条目，之后的步骤只处理新增的单词
+        let appending = append && output_file.exists();
+        if appending {
+            let existing = KnownWords::load(&output_file)?.exact();
+            let removed = result.remove_known(&existing);
+            println!("➕ 追加模式：{} 个条目已在 {:?} 中，新增 {} 个单词", removed, output_file, result.total_words);
+            if result.words.is_empty() && (!include_phrases || result.phrases.is_empty()) {
+                println!("✅ 没有新单词，{:?} 已是最新", output_file);
+                return Ok(());
+            }
+        }
+        
+        // 难度评估
+        if let Some(level) = &difficulty {
+            let llm = LLMCorrector::new()?.with_strict(strict);
+            if llm.is_enabled() {
+                println!("\n📊 正在按 {} 水平评估难度...", level);
+                let rated = llm.rate_words(&mut result.words, level)?;
+                result.sort_by_difficulty();
+                println!("✅ 已评估 {}/{} 个单词，按从易到难排序", rated, result.total_words);
+            } else {
+                log::warn!("⚠️  LLM 未启用，跳过难度评估");
+            }
+        }
+        
+        // 页码来源
+        let pages = if thumbnails {
+            let dir = markdown_file.parent().filter(|d| !d.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+            let pages = bbdc_core::PageIndex::find(dir)?;
+            match &pages {
+                Some(index) => println!("📑 标注了 {} 个单词的页码", bbdc_core::pages::fill_pages(&mut result.words, index)),
+                None => log::warn!("⚠️  {:?} 中没有 Mineru 的 content_list.json，跳过原文截图", dir),
+            }
+            pages
+        } else {
+            None
+        };
+        
+        // 词性
+        if pos || group_by_pos {
+            let tagged = bbdc_core::pos::fill_pos(&mut result.words, true);
+            println!("🏷️  标注了 {}/{} 个单词的词性", tagged, result.total_words);
+        }
+        
+        // 补充音标
+        if ipa {
+            Self::fill_phonetics(&mut result.words, cmudict, strict)?;
+        }
+        
+        // 保存文件
+        let json_output = Self::json_extension(&output_file);
+        let save = |result: &ExtractResult, path: &std::path::Path| match json_output {
+            _ if bbdc_import => {
+                let written = extractor.save_bbdc_import(&result.words, path)?;
+                println!("📤 不背单词导入格式：{} 个单词", written);
+                Ok(())
+            }
+            _ if export == Some("apkg") => extractor.save_apkg(&result.words, &metadata, path),
+            _ if export == Some("xlsx") => extractor.save_xlsx(&result.words, path),
+            Some("json") => extractor.save_json(&result.words, path),
+            Some(_) => extractor.save_jsonl(&result.words, path),
+            None if mode == ExtractMode::WordsOnly => extractor.save_words_only(&result.words, path),
+            None => extractor.save_with_meaning(result, path),
+        };
+        if appending {
+            match json_output {
+                Some("json") => extractor.append_json(&result.words, &output_file)?,
+                Some(_) => extractor.append_jsonl(&result.words, &output_file)?,
+                None if mode == ExtractMode::WordsOnly => extractor.append_words_only(&result.words, &output_file)?,
+                None => extractor.append_with_meaning(&result, &output_file)?,
+            }
+            println!("💾 已追加到: {:?}（{}）", output_file, Self::file_size(&output_file));
+        } else {
+            save(&result, &output_file)?;
+            println!("💾 已保存到: {:?}（{}）", output_file, Self::file_size(&output_file));
+        }
+        if let Some(path) = &save_result {
+            result.save(path)?;
+            println!("📦 提取结果已保存到: {:?}", path);
+        }
+        
+        // 易混词
+        let confusable_pairs = if confusables {
+            let pairs = bbdc_core::confusables::confusable_pairs(&result.words);
+            println!("\n🔀 找到 {} 对易混词", pairs.len());
+            for pair in pairs.iter().take(10) {
+                println!("   {} / {}（{}）", pair.first, pair.second, pair.reason());
+            }
+            if pairs.len() > 10 {
+                println!("   ...");
+            }
+            // 只含单词的输出用于上传，JSON 输出供程序读取，都不追加额外内容；追加模式下文件中可能已有易混词段落
+            if mode != ExtractMode::WordsOnly && json_output.is_none() && export.is_none() && !appending && !pairs.is_empty() {
+                let mut file = std::fs::OpenOptions::new().append(true).open(&output_file)?;
+                std::io::Write::write_all(&mut file, bbdc_core::confusables::markdown_section(&pairs, str::to_string).as_bytes())?;
+            }
+            pairs
+        } else {
+            Vec::new()
+        };
+        
+        if let Some(max_easy) = split_difficulty {
+            let (easy, hard) = result.split_by_difficulty(max_easy);
+            for (volume, name) in [(&easy, "easy"), (&hard, "hard")] {
+                let path = Self::volume_path(&output_file, name);
+                save(volume, &path)?;
+                println!("💾 {} 个单词已保存到: {:?}（{}）", volume.total_words, path, Self::file_size(&path));
+            }
+        }
+        
+        if split_chapters {
+            let volumes = result.split_by_chapter();
+            if volumes.iter().all(|(chapter, _)| chapter.is_none()) {
+                log::warn!("⚠️  没有找到 # 或 ## 标题，不按章节拆分");
+            } else {
+                for (i, (chapter, volume)) in volumes.iter().enumerate() {
+                    let name = Self::chapter_volume_name(i + 1, *chapter);
+                    let path = Self::volume_path(&output_file, &name);
+                    save(volume, &path)?;
+                    println!("💾 {}（{} 个单词）已保存到: {:?}", chapter.unwrap_or("无章节"), volume.total_words, path);
+                    if let (true, Some(title)) = (bbdc_import, chapter) {
+                        println!("   在不背单词创建词书时可命名为: {}", metadata.for_chapter(title).title);
+                    }
+                    if let Some(report_path) = &report {
+                        let path = Self::save_chapter_report(&metadata, *chapter, volume, report_path, &name)?;
+                        println!("📄 章节报告已保存到: {:?}", path);
+                    }
+                }
+            }
+        }
+        
+        // 自动核对
+        let mut check_result = None;
+        let mut corrections = Vec::new();
+        let mut model = None;
+        let backend = CheckBackend::from_env()?;
+        if auto_check
+            && mode == ExtractMode::WordsOnly
+            && (backend != CheckBackend::Bbdc || services::available(&services::BbdcService::from_env(), "自动核对"))
+        {
+            println!("\n🔍 开始自动核对...");
+            let checker = Self::build_backend(backend)?;
+            let extracted = &result;
+            let result = if json_output.is_some() || export.is_some() {
+                checker.check_word_structs(&extracted.words)?
+            } else {
+                checker.check_words_file(&output_file)?
+            };
+            
+            Self::print_check_result(&result);
+            let input_name = (!from_stdin).then(|| markdown_file.display().to_string());
+            Self::print_locations(&result, &extracted.words, input_name.as_deref());
+            
+            // LLM 自动更正
+            if result.unrecognized_count > 0 && services::available(&services::SiliconFlowService::from_env(), "LLM 自动更正") {
+                let llm = LLMCorrector::new()?.with_strict(strict);
+                if llm.is_enabled() {
+                    println!("\n🤖 开始 LLM 自动更正...");
+                    corrections = Self::handle_llm_correction(&result, &llm)?
+                        .into_iter()
+                        .map(|corr| {
+                            let mut record = CorrectionRecord::new(&corr.original, &corr.corrected, corr.confidence);
+                            record.source = llm.model().to_string();
+                            record.reason = corr.reason;
+                            record
+                        })
+                        .collect();
+                    model = Some(llm.model().to_string());
+                }
+            }
+            
+            Self::print_source_breakdown(&result.by_source(extracted.word_files()));
+            check_result = Some(result);
+        }
+        
+        // 生成报告
+        if let Some(report_path) = report {
+            let link_template = link_template.or_else(|| EnvLoader::get_optional("BBDC_WORD_LINK"));
+            let mut report = Report::new(&metadata)
+                .with_extract(&result)
+                .with_corrections(&corrections)
+                .with_confusables(&confusable_pairs)
+                .with_elapsed(started.elapsed());
+            if let Some(check) = &check_result {
+                report = report.with_check(check);
+            }
+            if let Some(template) = &link_template {
+                report = report.with_link_template(template);
+            }
+            if let Some(index) = &pages {
+                report = report.with_pages(index);
+            }
+            report.save(&report_path)?;
+            println!("📄 报告已保存到: {:?}", report_path);
+            
+            if open {
+                if let Err(e) = opener::open(&report_path) {
+                    log::warn!("⚠️  无法打开报告: {}", e);
+                }
+            }
+        }
+        
+        if let Some(path) = manifest {
+            let mut run = RunManifest::new(&input, &output_file, &result, started.elapsed())
+                .with_metadata(&metadata)
+                .with_args(std::env::args().skip(1).collect())
+                .with_corrections(&corrections);
+            if let Some(check) = &check_result {
+                run = run.with_check(check);
+            }
+            if let Some(model) = &model {
+                run = run.with_model(model);
+            }
+            run.save(&path)?;
+            println!("🧾 运行清单已保存到: {:?}", path);
+        }
+        
+        println!("\n⏱️  用时 {}", humanize::format_duration(started.elapsed(), Locale::current()));
+        Ok(())
+    }
+    
+    /// 没有提取到单词时的排查建议
+    fn empty_result_hints(result: &ExtractResult, input: &std::path::Path, frequency: bool, lists: bool, stopwords: bool) -> Vec<String> {
+        let mut hints = Vec::new();
+        if !result.skipped.is_empty() {
+            hints.push(format!(
+                "有 {} 行表格格式不正确被跳过（如 {}），表格布局不是「序号、单词、词义」时用 --columns 指定列",
+                result.skipped.len(),
+                result.skipped[0].reason
+            ));
+        }
+        if frequency {
+            hints.push("按词频提取时没有单词达到最低次数，试试更小的 --frequency 值".to_string());
+        } else {
+            let is_markdown = input.extension().and_then(|e| e.to_str()).is_some_and(|e| ["md", "markdown"].contains(&e.to_lowercase().as_str()));
+            if result.skipped.is_empty() && (is_markdown || input.as_os_str() == "-" || input.is_dir()) {
+                hints.push("没有找到表格：确认单词在 Markdown 或 HTML 表格中".to_string());
+                if !lists {
+                    hints.push("单词写成列表或“单词 — 释义”形式时加上 --lists".to_string());
+                }
+                hints.push("编号行格式特殊时用 --pattern 指定正则".to_string());
+            }
+            hints.push("单词和释义写在同一个单元格时用 --split-cell 指定分隔符，如 --split-cell newline".to_string());
+            hints.push("没有结构的正文（文章、字幕）可以用 --frequency 2 按词频提取".to_string());
+            hints.push("格式识别有误时用 --format 指定，如 --format csv".to_string());
+        }
+        if stopwords {
+            hints.push("单词可能都被停用词或排除词表过滤掉了，去掉 --stopwords / --exclude 再试".to_string());
+        }
+        hints
+    }
+    
+    /// 比较两次运行的清单
+    fn handle_compare_runs(before: PathBuf, after: PathBuf, json: bool) -> Result<()> {
+        let (before, after) = (RunManifest::load(&before)?, RunManifest::load(&after)?);
+        let diff = bbdc_core::manifest::compare(&before, &after);
+        
+        if json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+            return Ok(());
+        }
+        
+        let locale = Locale::current();
+        let duration = |ms: u64| humanize::format_duration(std::time::Duration::from_millis(ms), locale);
+        let rate = |r: Option<f64>| r.map_or("-".to_string(), |r| format!("{:.1}%", r * 100.0));
+        let model = |m: &Option<String>| m.clone().unwrap_or_else(|| "-".to_string());
+        println!("{:<12} {:>24} {:>24}", "", "前一次", "后一次");
+        println!("{:<12} {:>24} {:>24}", "版本", before.version, after.version);
+        println!("{:<12} {:>24} {:>24}", "模型", model(&diff.model.0), model(&diff.model.1));
+        println!("{:<12} {:>24} {:>24}", "单词数", before.words.len(), after.words.len());
+        println!("{:<12} {:>24} {:>24}", "识别成功率", rate(diff.recognition_rate.0), rate(diff.recognition_rate.1));
+        println!("{:<12} {:>24} {:>24}", "用时", duration(diff.elapsed_ms.0), duration(diff.elapsed_ms.1));
+        
+        let list = |title: &str, words: &[String]| {
+            if !words.is_empty() {
+                println!("\n{}（{}）: {}", title, words.len(), words.join(", "));
+            }
+        };
+        list("➕ 新增单词", &diff.added_words);
+        list("➖ 缺少单词", &diff.removed_words);
+        list("❌ 新增识别失败", &diff.newly_unrecognized);
+        list("✅ 新增识别成功", &diff.newly_recognized);
+        if !diff.changed_corrections.is_empty() {
+            println!("\n🔀 更正建议变化（{}）:", diff.changed_corrections.len());
+            for change in &diff.changed_corrections {
+                let show = |s: &Option<String>| s.clone().unwrap_or_else(|| "-".to_string());
+                println!("  {}: {} → {}", change.original, show(&change.before), show(&change.after));
+            }
+        }
+        
+        if diff.is_identical() {
+            println!("\n✅ 两次运行的结果相同");
+        }
+        Ok(())
+    }
+    
+    /// 比较两次核对结果
+    fn handle_compare_checks(before: PathBuf, after: PathBuf, json: bool) -> Result<()> {
+        let diff = CheckResult::load(&before)?.diff(&CheckResult::load(&after)?);
+        
+        if json {
+            println!("{}", serde_json::to_string_pretty(&diff)?);
+            return Ok(());
+        }
+        
+        let rate = |recognized: usize, total: usize| {
+            if total == 0 { "-".to_string() } else { format!("{:.1}%", recognized as f64 / total as f64 * 100.0) }
+        };
+        println!("{:<12} {:>12} {:>12}", "", "前一次", "后一次");
+        println!("{:<12} {:>12} {:>12}", "单词数", diff.total_count.0, diff.total_count.1);
+        println!("{:<12} {:>12} {:>12}", "识别成功", diff.recognized_count.0, diff.recognized_count.1);
+        println!(
+            "{:<12} {:>12} {:>12}",
+            "识别成功率",
+            rate(diff.recognized_count.0, diff.total_count.0),
+            rate(diff.recognized_count.1, diff.total_count.1)
+        );
+        
+        let list = |title: &str, words: &[String]| {
+            if !words.is_empty() {
+                println!("\n{}（{}）: {}", title, words.len(), words.join(", "));
+            }
+        };
+        list("✅ 变为识别成功", &diff.newly_recognized);
+        list("❌ 变为识别失败", &diff.newly_unrecognized);
+        list("➕ 新增单词（识别成功）", &diff.added_recognized);
+        list("➕ 新增单词（识别失败）", &diff.added_unrecognized);
+        list("➖ 不再核对的单词", &diff.removed);
+        list("⚠️  仍然识别失败", &diff.still_unrecognized);
+        
+        if diff.is_identical() {
+            println!("\n✅ 两次核对的结果相同");
+        }
+        Ok(())
+    }
+    
+    /// 为缺少音标的单词补充 IPA：发音词典 → eSpeak NG → LLM
+    fn fill_phonetics(words: &mut [Word], cmudict: Option<PathBuf>, strict: bool) -> Result<()> {
+        println!("\n🔤 正在补充音标...");
+        let missing = words.iter().filter(|w| w.phonetic.is_none()).count();
+        
+        let cmudict = cmudict.or_else(|| EnvLoader::get_optional("BBDC_CMUDICT").map(PathBuf::from));
+        if let Some(path) = cmudict {
+            let dict = PronouncingDictionary::load(&path)?;
+            let filled = phonetics::fill_phonetics(words, |w| dict.lookup(w).map(str::to_string));
+            println!("   发音词典: {} 个", filled);
+        }
+        
+        let filled = phonetics::fill_phonetics(words, phonetics::espeak_ipa);
+        if filled > 0 {
+            println!("   eSpeak NG: {} 个", filled);
+        }
+        
+        if words.iter().any(|w| w.phonetic.is_none()) {
+            let llm = LLMCorrector::new()?.with_strict(strict);
+            if llm.is_enabled() {
+                let mut failure = None;
+                let filled = phonetics::fill_phonetics(words, |w| {
+                    if failure.is_some() {
+                        return None;
+                    }
+                    llm.phonetic(w).unwrap_or_else(|e| {
+                        log::warn!("⚠️  生成 {} 的音标失败: {}", w, e);
+                        failure = strict.then_some(e);
+                        None
+                    })
+                });
+                if let Some(e) = failure {
+                    return Err(e);
+                }
+                println!("   LLM: {} 个", filled);
+            }
+        }
+        
+        let remaining = words.iter().filter(|w| w.phonetic.is_none()).count();
+        println!("✅ 已补充 {}/{} 个缺少音标的单词", missing - remaining, missing);
+        if remaining > 0 {
+            log::warn!("⚠️  {} 个单词仍缺少音标", remaining);
+        }
+        Ok(())
+    }
+    
+    /// 处理核对命令
+    fn handle_check(args: CheckArgs) -> Result<()> {
+        let CheckArgs {
+            inputs,
+            json,
+            backend,
+            chunk_size,
+            concurrency,
+            offline_wordlist,
+            no_cache,
+            no_resume,
+            force,
+            save_lists,
+            save_result,
+            variants,
+        } = args;
+        if !json {
+            println!("🔍 开始核对单词...");
+        }
+        
+        let results = match backend.map_or_else(CheckBackend::from_env, Ok)? {
+            CheckBackend::Bbdc => {
+                let mut checker = Self::build_checker(no_cache, no_resume)?
+                    .with_variant_recheck(variants)
+                    .with_skip_unchanged(!force);
+                if let Some(chunk_size) = chunk_size {
+                    checker = checker.with_chunk_size(chunk_size);
+                }
+                if let Some(concurrency) = concurrency {
+                    checker = checker.with_concurrency(concurrency)?;
+                }
+                if let Some(path) = offline_wordlist {
+                    checker = checker.with_offline_fallback(OfflineDictionary::load(path)?);
+                }
+                let results = Self::check_inputs(&checker, &inputs, json, save_lists)?;
+                if let (Some(cache), false) = (checker.cache(), json) {
+                    println!("🗃️  缓存命中 {} 个单词，未重新上传（缓存共 {} 个单词: {:?}）", cache.hits(), cache.len(), cache.path());
+                }
+                results
+            }
+            CheckBackend::Wordlist => {
+                let dictionary = match offline_wordlist {
+                    Some(path) => OfflineDictionary::load(path)?,
+                    None => CheckBackend::wordlist_from_env()?,
+                };
+                Self::check_inputs(&dictionary, &inputs, json, save_lists)?
+            }
+        };
+        
+        let save = |result: &CheckResult| -> Result<()> {
+            if let Some(path) = &save_result {
+                result.save(path)?;
+                if !json {
+                    println!("📦 核对结果已保存到: {:?}", path);
+                }
+            }
+            Ok(())
+        };
+        
+        let [result] = results.as_slice() else {
+            let overall = CheckResult::merge(&results);
+            let mut by_source: Vec<SourceCheck> = inputs
+                .iter()
+                .zip(&results)
+                .map(|(input, result)| SourceCheck::new(&input.display().to_string(), result))
+                .collect();
+            by_source.sort_by(|a, b| a.recognition_rate().total_cmp(&b.recognition_rate()));
+            if json {
+                println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "overall": overall, "by_source": by_source }))?);
+            } else {
+                Self::print_check_result(&overall);
+                Self::print_source_breakdown(&by_source);
+            }
+            return save(&overall);
+        };
+        
+        if json {
+            println!("{}", serde_json::to_string_pretty(result)?);
+        } else {
+            Self::print_check_result(result);
+        }
+        save(result)?;
+        
+        Ok(())
+    }
+    
+    /// 输出 JSON Schema
+    fn handle_schema(kind: Option<SchemaKind>) -> Result<()> {
+        let output = match kind {
+            Some(kind) => serde_json::to_value(kind.schema())?,
+            None => {
+                let schemas: serde_json::Map<String, serde_json::Value> = SchemaKind::value_variants()
+                    .iter()
+                    .map(|kind| {
+                        let name = kind.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default();
+                        serde_json::to_value(kind.schema()).map(|schema| (name, schema))
+                    })
+                    .collect::<std::result::Result<_, _>>()?;
+                serde_json::Value::Object(schemas)
+            }
+        };
+        
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        Ok(())
+    }
+    
+    /// 处理任务队列
+    fn handle_serve(daemon: bool, addr: &str) -> Result<()> {
+        let queue = JobQueue::open_default()?;
+        if daemon {
+            return crate::daemon::serve(queue, addr, ApiConfig::from_env()?);
+        }
+        
+        let recovered = queue.recover()?;
+        if recovered > 0 {
+            println!("♻️  恢复了 {} 个中断的任务", recovered);
+        }
+        let processed = queue.process_ready()?;
+        println!("✅ 已处理 {} 个任务", processed);
+        Ok(())
+    }
+    
+    /// 列出任务队列
+    fn handle_jobs() -> Result<()> {
+        let jobs = JobQueue::open_default()?.list()?;
+        if jobs.is_empty() {
+            println!("📭 队列为空");
+        }
+        for job in jobs {
+            println!("{}  {:<8}  第 {} 次  {:?}", job.id, format!("{:?}", job.status), job.attempts, job.spec.input);
+            if let Some(error) = &job.last_error {
+                println!("    ⚠️  {}", error);
+            }
+        }
+        Ok(())
+    }
+    
+    /// 处理登录态命令
+    fn handle_auth(action: AuthCommands) -> Result<()> {
+        match action {
+            AuthCommands::ImportCookies { browser } => {
+                println!("🍪 正在从 {:?} 导入不背单词 Cookie...", browser);
+                
+                let cookies = bbdc_providers::auth::import_browser_cookies(browser)?;
+                let path = CookieStore::save(&cookies)?;
+                
+                println!("✅ 已导入 {} 个 Cookie:", cookies.len());
+                for cookie in &cookies {
+                    println!("   {}", cookie.name);
+                }
+                println!("💾 已保存到: {:?}", path);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 处理环境检查
+    fn handle_env_check(probe: bool) -> Result<()> {
+        println!("🔍 检查环境配置...\n");
+        
+        let (_, path) = EnvLoader::check_env_file();
+        
+        if let Some(path) = path {
+            println!("✅ 找到 .env 文件: {:?}", path);
+        } else {
+            println!("❌ 未找到 .env 文件");
+            println!("💡 请在以下位置之一创建 .env 文件：");
+            println!("   - 可执行文件所在目录");
+            println!("   - 当前工作目录");
+        }
+        
+        println!("\n外部服务状态：");
+        
+        for service in services::all() {
+            let status = if probe {
+                services::ServiceStatus::probe(service.as_ref())
+            } else {
+                services::ServiceStatus::configured(service.as_ref())
+            };
+            let quota = status.quota.as_ref().map(|q| format!("，余额 {}", q)).unwrap_or_default();
+            match &status.health {
+                Some(health) => {
+                    let icon = if health.is_healthy() { "✅" } else { "❌" };
+                    println!("{} {}: {}{}", icon, status.name, health, quota);
+                }
+                None if status.configured => println!("✅ {}: 已配置", status.name),
+                None => println!("❌ {}: 未配置", status.name),
+            }
+        }
+        if !probe {
+            println!("💡 加上 --probe 访问各服务检查是否可用并查询余额");
+        }
+        
+        println!("\n环境变量状态：");
+        
+        // 检查其他配置
+        if let Ok(url) = EnvLoader::get("SILICONFLOW_BASE_URL", Some("default")) {
+            println!("   SILICONFLOW_BASE_URL: {}", url);
+        }
+        
+        if let Ok(model) = EnvLoader::get("SILICONFLOW_MODEL", Some("default")) {
+            println!("   SILICONFLOW_MODEL: {}", model);
+        }
+        
+        // 检查不背单词登录态
+        if CookieStore::load_header().is_some() {
+            println!("✅ 不背单词 Cookie: 已设置");
+        } else {
+            println!("   不背单词 Cookie: 未设置（可用 auth import-cookies 导入）");
+        }
+        
+        Ok(())
+    }
+    
+    /// 交互模式
+    fn interactive_mode(cli: Cli) -> Result<()> {
+        println!("\n{}", "=".repeat(60));
+        println!("           📚 单词提取工具 - Word Extractor");
+        println!("           支持 PDF 和 Markdown 文件");
+        println!("{}\n", "=".repeat(60));
+        
+        // 获取输入文件
+        let input_file = if let Some(input) = cli.input {
+            input
+        } else {
+            println!("📂 请输入文件路径（PDF 或 Markdown）:");
+            let mut input = String::new();
+            io::stdin().read_line(&mut input)?;
+            PathBuf::from(input.trim().trim_matches('"'))
+        };
+        
+        if !input_file.exists() {
+            return Err(Error::FileRead(io::Error::new(io::ErrorKind::NotFound, format!("文件不存在: {:?}", input_file))));
+        }
+        
+        // 检查是否是 PDF
+        let is_pdf = input_file.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase() == "pdf")
+            .unwrap_or(false);
+        
+        let markdown_file = if is_pdf {
+            println!("\n📄 检测到 PDF 文件，正在通过 Mineru API 处理...");
+            let client = bbdc_providers::MineruClient::new()?;
+            let out_dir = cli.output.as_ref().and_then(|p| p.parent()).map(|p| p.to_path_buf());
+            client.process_pdf(&input_file, out_dir.as_ref(), true)?
+        } else {
+            input_file.clone()
+        };
+        
+        // 确定输出文件
+        let output_file = match cli.output {
+            Some(output) => output,
+            None => Self::default_output(&input_file, ExtractMode::WordsOnly.output_suffix())?,
+        };
+        
+        println!("\n🔄 正在提取单词...");
+        
+        let extractor = WordExtractor::new(cli.unique, cli.include_phrases);
+        let result = extractor.extract_from_file(&markdown_file)?;
+        
+        println!("✅ 提取完成！共 {} 个单词", result.total_words);
+        
+        extractor.save_words_only(&result.words, &output_file)?;
+        println!("💾 已保存到: {:?}", output_file);
+        
+        // 自动核对
+        if cli.auto_check {
+            println!("\n🔍 正在自动核对...");
+            let checker = Self::build_backend(CheckBackend::from_env()?)?;
+            let check_result = checker.check_words_file(&output_file)?;
+            
+            Self::print_check_result(&check_result);
+        }
+        
+        println!("\n✨ 完成！");
+        
+        Ok(())
+    }
+    
+    /// 根据输入文件名生成默认输出文件名
+    fn default_output(input: &std::path::Path, suffix: &str) -> Result<PathBuf> {
+        if input.as_os_str() == "-" {
+            return Ok(PathBuf::from(format!("stdin{}", suffix)));
+        }
+        // 通配符取第一个通配符之前的目录名
+        let input = match input.to_str().and_then(|p| p.find(['*', '?']).map(|i| &p[..i])) {
+            Some(literal) => std::path::Path::new(literal.trim_end_matches(['/', '\\'])),
+            None => input,
+        };
+        let base = input
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .ok_or_else(|| Error::Other(format!("无法从输入路径确定输出文件名: {:?}", input)))?;
+        Ok(PathBuf::from(format!("{}{}", base, suffix)))
+    }
+    
+    /// 内置表头标记加上命令行和环境变量中的标记
+    fn header_markers(mut extra: Vec<String>) -> HashSet<String> {
+        if let Some(markers) = EnvLoader::get_optional("BBDC_HEADER_MARKERS") {
+            extra.extend(markers.split(',').map(str::to_string));
+        }
+        WordExtractor::header_markers(&extra)
+    }
+    
+    /// 提取时跳过的停用词：未启用时为空
+    fn stopwords(enabled: bool, exclude: Option<PathBuf>) -> Result<HashSet<String>> {
+        let exclude = exclude.or_else(|| EnvLoader::get_optional("BBDC_STOPWORDS_FILE").map(PathBuf::from));
+        let exclusions = match &exclude {
+            Some(path) => Some(std::fs::read_to_string(path)?),
+            None if enabled => None,
+            None => return Ok(HashSet::new()),
+        };
+        let stopwords = WordExtractor::stopwords(exclusions.as_deref());
+        println!("🚫 停用词: {} 个", stopwords.len());
+        Ok(stopwords)
+    }
+    
+    /// 白名单中的单词：未指定时为空
+    fn whitelist(include_list: Option<PathBuf>) -> Result<HashSet<String>> {
+        let Some(path) = include_list else { return Ok(HashSet::new()) };
+        let whitelist = WordExtractor::word_list(&std::fs::read_to_string(path)?);
+        println!("✅ 白名单: {} 个", whitelist.len());
+        Ok(whitelist)
+    }
+    
+    /// 文件大小的可读文本，读取失败时为空
+    fn file_size(path: &std::path::Path) -> String {
+        std::fs::metadata(path)
+            .map(|m| humanize::format_size(m.len(), Locale::current()))
+            .unwrap_or_default()
+    }
+    
+    /// 输出文件的扩展名为 `json` 或 `jsonl`（不区分大小写）时返回该扩展名，此时保存单词的全部字段
+    fn json_extension(output: &std::path::Path) -> Option<&'static str> {
+        let ext = output.extension()?.to_str()?;
+        ["json", "jsonl"].into_iter().find(|json| ext.eq_ignore_ascii_case(json))
+    }
+    
+    /// 输出文件的扩展名为 `apkg` 或 `xlsx`（不区分大小写）时返回该扩展名，此时导出为 Anki 牌组或 Excel 工作簿
+    fn export_extension(output: &std::path::Path) -> Option<&'static str> {
+        let ext = output.extension()?.to_str()?;
+        ["apkg", "xlsx"].into_iter().find(|export| ext.eq_ignore_ascii_case(export))
+    }
+    
+    /// 分册文件名：在输出文件名后加上 `_<name>`
+    fn volume_path(output: &std::path::Path, name: &str) -> PathBuf {
+        let stem = output.file_stem().and_then(|s| s.to_str()).unwrap_or_default();
+        let file_name = match output.extension().and_then(|e| e.to_str()) {
+            Some(ext) => format!("{}_{}.{}", stem, name, ext),
+            None => format!("{}_{}", stem, name),
+        };
+        output.with_file_name(file_name)
+    }
+    
+    /// 按章节拆分时为一章生成报告，文件名与该章的单词文件对应；标题和标签取自章节，
+    /// 见 [`BookMetadata::for_chapter`]
+    fn save_chapter_report(
+        metadata: &BookMetadata,
+        chapter: Option<&str>,
+        volume: &ExtractResult,
+        report_path: &std::path::Path,
+        name: &str,
+    ) -> Result<PathBuf> {
+        let metadata = chapter.map_or_else(|| metadata.clone(), |title| metadata.for_chapter(title));
+        let path = Self::volume_path(report_path, name);
+        Report::new(&metadata).with_extract(volume).save(&path)?;
+        Ok(path)
+    }
+    
+    /// 章节文件名：两位序号加标题，标题中的空白和标点替换为下划线
+    fn chapter_volume_name(index: usize, chapter: Option<&str>) -> String {
+        let title: Vec<&str> = chapter.unwrap_or("untitled").split(|c: char| !c.is_alphanumeric()).filter(|s| !s.is_empty()).collect();
+        if title.is_empty() {
+            format!("{:02}", index)
+        } else {
+            format!("{:02}_{}", index, title.join("_"))
+        }
+    }
+    
+    /// 逐个核对输入文件，`save_lists` 时在文件旁保存识别成功、识别失败的单词
+    fn check_inputs(checker: &dyn Checker, inputs: &[PathBuf], json: bool, save_lists: bool) -> Result<Vec<CheckResult>> {
+        let mut results = Vec::new();
+        for input in inputs {
+            if !json && inputs.len() > 1 {
+                println!("📄 {}", input.display());
+            }
+            let result = Self::check_input(checker, input)?;
+            if save_lists {
+                let (recognized, unrecognized) = result.save_word_lists(input)?;
+                if !json {
+                    println!("💾 已保存: {}、{}", recognized.display(), unrecognized.display());
+                }
+            }
+            results.push(result);
+        }
+        Ok(results)
+    }
+    
+    /// 核对单词文件；扩展名为 .json 时读取保存的提取结果（见 [`ExtractResult::load`]）核对其中的单词
+    fn check_input(checker: &dyn Checker, input: &std::path::Path) -> Result<CheckResult> {
+        if input.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+            let result = ExtractResult::load(input)?;
+            log::info!("读取提取结果: {} 个单词", result.total_words);
+            return checker.check_word_structs(&result.words);
+        }
+        checker.check_words_file(input)
+    }
+    
+    /// 创建指定后端的核对器，不背单词见 [`build_checker`](Self::build_checker)，本地词表读取 BBDC_OFFLINE_WORDLIST
+    fn build_backend(backend: CheckBackend) -> Result<Box<dyn Checker>> {
+        match backend {
+            CheckBackend::Bbdc => Ok(Box::new(Self::build_checker(false, false)?)),
+            CheckBackend::Wordlist => Ok(Box::new(CheckBackend::wordlist_from_env()?)),
+        }
+    }
+    
+    /// 创建核对器，遇到验证码时提示用户手动输入；设置了 BBDC_OFFLINE_WORDLIST 时无法连接则改用离线词表
+    ///
+    /// 默认使用核对结果缓存和分块核对进度：`no_cache` 只关闭缓存，`no_resume` 只关闭核对进度，
+    /// 模拟核对或无法确定文件位置时都不使用
+    fn build_checker(no_cache: bool, no_resume: bool) -> Result<BBDCChecker> {
+        let mut checker = BBDCChecker::from_env()?
+            .with_captcha_solver(Self::prompt_captcha)
+            .with_progress(Self::print_check_progress);
+        let mock = EnvLoader::exists(bbdc_providers::transport::MOCK_CHECK_ENV);
+        if !no_cache && !mock {
+            match CheckCache::open_default() {
+                Ok(cache) => checker = checker.with_cache(cache),
+                Err(e) => log::warn!("不使用核对缓存: {}", e),
+            }
+        }
+        if !no_resume && !mock {
+            match CheckState::open_default() {
+                Ok(state) => checker = checker.with_state(state),
+                Err(e) => log::warn!("不保存核对进度: {}", e),
+            }
+        }
+        match EnvLoader::get_optional("BBDC_OFFLINE_WORDLIST") {
+            Some(path) => Ok(checker.with_offline_fallback(OfflineDictionary::load(path)?)),
+            None => Ok(checker),
+        }
+    }
+    
+    /// 分块上传时输出每块的进度，写到标准错误，不影响 --json 输出
+    fn print_check_progress(event: &CheckEvent) {
+        match event {
+            CheckEvent::Started { words, chunks } if *chunks > 1 => {
+                eprintln!("⏫ 共 {} 个单词，分 {} 块上传", words, chunks);
+            }
+            CheckEvent::Resumed { chunks } => {
+                eprintln!("⏯️  从上次中断处继续，{} 块已完成，不再上传", chunks);
+            }
+            CheckEvent::Unchanged { .. } => {
+                eprintln!("⏭️  单词列表与上次成功核对时相同，使用上次的结果（--force 重新核对）");
+            }
+            CheckEvent::ChunkFinished { index, total, recognized } if *total > 1 => {
+                eprintln!("   第 {}/{} 块完成，识别 {} 个", index, total, recognized);
+            }
+            CheckEvent::Variants { words, variants } => {
+                eprintln!("🔁 用 {} 种其他写法重新核对 {} 个识别失败的单词", variants, words);
+            }
+            _ => {}
+        }
+    }
+    
+    /// 提示用户完成验证码
+    fn prompt_captcha(challenge: &CaptchaChallenge) -> Option<String> {
+        println!("\n🔐 不背单词要求输入验证码");
+        match &challenge.image_path {
+            Some(path) => println!("   验证码图片已保存到: {:?}", path),
+            None => println!("   验证码图片: {}", challenge.image_url),
+        }
+        println!("   也可以在浏览器中打开上面的地址查看");
+        print!("请输入验证码（直接回车放弃）: ");
+        io::stdout().flush().ok()?;
+        
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).ok()?;
+        let token = input.trim();
+        
+        if token.is_empty() {
+            None
+        } else {
+            Some(token.to_string())
+        }
+    }
+    
+    /// 打印核对结果
+    /// 识别失败的单词在原文中的位置，便于手动修正
+    fn print_locations(result: &CheckResult, words: &[Word], file: Option<&str>) {
+        let locations: Vec<(String, String)> = result
+            .unrecognized_words
+            .iter()
+            .filter_map(|failed| {
+                let word = words.iter().find(|w| w.word == *failed)?;
+                Some((failed.clone(), word.location(file)?))
+            })
+            .collect();
+        if locations.is_empty() {
+            return;
+        }
+        println!("\n📍 识别失败单词的位置:");
+        for (word, location) in locations.iter().take(10) {
+            println!("  {:<20} {}", word, location);
+        }
+        if locations.len() > 10 {
+            println!("  ... 还有 {} 个", locations.len() - 10);
+        }
+    }
+    
+    fn print_source_breakdown(groups: &[SourceCheck]) {
+        if groups.is_empty() {
+            return;
+        }
+        println!("\n📂 各文件识别率（从低到高）:");
+        for group in groups {
+            println!(
+                "  {:>6.1}%  {:>4}/{:<4}  {}",
+                group.recognition_rate() * 100.0,
+                group.recognized_count,
+                group.total_count,
+                group.source
+            );
+        }
+    }
+    
+    fn print_check_result(result: &CheckResult) {
+        println!("\n{}", "=".repeat(60));
+        println!("📊 不背单词词书核对结果");
+        println!("{}", "=".repeat(60));
+        
+        println!("\n📈 统计信息:");
+        println!("  总单词数: {}", result.total_count);
+        println!("  识别成功: {}", result.recognized_count);
+        println!("  识别失败: {}", result.unrecognized_count);
+        if result.removed_count > 0 {
+            println!("  上传前去掉的空行和重复单词: {}", result.removed_count);
+        }
+        
+        if result.total_count > 0 {
+            let success_rate = result.recognized_count as f64 / result.total_count as f64 * 100.0;
+            println!("  识别成功率: {:.1}%", success_rate);
+        }
+        
+        if !result.unrecognized_words.is_empty() {
+            println!("\n❌ 识别失败的单词（前10个）:");
+            for (i, word) in result.unrecognized_words.iter().take(10).enumerate() {
+                println!("  {:2}. {}", i + 1, word);
+            }
+            if result.unrecognized_words.len() > 10 {
+                println!("  ... 还有 {} 个", result.unrecognized_words.len() - 10);
+            }
+        }
+
+        if !result.variants.is_empty() {
+            println!("\n💡 换一种写法即可识别的单词（前10个）:");
+            for (word, variant) in result.variants.iter().take(10) {
+                println!("  {} → {}", word, variant);
+            }
+            if result.variants.len() > 10 {
+                println!("  ... 还有 {} 个", result.variants.len() - 10);
+            }
+        }
+        
+        if !result.normalized.is_empty() {
+            println!("\n🔁 不背单词改写了 {} 个单词的写法（前10个）:", result.normalized.len());
+            for (submitted, form) in result.normalized.iter().take(10) {
+                println!("  {} → {}", submitted, form);
+            }
+        }
+    }
+    
+    /// 处理 LLM 自动更正，返回成功更正的建议
+    fn handle_llm_correction(
+        check_result: &CheckResult,
+        llm: &LLMCorrector,
+    ) -> Result<Vec<CorrectionResult>> {
+        println!("正在处理 {} 个识别失败的单词...", check_result.unrecognized_count);
+        
+        let mut corrections = Vec::new();
+        let mut variant_corrections = Vec::new();
+        
+        for (i, word) in check_result.unrecognized_words.iter().enumerate() {
+            print!("[{}/{}] 处理: {} ... ", 
+                i + 1, check_result.unrecognized_count, word);
+            io::stdout().flush()?;
+            
+            // 换一种写法即可识别的单词不需要 LLM
+            if let Some(variant) = check_result.variants.get(word) {
+                println!("✓ → {}（其他写法）", variant);
+                variant_corrections.push(CorrectionResult {
+                    success: true,
+                    original: word.clone(),
+                    corrected: variant.clone(),
+                    confidence: Confidence::High,
+                    reason: "不背单词可以识别的其他写法".to_string(),
+                });
+                continue;
+            }
+            
+            let result = llm.correct_word(word, "")?;
+            
+            if result.success && result.corrected != result.original {
+                println!("✓ → {}", result.corrected);
+                corrections.push(result);
+            } else {
+                println!("×");
+            }
+            
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+        
+        if !corrections.is_empty() || !variant_corrections.is_empty() {
+            println!("\n✅ 成功更正 {} 个单词", corrections.len() + variant_corrections.len());
+            for corr in variant_corrections.iter().chain(&corrections) {
+                println!("  {} → {} ({})", corr.original, corr.corrected, corr.confidence);
+            }
+            // 其他写法来自核对结果，只记录 LLM 给出的建议
+            if !corrections.is_empty() {
+                Self::record_corrections(&corrections, llm.model());
+            }
+        } else {
+            println!("\n⚠️  未能自动更正任何单词");
+        }
+        
+        variant_corrections.extend(corrections);
+        Ok(variant_corrections)
+    }
+    
+    /// 把更正建议写入更正记录；在终端中运行时逐个询问是否采纳
+    fn record_corrections(corrections: &[CorrectionResult], model: &str) {
+        let log = match CorrectionLog::open_default() {
+            Ok(log) => log,
+            Err(e) => {
+                log::warn!("⚠️  无法保存更正记录: {}", e);
+                return;
+            }
+        };
+        
+        let interactive = io::stdin().is_terminal();
+        if interactive {
+            println!("\n📝 逐个确认是否采纳（y 采纳 / n 拒绝 / 回车跳过）:");
+        }
+        
+        for corr in corrections {
+            let accepted = if interactive {
+                print!("  {} → {} ? ", corr.original, corr.corrected);
+                io::stdout().flush().ok();
+                let mut input = String::new();
+                io::stdin().read_line(&mut input).ok();
+                match input.trim().to_lowercase().as_str() {
+                    "y" | "yes" => Some(true),
+                    "n" | "no" => Some(false),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+            
+            let mut record = CorrectionRecord::new(&corr.original, &corr.corrected, corr.confidence);
+            record.accepted = accepted;
+            record.source = model.to_string();
+            record.reason = corr.reason.clone();
+            if let Err(e) = log.append(&record) {
+                log::warn!("⚠️  无法保存更正记录: {}", e);
+                return;
+            }
+        }
+        
+        println!("💾 更正记录已保存到: {:?}", log.path());
+    }
+    
+    /// 核对后更正识别失败的单词，指定多个模型时输出对比表
+    fn handle_correct(input: PathBuf, compare: Vec<String>, json: bool, variants: bool) -> Result<()> {
+        if compare.len() == 1 {
+            return Err(Error::Other("--compare 至少需要两个模型，如 --compare modelA,modelB".to_string()));
+        }
+        
+        let llm = LLMCorrector::new()?;
+        if !llm.is_enabled() {
+            return Err(Error::EnvVar("更正需要 LLM，请设置 SILICONFLOW_API_KEY".to_string()));
+        }
+        
+        if !json {
+            println!("🔍 开始核对单词...");
+        }
+        let checker: Box<dyn Checker> = match CheckBackend::from_env()? {
+            CheckBackend::Bbdc => Box::new(Self::build_checker(false, false)?.with_variant_recheck(variants)),
+            backend => Self::build_backend(backend)?,
+        };
+        let check_result = Self::check_input(checker.as_ref(), &input)?;
+        if !json {
+            Self::print_check_result(&check_result);
+        }
+        
+        if compare.is_empty() {
+            if check_result.unrecognized_count > 0 {
+                println!("\n🤖 开始 LLM 自动更正...");
+                Self::handle_llm_correction(&check_result, &llm)?;
+            }
+            return Ok(());
+        }
+        
+        let mut runs = Vec::new();
+        for model in compare {
+            let llm = LLMCorrector::new()?.with_model(model);
+            if !json {
+                println!("\n🤖 使用 {} 更正 {} 个单词...", llm.model(), check_result.unrecognized_count);
+            }
+            runs.push(ModelRun::run(&llm, &check_result.unrecognized_words));
+        }
+        let report = bbdc_providers::comparison::compare(&runs);
+        
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+        
+        println!("\n{:<36} {:>6} {:>10} {:>10} {:>10}", "模型", "更正", "输入token", "输出token", "总token");
+        for m in &report.models {
+            println!(
+                "{:<36} {:>6} {:>10} {:>10} {:>10}",
+                m.model,
+                m.suggested,
+                humanize::format_number(m.usage.prompt_tokens),
+                humanize::format_number(m.usage.completion_tokens),
+                humanize::format_number(m.usage.total())
+            );
+        }
+        println!("\n🤝 建议一致: {}/{} ({:.1}%)", report.agreed, report.words.len(), report.agreement * 100.0);
+        
+        let divergences: Vec<_> = report.divergences().collect();
+        if !divergences.is_empty() {
+            println!("\n🔀 建议不一致的单词:");
+            for word in divergences {
+                let suggestions: Vec<&str> = word.suggestions.iter().map(|s| s.as_deref().unwrap_or("-")).collect();
+                println!("  {}: {}", word.original, suggestions.join(" | "));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 按语义主题拆分词书
+    fn handle_cluster(
+        input: PathBuf,
+        clusters: Option<usize>,
+        vectors: Option<PathBuf>,
+        provider: EmbeddingProvider,
+        mode: ExtractMode,
+        lists: bool,
+        output: Option<PathBuf>,
+    ) -> Result<()> {
+        let extractor = WordExtractor::new(true, false).with_lists(lists);
+        let result = extractor.extract_from_file(&input)?;
+        let words: Vec<String> = result.words.iter().map(|w| w.word.clone()).collect();
+        let embeddings = Self::embed_words(&result.words, vectors, provider)?;
+        
+        let k = clusters.unwrap_or_else(|| clustering::default_cluster_count(words.len()));
+        let clustering = clustering::cluster(&words, &embeddings, k);
+        
+        let out_dir = output.unwrap_or_else(|| PathBuf::from("."));
+        std::fs::create_dir_all(&out_dir)?;
+        let stem = Self::default_output(&input, "")?;
+        
+        let mut books: Vec<(String, &[usize])> = clustering
+            .clusters
+            .iter()
+            .map(|c| (c.label.to_lowercase(), c.members.as_slice()))
+            .collect();
+        if !clustering.unassigned.is_empty() {
+            books.push(("other".to_string(), &clustering.unassigned));
+        }
+        
+        println!("✅ 分为 {} 个主题:", books.len());
+        for (label, members) in books {
+            let words: Vec<Word> = members.iter().map(|&i| result.words[i].clone()).collect();
+            let theme = ExtractResult { total_words: words.len(), total_phrases: 0, words, phrases: Vec::new(), skipped: Vec::new(), cleaned: Vec::new(), files: Vec::new() };
+            let metadata = BookMetadata { title: label.clone(), ..BookMetadata::default() };
+            let book = Book::from_extract(&label, metadata, &theme)?;
+            let preview = book.entries().iter().take(5).map(|e| e.word()).collect::<Vec<_>>().join(", ");
+            
+            let path = out_dir.join(format!("{}_{}{}", stem.display(), label, mode.output_suffix()));
+            let exported = ExtractResult::from(&book);
+            if mode == ExtractMode::WordsOnly {
+                extractor.save_words_only(&exported.words, &path)?;
+            } else {
+                extractor.save_with_meaning(&exported, &path)?;
+            }
+            println!("  📁 {} ({}): {} → {:?}", label, book.len(), preview, path);
+        }
+        
+        Ok(())
+    }
+    
+    /// 查找近义词
+    fn handle_synonyms(
+        input: PathBuf,
+        threshold: f32,
+        vectors: Option<PathBuf>,
+        provider: EmbeddingProvider,
+        action: SynonymAction,
+        lists: bool,
+        output: Option<PathBuf>,
+    ) -> Result<()> {
+        let extractor = WordExtractor::new(true, false).with_lists(lists);
+        let mut result = extractor.extract_from_file(&input)?;
+        let embeddings = Self::embed_words(&result.words, vectors, provider)?;
+        
+        let pairs = synonyms::similar_pairs(&result.words, &embeddings, threshold);
+        println!("🔗 找到 {} 对近义词（相似度 ≥ {}）:", pairs.len(), threshold);
+        for pair in &pairs {
+            println!(
+                "  {} ≈ {} ({:.3})",
+                result.words[pair.first].word, result.words[pair.second].word, pair.similarity
+            );
+        }
+        
+        let suffix = match action {
+            SynonymAction::Report => return Ok(()),
+            SynonymAction::Merge => {
+                let removed = synonyms::merge_similar(&mut result.words, &pairs);
+                result.total_words = result.words.len();
+                println!("✅ 合并后移除了 {} 个单词", removed);
+                "_近义合并.txt"
+            }
+            SynonymAction::Annotate => {
+                synonyms::annotate_similar(&mut result.words, &pairs);
+                "_近义标注.txt"
+            }
+        };
+        
+        let output_file = match output {
+            Some(output) => output,
+            None => Self::default_output(&input, suffix)?,
+        };
+        extractor.save_with_meaning(&result, &output_file)?;
+        println!("💾 已保存到: {:?}", output_file);
+        
+        Ok(())
+    }
+    
+    /// 获取单词的词向量：指定 `.vec` 文件时离线读取，否则调用嵌入接口（结果缓存在磁盘上）
+    fn embed_words(words: &[Word], vectors: Option<PathBuf>, provider: EmbeddingProvider) -> Result<Vec<Option<Vec<f32>>>> {
+        let words: Vec<String> = words.iter().map(|w| w.word.clone()).collect();
+        let embedder: Box<dyn Embedder> = match vectors {
+            Some(path) => Box::new(FastTextVectors::load(path, &words)?),
+            None => Box::new(CachedEmbedder::open_default(HttpEmbedder::from_provider(provider)?)?),
+        };
+        println!("🧭 正在获取 {} 个单词的词向量...", words.len());
+        embedder.embed(&words)
+    }
+    
+    /// 评测更正质量
+    fn handle_eval(dataset: PathBuf, json: bool) -> Result<()> {
+        let pairs = bbdc_providers::evaluation::load_dataset(&dataset)?;
+        let llm = LLMCorrector::new()?;
+        if !llm.is_enabled() {
+            return Err(Error::EnvVar("评测需要 LLM，请设置 SILICONFLOW_API_KEY".to_string()));
+        }
+        
+        if !json {
+            println!("🧪 使用 {} 评测 {} 条样本...", llm.model(), pairs.len());
+        }
+        let report = bbdc_providers::evaluation::evaluate(&pairs, |word, meaning| llm.correct_word(word, meaning));
+        
+        if json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            return Ok(());
+        }
+        
+        println!("\n{:<8} {:>6} {:>6} {:>8} {:>8} {:>10}", "阈值", "采纳", "正确", "精确率", "召回率", "采纳准确率");
+        for m in &report.metrics {
+            println!(
+                "{:<8} {:>6} {:>6} {:>7.1}% {:>7.1}% {:>9.1}%",
+                m.min_confidence.to_string(),
+                m.applied,
+                m.correct,
+                m.precision * 100.0,
+                m.recall * 100.0,
+                m.accuracy * 100.0
+            );
+        }
+        
+        let misses: Vec<_> = report
+            .cases
+            .iter()
+            .filter(|c| c.suggested.as_deref().map_or(true, |s| !s.eq_ignore_ascii_case(&c.expected)) && c.original != c.expected)
+            .collect();
+        if !misses.is_empty() {
+            println!("\n❌ 未能正确更正的样本:");
+            for case in misses {
+                println!("  {} → {}（期望 {}）", case.original, case.suggested.as_deref().unwrap_or("-"), case.expected);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// 处理更正记录命令
+    fn handle_corrections(action: CorrectionsCommands) -> Result<()> {
+        match action {
+            CorrectionsCommands::Export { format, output } => {
+                let records = CorrectionLog::open_default()?.decisions()?;
+                let written = match &output {
+                    Some(path) => bbdc_providers::export_dataset(&records, format, std::fs::File::create(path)?)?,
+                    None => bbdc_providers::export_dataset(&records, format, io::stdout().lock())?,
+                };
+                if let Some(path) = output {
+                    println!("✅ 已导出 {} 条记录到: {:?}", written, path);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_chapter_reports_use_chapter_metadata() {
+        let dir = std::env::temp_dir().join(format!("bbdc_chapter_reports_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let markdown = "# Lesson 1\n\n| NO. | 单词 | 词义 |\n|---|---|---|\n| 1 | abandon | 放弃 |\n";
+        let result = WordExtractor::new(true, false).extract_from_markdown(markdown).unwrap();
+        let metadata = BookMetadata { title: "新概念".to_string(), tags: vec!["英语".to_string()], ..Default::default() };
+        
+        let (chapter, volume) = &result.split_by_chapter()[0];
+        let name = Cli::chapter_volume_name(1, *chapter);
+        let path = Cli::save_chapter_report(&metadata, *chapter, volume, &dir.join("report.md"), &name).unwrap();
+        let report = std::fs::read_to_string(&path).unwrap();
+        let _ = std::fs::remove_dir_all(&dir);
+        
+        assert_eq!(path.file_name().unwrap(), "report_01_Lesson_1.md");
+        assert!(report.starts_with("# 新概念 - Lesson 1"));
+        assert!(report.contains("- 标签: 英语, Lesson 1"));
+    }
+    
+    #[test]
+    fn test_check_concurrency_range() {
+        let concurrency = |value: &str| match Cli::try_parse_from(["bbdc_word_tool", "check", "words.txt", "--concurrency", value]) {
+            Ok(Cli { command: Some(Commands::Check(args)), .. }) => Ok(args.concurrency),
+            Ok(_) => unreachable!(),
+            Err(e) => Err(e.kind()),
+        };
+        assert_eq!(concurrency("3"), Ok(Some(3)));
+        assert_eq!(concurrency("0"), Err(clap::error::ErrorKind::ValueValidation));
+        assert_eq!(concurrency("5"), Err(clap::error::ErrorKind::ValueValidation));
+    }
+}
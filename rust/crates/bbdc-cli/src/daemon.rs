@@ -0,0 +1,543 @@
+//! 守护进程模块
+//!
+//! 以磁盘上的任务队列驱动提取和核对：每个任务是队列目录中的一个 JSON 文件，
+//! 通过 `submit` 命令或 HTTP API 加入。失败的任务按退避时间自动重试，
+//! 进程重启后会把中断的任务重新放回队列
+//!
+//! HTTP API 需要 `Authorization: Bearer <BBDC_DAEMON_TOKEN>`，提交的任务只能读取
+//! `BBDC_DAEMON_INPUT_ROOT` 下的文件，输出也写在该目录下
+
+use bbdc_core::{EnvLoader, Error, ExtractMode, Result, WordExtractor};
+use bbdc_providers::{services, CheckBackend, ServiceStatus};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// 单个任务最多尝试的次数
+const MAX_ATTEMPTS: u32 = 3;
+
+/// 重试退避的基准秒数，第 n 次失败后等待 `RETRY_BASE_SECS * 2^(n-1)` 秒
+const RETRY_BASE_SECS: i64 = 30;
+
+/// 守护进程轮询队列的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// `POST /jobs` 请求体的字节数上限，任务内容通常只有几百字节
+const MAX_BODY_BYTES: u64 = 64 * 1024;
+
+/// 任务内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSpec {
+    /// 输入的 Markdown 文件
+    pub input: PathBuf,
+    /// 输出文件，缺省时写到输入文件旁
+    #[serde(default)]
+    pub output: Option<PathBuf>,
+    #[serde(default)]
+    pub mode: ExtractMode,
+    /// 提取后是否核对（守护进程无法交互输入验证码）
+    #[serde(default)]
+    pub check: bool,
+    /// 是否同时提取列表
+    #[serde(default)]
+    pub lists: bool,
+}
+
+/// 任务状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+/// 队列中的任务
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: String,
+    #[serde(flatten)]
+    pub spec: JobSpec,
+    pub status: JobStatus,
+    pub attempts: u32,
+    /// 下次可以执行的时间（Unix 秒）
+    pub not_before: i64,
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<JobResult>,
+}
+
+/// 任务结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobResult {
+    pub output: PathBuf,
+    pub total_words: usize,
+    pub total_phrases: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unrecognized_words: Option<Vec<String>>,
+}
+
+/// 磁盘任务队列
+#[derive(Debug, Clone)]
+pub struct JobQueue {
+    dir: PathBuf,
+}
+
+impl JobQueue {
+    /// 打开默认队列：`BBDC_JOBS_DIR`，否则为 `~/.bbdc_word_tool/jobs`
+    pub fn open_default() -> Result<Self> {
+        let dir = EnvLoader::get_optional("BBDC_JOBS_DIR")
+            .map(PathBuf::from)
+            .or_else(|| EnvLoader::data_dir().map(|dir| dir.join("jobs")))
+            .ok_or_else(|| Error::Other("无法确定任务队列目录，请设置 BBDC_JOBS_DIR".to_string()))?;
+        Self::open(dir)
+    }
+
+    /// 打开指定目录下的队列，目录不存在时创建
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        fs::create_dir_all(dir.as_ref())?;
+        Ok(Self { dir: dir.as_ref().to_path_buf() })
+    }
+
+    /// 加入新任务
+    pub fn submit(&self, mut spec: JobSpec) -> Result<Job> {
+        // 守护进程的工作目录可能不同，提交时就转换为绝对路径
+        spec.input = fs::canonicalize(&spec.input)
+            .map_err(|e| Error::Other(format!("无法读取输入文件 {:?}: {}", spec.input, e)))?;
+        if let Some(output) = spec.output.take() {
+            spec.output = Some(std::env::current_dir()?.join(output));
+        }
+        self.enqueue(spec)
+    }
+
+    /// 加入通过 HTTP API 提交的任务
+    ///
+    /// 输入按 `input_root` 解析，解析后（包括符号链接）必须仍在 `input_root` 下；
+    /// 输出只能是不含 `..` 的相对路径，同样写到 `input_root` 下：提交时创建所在目录，
+    /// 目录经符号链接指向 `input_root` 之外或输出文件本身是符号链接时拒绝
+    pub fn submit_within(&self, mut spec: JobSpec, input_root: &Path) -> Result<Job> {
+        let root = fs::canonicalize(input_root)?;
+        spec.input = fs::canonicalize(root.join(&spec.input))
+            .map_err(|e| Error::Other(format!("无法读取输入文件 {:?}: {}", spec.input, e)))?;
+        if !spec.input.starts_with(&root) {
+            return Err(Error::Other(format!("输入文件必须位于 {:?} 下: {:?}", root, spec.input)));
+        }
+        if let Some(output) = spec.output.take() {
+            if output.as_os_str().is_empty() || !output.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) {
+                return Err(Error::Other(format!("输出路径必须是不含 .. 的相对路径: {:?}", output)));
+            }
+            let output = root.join(output);
+            let (Some(parent), Some(file_name)) = (output.parent(), output.file_name()) else {
+                return Err(Error::Other(format!("输出路径缺少文件名: {:?}", output)));
+            };
+            let output = create_dir_within(&root, parent)?.join(file_name);
+            if fs::symlink_metadata(&output).is_ok_and(|meta| meta.file_type().is_symlink()) {
+                return Err(Error::Other(format!("输出文件不能是符号链接: {:?}", output)));
+            }
+            spec.output = Some(output);
+        }
+        self.enqueue(spec)
+    }
+
+    fn enqueue(&self, spec: JobSpec) -> Result<Job> {
+        let now = chrono::Local::now();
+        let job = Job {
+            id: format!("{}-{:08x}", now.format("%Y%m%d%H%M%S%3f"), std::process::id() ^ now.timestamp_subsec_nanos()),
+            spec,
+            status: JobStatus::Pending,
+            attempts: 0,
+            not_before: 0,
+            created_at: now.to_rfc3339(),
+            last_error: None,
+            result: None,
+        };
+        self.save(&job)?;
+        Ok(job)
+    }
+
+    /// 所有任务，按提交顺序排列
+    pub fn list(&self) -> Result<Vec<Job>> {
+        let mut jobs = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                match fs::read(&path).map_err(Error::from).and_then(|data| Ok(serde_json::from_slice::<Job>(&data)?)) {
+                    Ok(job) => jobs.push(job),
+                    Err(e) => log::warn!("⚠️  跳过无法读取的任务文件 {:?}: {}", path, e),
+                }
+            }
+        }
+        jobs.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(jobs)
+    }
+
+    /// 按 ID 查找任务
+    pub fn get(&self, id: &str) -> Result<Option<Job>> {
+        let path = match self.job_path(id) {
+            Some(path) if path.exists() => path,
+            _ => return Ok(None),
+        };
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// 保存任务（先写临时文件再改名，避免读到半个文件）
+    pub fn save(&self, job: &Job) -> Result<()> {
+        let path = self
+            .job_path(&job.id)
+            .ok_or_else(|| Error::Other(format!("无效的任务 ID: {}", job.id)))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(job)?)?;
+        fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    /// 把上次运行中断的任务重新放回队列，返回恢复的数量
+    pub fn recover(&self) -> Result<usize> {
+        let mut recovered = 0;
+        for mut job in self.list()? {
+            if job.status == JobStatus::Running {
+                job.status = JobStatus::Pending;
+                self.save(&job)?;
+                recovered += 1;
+            }
+        }
+        Ok(recovered)
+    }
+
+    /// 下一个可以执行的任务
+    fn next_ready(&self, now: i64) -> Result<Option<Job>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .find(|job| job.status == JobStatus::Pending && job.not_before <= now))
+    }
+
+    fn job_path(&self, id: &str) -> Option<PathBuf> {
+        let valid = !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+        valid.then(|| self.dir.join(format!("{}.json", id)))
+    }
+
+    /// 执行一个任务并记录结果，失败时安排重试
+    fn run(&self, mut job: Job) -> Result<Job> {
+        job.status = JobStatus::Running;
+        job.attempts += 1;
+        self.save(&job)?;
+
+        log::info!("▶️  执行任务 {}（第 {} 次）: {:?}", job.id, job.attempts, job.spec.input);
+        match run_spec(&job.spec) {
+            Ok(result) => {
+                log::info!("✅ 任务 {} 完成: {:?}", job.id, result.output);
+                job.status = JobStatus::Done;
+                job.last_error = None;
+                job.result = Some(result);
+            }
+            Err(e) => {
+                log::warn!("❌ 任务 {} 失败: {}", job.id, e);
+                job.last_error = Some(e.to_string());
+                if job.attempts >= MAX_ATTEMPTS {
+                    job.status = JobStatus::Failed;
+                } else {
+                    job.status = JobStatus::Pending;
+                    job.not_before = chrono::Utc::now().timestamp() + RETRY_BASE_SECS * (1 << (job.attempts - 1));
+                }
+            }
+        }
+        self.save(&job)?;
+        Ok(job)
+    }
+
+    /// 依次执行当前可以执行的任务，返回执行的数量
+    pub fn process_ready(&self) -> Result<usize> {
+        let mut processed = 0;
+        while let Some(job) = self.next_ready(chrono::Utc::now().timestamp())? {
+            self.run(job)?;
+            processed += 1;
+        }
+        Ok(processed)
+    }
+}
+
+/// 执行任务内容
+fn run_spec(spec: &JobSpec) -> Result<JobResult> {
+    let extractor = WordExtractor::new(true, spec.mode.includes_phrases()).with_lists(spec.lists);
+    let extracted = extractor.extract_from_file(&spec.input)?;
+
+    let output = match &spec.output {
+        Some(output) => output.clone(),
+        None => {
+            let stem = spec
+                .input
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| Error::Other(format!("无法从输入路径确定输出文件名: {:?}", spec.input)))?;
+            spec.input.with_file_name(format!("{}{}", stem, spec.mode.output_suffix()))
+        }
+    };
+
+    if spec.mode == ExtractMode::WordsOnly {
+        extractor.save_words_only(&extracted.words, &output)?;
+    } else {
+        extractor.save_with_meaning(&extracted, &output)?;
+    }
+
+    let unrecognized_words = if spec.check {
+        Some(CheckBackend::from_env()?.checker_from_env()?.check_word_structs(&extracted.words)?.unrecognized_words)
+    } else {
+        None
+    };
+
+    Ok(JobResult {
+        output,
+        total_words: extracted.total_words,
+        total_phrases: extracted.total_phrases,
+        unrecognized_words,
+    })
+}
+
+/// HTTP API 的访问控制
+#[derive(Debug, Clone)]
+pub struct ApiConfig {
+    /// 请求需要携带的 `Authorization: Bearer` 令牌
+    pub token: String,
+    /// 通过 HTTP API 提交的任务可以读写的目录
+    pub input_root: PathBuf,
+}
+
+impl ApiConfig {
+    /// 从环境变量读取：`BBDC_DAEMON_TOKEN`（必需），`BBDC_DAEMON_INPUT_ROOT`（默认为当前目录）
+    pub fn from_env() -> Result<Self> {
+        let token = EnvLoader::get_optional("BBDC_DAEMON_TOKEN")
+            .filter(|token| !token.trim().is_empty())
+            .ok_or_else(|| Error::EnvVar("守护进程需要用 BBDC_DAEMON_TOKEN 设置 HTTP API 令牌".to_string()))?;
+        let input_root = match EnvLoader::get_optional("BBDC_DAEMON_INPUT_ROOT") {
+            Some(root) => PathBuf::from(root),
+            None => std::env::current_dir()?,
+        };
+        let input_root = fs::canonicalize(&input_root)
+            .map_err(|e| Error::EnvVar(format!("BBDC_DAEMON_INPUT_ROOT 无效 {:?}: {}", input_root, e)))?;
+        Ok(Self { token: token.trim().to_string(), input_root })
+    }
+
+    /// 请求头中的令牌是否正确
+    fn authorized(&self, authorization: Option<&str>) -> bool {
+        let Some(token) = authorization.and_then(|value| value.trim().strip_prefix("Bearer ")) else {
+            return false;
+        };
+        // 逐字节比较全部内容，耗时不随第一个不同字节的位置变化
+        let (given, expected) = (token.trim().as_bytes(), self.token.as_bytes());
+        given.len() == expected.len() && given.iter().zip(expected).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+    }
+}
+
+/// 在 `root`（已规范化）下创建目录 `dir`，返回规范化后的路径；已有的上级目录或创建后的目录
+/// 经符号链接指向 `root` 之外时报错，不在 `root` 之外创建目录
+fn create_dir_within(root: &Path, dir: &Path) -> Result<PathBuf> {
+    let outside = |path: &Path| Error::Other(format!("输出目录必须位于 {:?} 下: {:?}", root, path));
+    if let Some(existing) = dir.ancestors().find(|path| path.exists()) {
+        let existing = fs::canonicalize(existing)?;
+        if !existing.starts_with(root) {
+            return Err(outside(&existing));
+        }
+    }
+    fs::create_dir_all(dir).map_err(|e| Error::Other(format!("无法创建输出目录 {:?}: {}", dir, e)))?;
+    let dir = fs::canonicalize(dir)?;
+    if !dir.starts_with(root) {
+        return Err(outside(&dir));
+    }
+    Ok(dir)
+}
+
+/// 以守护进程方式运行：在 `addr` 上提供 HTTP API，并持续处理队列
+///
+/// HTTP API（均需 `Authorization: Bearer <令牌>`）：
+/// - `POST /jobs`：提交任务，请求体为 `Content-Type: application/json` 的 [`JobSpec`]（不超过 64 KiB），
+///   路径限制见 [`JobQueue::submit_within`]
+/// - `GET /jobs`：列出所有任务
+/// - `GET /jobs/{id}`：查询单个任务
+/// - `GET /status`：各状态的任务数与外部服务状态（见 [`status`]）
+pub fn serve(queue: JobQueue, addr: &str, api: ApiConfig) -> Result<()> {
+    let recovered = queue.recover()?;
+    if recovered > 0 {
+        log::info!("♻️  恢复了 {} 个中断的任务", recovered);
+    }
+
+    let server = tiny_http::Server::http(addr)
+        .map_err(|e| Error::Other(format!("无法监听 {}: {}", addr, e)))?;
+    log::info!("🚀 守护进程已启动: http://{}（输入目录 {:?}）", addr, api.input_root);
+
+    let api_queue = queue.clone();
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            handle_request(&api_queue, &api, request);
+        }
+    });
+
+    loop {
+        if let Err(e) = queue.process_ready() {
+            log::error!("处理任务队列失败: {}", e);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// 状态面板：各状态的任务数，以及访问各外部服务得到的可用性和余额
+pub fn status(queue: &JobQueue) -> Result<serde_json::Value> {
+    let jobs = queue.list()?;
+    let count = |status: JobStatus| jobs.iter().filter(|job| job.status == status).count();
+    let services: Vec<ServiceStatus> = services::all().iter().map(|s| ServiceStatus::probe(s.as_ref())).collect();
+    Ok(serde_json::json!({
+        "jobs": {
+            "pending": count(JobStatus::Pending),
+            "running": count(JobStatus::Running),
+            "done": count(JobStatus::Done),
+            "failed": count(JobStatus::Failed),
+        },
+        "services": services,
+    }))
+}
+
+/// 请求头的值
+fn header<'a>(request: &'a tiny_http::Request, name: &'static str) -> Option<&'a str> {
+    request.headers().iter().find(|header| header.field.equiv(name)).map(|header| header.value.as_str())
+}
+
+/// 处理一个 HTTP 请求
+fn handle_request(queue: &JobQueue, api: &ApiConfig, mut request: tiny_http::Request) {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let path = url.split('?').next().unwrap_or_default().trim_end_matches('/');
+    // 只接受 JSON 请求体，浏览器无法在不预检的情况下跨站发送
+    let is_json = header(&request, "Content-Type")
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+
+    let response = match (&method, path) {
+        _ if !api.authorized(header(&request, "Authorization")) => {
+            Ok((401, Ok(serde_json::json!({ "error": "缺少或错误的令牌" }))))
+        }
+        (tiny_http::Method::Post, "/jobs") if !is_json => {
+            Ok((415, Ok(serde_json::json!({ "error": "请求体必须是 application/json" }))))
+        }
+        (tiny_http::Method::Post, "/jobs") => {
+            let mut body = String::new();
+            match request.as_reader().take(MAX_BODY_BYTES + 1).read_to_string(&mut body) {
+                Ok(_) if body.len() as u64 > MAX_BODY_BYTES => {
+                    Ok((413, Ok(serde_json::json!({ "error": format!("请求体超过 {} 字节", MAX_BODY_BYTES) }))))
+                }
+                Ok(_) => serde_json::from_str::<JobSpec>(&body)
+                    .map_err(Error::from)
+                    .and_then(|spec| queue.submit_within(spec, &api.input_root))
+                    .map(|job| (201, serde_json::to_value(job))),
+                Err(e) => Err(Error::from(e)),
+            }
+        }
+        (tiny_http::Method::Get, "/jobs") => queue.list().map(|jobs| (200, serde_json::to_value(jobs))),
+        (tiny_http::Method::Get, "/status") => status(queue).map(|body| (200, Ok(body))),
+        (tiny_http::Method::Get, _) if path.starts_with("/jobs/") => {
+            queue.get(&path["/jobs/".len()..]).map(|job| match job {
+                Some(job) => (200, serde_json::to_value(job)),
+                None => (404, Ok(serde_json::json!({ "error": "任务不存在" }))),
+            })
+        }
+        _ => Ok((404, Ok(serde_json::json!({ "error": "未知的接口" })))),
+    };
+
+    let (status, body) = match response {
+        Ok((status, Ok(body))) => (status, body),
+        Ok((_, Err(e))) => (500, serde_json::json!({ "error": e.to_string() })),
+        Err(e) => (400, serde_json::json!({ "error": e.to_string() })),
+    };
+
+    let mut response = tiny_http::Response::from_string(body.to_string()).with_status_code(status);
+    if let Ok(header) = tiny_http::Header::from_bytes("Content-Type", "application/json; charset=utf-8") {
+        response.add_header(header);
+    }
+    if let Err(e) = request.respond(response) {
+        log::warn!("⚠️  响应 {} {} 失败: {}", method, url, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("bbdc_jobs_test_{}", std::process::id()));
+        let input = dir.join("unit1.md");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&input, "| NO. | 单词 | 释义 |\n|---|---|---|\n| 1 | hello | 你好 |\n").unwrap();
+
+        let queue = JobQueue::open(dir.join("queue")).unwrap();
+        let spec = JobSpec { input: input.clone(), output: None, mode: ExtractMode::WordsOnly, check: false, lists: false };
+        let mut job = queue.submit(spec).unwrap();
+
+        // 模拟进程在执行中退出
+        job.status = JobStatus::Running;
+        queue.save(&job).unwrap();
+
+        let queue = JobQueue::open(dir.join("queue")).unwrap();
+        assert_eq!(queue.recover().unwrap(), 1);
+        assert_eq!(queue.process_ready().unwrap(), 1);
+
+        let job = queue.get(&job.id).unwrap().unwrap();
+        assert_eq!(job.status, JobStatus::Done);
+        assert_eq!(job.result.unwrap().total_words, 1);
+        assert_eq!(fs::read_to_string(dir.join("unit1_单词.txt")).unwrap(), "hello");
+        assert!(queue.get("../unit1").unwrap().is_none());
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_remote_jobs_stay_within_input_root() {
+        let dir = std::env::temp_dir().join(format!("bbdc_jobs_root_test_{}", std::process::id()));
+        let root = dir.join("inputs");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("unit1.md"), "| NO. | 单词 | 释义 |\n|---|---|---|\n| 1 | hello | 你好 |\n").unwrap();
+        fs::write(dir.join("secret.md"), "").unwrap();
+        let queue = JobQueue::open(dir.join("queue")).unwrap();
+        let spec = |input: &str, output: Option<&str>| JobSpec {
+            input: PathBuf::from(input),
+            output: output.map(PathBuf::from),
+            mode: ExtractMode::WordsOnly,
+            check: false,
+            lists: false,
+        };
+
+        let job = queue.submit_within(spec("unit1.md", Some("out/unit1.txt")), &root).unwrap();
+        let root = fs::canonicalize(&root).unwrap();
+        assert_eq!(job.spec.input, root.join("unit1.md"));
+        assert_eq!(job.spec.output, Some(root.join("out/unit1.txt")));
+        assert!(queue.submit_within(spec("../secret.md", None), &root).is_err());
+        assert!(queue.submit_within(spec(dir.join("secret.md").to_str().unwrap(), None), &root).is_err());
+        assert!(queue.submit_within(spec("unit1.md", Some("../unit1.txt")), &root).is_err());
+        assert!(queue.submit_within(spec("unit1.md", Some("/tmp/unit1.txt")), &root).is_err());
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(&dir, root.join("link")).unwrap();
+            std::os::unix::fs::symlink(dir.join("secret.md"), root.join("secret.txt")).unwrap();
+            assert!(queue.submit_within(spec("unit1.md", Some("link/unit1.txt")), &root).is_err());
+            assert!(queue.submit_within(spec("unit1.md", Some("link/new/unit1.txt")), &root).is_err());
+            assert!(!dir.join("new").exists());
+            assert!(queue.submit_within(spec("unit1.md", Some("secret.txt")), &root).is_err());
+        }
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_api_token() {
+        let api = ApiConfig { token: "s3cret".to_string(), input_root: PathBuf::from(".") };
+        assert!(api.authorized(Some("Bearer s3cret")));
+        assert!(!api.authorized(Some("Bearer s3cre")));
+        assert!(!api.authorized(Some("s3cret")));
+        assert!(!api.authorized(None));
+    }
+}
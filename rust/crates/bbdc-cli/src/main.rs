@@ -2,12 +2,16 @@
 //! 
 //! 主程序入口
 
-use bbdc_word_tool::cli::Cli;
+mod cli;
+mod daemon;
+mod notify;
+
+use cli::Cli;
 
 fn main() {
     // 运行CLI
     if let Err(e) = Cli::run() {
         eprintln!("❌ 错误: {}", e);
-        std::process::exit(1);
+        std::process::exit(e.exit_code());
     }
 }
@@ -0,0 +1,24 @@
+//! 桌面通知模块
+//!
+//! 耗时较长的命令结束后发送一条桌面通知，需启用 `notify` 特性
+
+/// 发送桌面通知，失败时只记录警告
+pub fn send(summary: &str, body: &str) {
+    #[cfg(feature = "notify")]
+    {
+        let result = notify_rust::Notification::new()
+            .appname("bbdc_word_tool")
+            .summary(summary)
+            .body(body)
+            .show();
+        if let Err(e) = result {
+            log::warn!("⚠️  无法发送桌面通知: {}", e);
+        }
+    }
+
+    #[cfg(not(feature = "notify"))]
+    {
+        let _ = (summary, body);
+        log::warn!("⚠️  未启用 notify 特性，无法发送桌面通知（使用 --features notify 编译）");
+    }
+}
@@ -0,0 +1,420 @@
+//! Anki 牌组模块
+//!
+//! 读取 Anki 导出的 `.apkg`（zip 包中的 SQLite 数据库），按字段名或序号取出单词和释义。
+//! Anki 2.1.50 起默认导出的 `collection.anki21b` 经过 zstd 压缩，需要在导出时勾选
+//! 「支持旧版本 Anki」
+//!
+//! 也可以把单词导出为旧版格式（`collection.anki2`）的牌组，各版本 Anki 都能导入；
+//! 单词所在章节作为子牌组和标签保留
+
+use crate::{BookMetadata, Error, Result, Word};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Write};
+
+/// 导出牌组的笔记类型字段，第 1、2 个字段与读取时的默认字段映射一致
+const NOTE_FIELDS: [&str; 4] = ["单词", "释义", "音标", "例句"];
+
+/// 没有标题时的牌组名称
+const DEFAULT_DECK: &str = "不背单词";
+
+/// 字段映射：字段名（忽略大小写）或从 1 开始的序号
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnkiFields {
+    pub word: String,
+    pub meaning: String,
+    /// 音标字段，None 时不读取音标
+    pub phonetic: Option<String>,
+}
+
+impl Default for AnkiFields {
+    /// 第 1 个字段为单词，第 2 个字段为释义（Basic 笔记类型的正面、背面）
+    fn default() -> Self {
+        Self { word: "1".to_string(), meaning: "2".to_string(), phonetic: None }
+    }
+}
+
+/// 一条笔记：字段名与字段值（已去除 HTML）
+#[derive(Debug, Clone)]
+pub struct AnkiNote {
+    pub fields: Vec<(String, String)>,
+}
+
+impl AnkiNote {
+    /// 按字段名或序号取字段值
+    pub fn field(&self, field: &str) -> Option<&str> {
+        let by_index = field
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| i.checked_sub(1))
+            .and_then(|i| self.fields.get(i));
+        by_index
+            .or_else(|| self.fields.iter().find(|(name, _)| name.eq_ignore_ascii_case(field)))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// 读取 `.apkg` 中的全部笔记
+pub fn read_notes(data: &[u8]) -> Result<Vec<AnkiNote>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| Error::Parse(format!("无法打开 Anki 牌组: {}", e)))?;
+
+    // 新版导出同时包含提示升级的 collection.anki2，优先使用 collection.anki21
+    let name = ["collection.anki21", "collection.anki2"]
+        .into_iter()
+        .find(|name| archive.by_name(name).is_ok())
+        .ok_or_else(|| {
+            if archive.by_name("collection.anki21b").is_ok() {
+                Error::Parse("牌组使用了新版压缩格式，请在 Anki 导出时勾选「支持旧版本 Anki」".to_string())
+            } else {
+                Error::Parse("Anki 牌组中缺少 collection.anki2".to_string())
+            }
+        })?;
+
+    let mut db = Vec::new();
+    archive
+        .by_name(name)
+        .map_err(|e| Error::Parse(format!("读取 {} 失败: {}", name, e)))?
+        .read_to_end(&mut db)?;
+
+    // rusqlite 只能打开文件，先写入临时文件
+    let path = std::env::temp_dir().join(format!(
+        "bbdc_apkg_{}_{}.sqlite",
+        std::process::id(),
+        chrono::Local::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    std::fs::write(&path, &db)?;
+    let notes = Connection::open(&path).map_err(sql_error).and_then(|conn| query_notes(&conn));
+    std::fs::remove_file(&path).ok();
+    notes
+}
+
+fn query_notes(conn: &Connection) -> Result<Vec<AnkiNote>> {
+    let field_names = field_names(conn)?;
+
+    let mut stmt = conn.prepare("SELECT mid, flds FROM notes ORDER BY id").map_err(sql_error)?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(sql_error)?;
+
+    let mut notes = Vec::new();
+    for row in rows {
+        let (mid, flds) = row.map_err(sql_error)?;
+        let names = field_names.get(&mid);
+        let fields = flds
+            .split('\u{1f}')
+            .enumerate()
+            .map(|(i, value)| {
+                let name = names.and_then(|n| n.get(i)).cloned().unwrap_or_else(|| (i + 1).to_string());
+                (name, strip_html(value))
+            })
+            .collect();
+        notes.push(AnkiNote { fields });
+    }
+
+    log::info!("从 Anki 牌组读取到 {} 条笔记", notes.len());
+    Ok(notes)
+}
+
+/// 每种笔记类型的字段名，按字段顺序排列
+///
+/// 新版数据库记录在 `fields` 表中，旧版记录在 `col.models` 的 JSON 中
+fn field_names(conn: &Connection) -> Result<HashMap<i64, Vec<String>>> {
+    let mut names: HashMap<i64, Vec<String>> = HashMap::new();
+
+    if let Ok(mut stmt) = conn.prepare("SELECT ntid, name FROM fields ORDER BY ntid, ord") {
+        let rows = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+            .map_err(sql_error)?;
+        for row in rows {
+            let (ntid, name) = row.map_err(sql_error)?;
+            names.entry(ntid).or_default().push(name);
+        }
+        if !names.is_empty() {
+            return Ok(names);
+        }
+    }
+
+    let models: String = conn
+        .query_row("SELECT models FROM col", [], |row| row.get(0))
+        .map_err(sql_error)?;
+    let models: HashMap<String, serde_json::Value> = serde_json::from_str(&models)
+        .map_err(|e| Error::Parse(format!("无法解析笔记类型: {}", e)))?;
+
+    for (id, model) in models {
+        let Ok(id) = id.parse::<i64>() else { continue };
+        let mut fields: Vec<(i64, String)> = model["flds"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|f| Some((f["ord"].as_i64()?, f["name"].as_str()?.to_string())))
+            .collect();
+        fields.sort_by_key(|(ord, _)| *ord);
+        names.insert(id, fields.into_iter().map(|(_, name)| name).collect());
+    }
+
+    Ok(names)
+}
+
+/// 去掉字段中的 HTML 标签和 `[sound:...]`，并解码常见实体
+fn strip_html(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut in_tag = false;
+    for c in value.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                text.push(' ');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    let mut text = text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&");
+    while let Some(start) = text.find("[sound:") {
+        let end = text[start..].find(']').map_or(text.len(), |i| start + i + 1);
+        text.replace_range(start..end, "");
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn sql_error(e: rusqlite::Error) -> Error {
+    Error::Parse(format!("Anki 数据库错误: {}", e))
+}
+
+/// 把单词导出为 `.apkg`，每个单词一张卡片（正面为单词，背面为音标、释义和例句）
+///
+/// 牌组名称为 `metadata` 的标题；有章节的单词放入子牌组 `标题::章节`，标签取自
+/// [`BookMetadata::for_chapter`]，没有章节的单词使用 `metadata` 的标签。
+/// Anki 的标签以空格分隔，标签中的空白替换为下划线
+pub fn write_deck(words: &[Word], metadata: &BookMetadata) -> Result<Vec<u8>> {
+    let path = std::env::temp_dir().join(format!(
+        "bbdc_apkg_export_{}_{}.sqlite",
+        std::process::id(),
+        chrono::Local::now().timestamp_nanos_opt().unwrap_or_default()
+    ));
+    let db = Connection::open(&path)
+        .map_err(sql_error)
+        .and_then(|conn| write_collection(&conn, words, metadata))
+        .and_then(|()| Ok(std::fs::read(&path)?));
+    std::fs::remove_file(&path).ok();
+    let db = db?;
+
+    let mut apkg = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let zip_error = |e: zip::result::ZipError| Error::Parse(format!("无法写入 Anki 牌组: {}", e));
+    apkg.start_file("collection.anki2", zip::write::FileOptions::default()).map_err(zip_error)?;
+    apkg.write_all(&db)?;
+    apkg.start_file("media", zip::write::FileOptions::default()).map_err(zip_error)?;
+    apkg.write_all(b"{}")?;
+    Ok(apkg.finish().map_err(zip_error)?.into_inner())
+}
+
+/// 建表并写入笔记类型、牌组、笔记和卡片
+fn write_collection(conn: &Connection, words: &[Word], metadata: &BookMetadata) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE col (id integer primary key, crt integer not null, mod integer not null, scm integer not null,
+             ver integer not null, dty integer not null, usn integer not null, ls integer not null, conf text not null,
+             models text not null, decks text not null, dconf text not null, tags text not null);
+         CREATE TABLE notes (id integer primary key, guid text not null, mid integer not null, mod integer not null,
+             usn integer not null, tags text not null, flds text not null, sfld text not null, csum integer not null,
+             flags integer not null, data text not null);
+         CREATE TABLE cards (id integer primary key, nid integer not null, did integer not null, ord integer not null,
+             mod integer not null, usn integer not null, type integer not null, queue integer not null, due integer not null,
+             ivl integer not null, factor integer not null, reps integer not null, lapses integer not null, left integer not null,
+             odue integer not null, odid integer not null, flags integer not null, data text not null);
+         CREATE TABLE revlog (id integer primary key, cid integer not null, usn integer not null, ivl integer not null,
+             lastIvl integer not null, factor integer not null, time integer not null, type integer not null);
+         CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null);",
+    )
+    .map_err(sql_error)?;
+
+    let now = chrono::Local::now().timestamp_millis();
+    let title = if metadata.title.trim().is_empty() { DEFAULT_DECK } else { metadata.title.trim() };
+
+    // 牌组：默认牌组、以标题命名的牌组和各章的子牌组，ID 按出现顺序从 now 递增
+    let mut decks: Vec<(String, i64)> = vec![(title.to_string(), now)];
+    let deck_of = |chapter: Option<&str>| chapter.map_or_else(|| title.to_string(), |chapter| format!("{}::{}", title, chapter));
+    for word in words {
+        let name = deck_of(word.chapter.as_deref());
+        if !decks.iter().any(|(deck, _)| *deck == name) {
+            decks.push((name, now + decks.len() as i64));
+        }
+    }
+    let deck_json = |id: i64, name: &str, desc: &str| {
+        serde_json::json!({
+            "id": id, "name": name, "desc": desc, "mod": now / 1000, "usn": -1, "collapsed": false, "dyn": 0, "conf": 1,
+            "newToday": [0, 0], "revToday": [0, 0], "lrnToday": [0, 0], "timeToday": [0, 0], "extendNew": 10, "extendRev": 50,
+        })
+    };
+    let mut deck_map = serde_json::Map::new();
+    deck_map.insert("1".to_string(), deck_json(1, "Default", ""));
+    for (name, id) in &decks {
+        deck_map.insert(id.to_string(), deck_json(*id, name, &metadata.description));
+    }
+
+    let model_id = now;
+    let fields: Vec<serde_json::Value> = NOTE_FIELDS
+        .iter()
+        .enumerate()
+        .map(|(ord, name)| serde_json::json!({ "name": name, "ord": ord, "sticky": false, "rtl": false, "font": "Arial", "size": 20, "media": [] }))
+        .collect();
+    let model = serde_json::json!({
+        "id": model_id, "name": "不背单词", "type": 0, "mod": now / 1000, "usn": -1, "sortf": 0, "did": decks[0].1,
+        "flds": fields,
+        "tmpls": [{
+            "name": "卡片 1", "ord": 0, "did": null, "bqfmt": "", "bafmt": "",
+            "qfmt": "{{单词}}",
+            "afmt": "{{FrontSide}}<hr id=answer>{{音标}}<br>{{释义}}<br><i>{{例句}}</i>",
+        }],
+        "css": ".card { font-family: arial; font-size: 20px; text-align: center; color: black; background-color: white; }",
+        "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage[utf8]{inputenc}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+        "latexPost": "\\end{document}",
+        "tags": [], "vers": [], "req": [[0, "any", [0]]],
+    });
+    let conf = serde_json::json!({
+        "nextPos": words.len() + 1, "estTimes": true, "activeDecks": [1], "sortType": "noteFld", "timeLim": 0,
+        "sortBackwards": false, "addToCur": true, "curDeck": 1, "newBury": true, "newSpread": 0, "dueCounts": true,
+        "curModel": model_id.to_string(), "collapseTime": 1200,
+    });
+    let dconf = serde_json::json!({ "1": {
+        "id": 1, "name": "Default", "mod": 0, "usn": 0, "maxTaken": 60, "autoplay": true, "timer": 0, "replayq": true, "dyn": false,
+        "new": { "delays": [1, 10], "ints": [1, 4, 7], "initialFactor": 2500, "order": 1, "perDay": 20, "bury": true, "separate": true },
+        "lapse": { "delays": [10], "mult": 0, "minInt": 1, "leechFails": 8, "leechAction": 0 },
+        "rev": { "perDay": 200, "ease4": 1.3, "fuzz": 0.05, "minSpace": 1, "ivlFct": 1, "maxIvl": 36500, "bury": true, "hardFactor": 1.2 },
+    }});
+    conn.execute(
+        "INSERT INTO col VALUES (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+        params![
+            now / 1000,
+            now,
+            conf.to_string(),
+            serde_json::json!({ model_id.to_string(): model }).to_string(),
+            serde_json::Value::Object(deck_map).to_string(),
+            dconf.to_string()
+        ],
+    )
+    .map_err(sql_error)?;
+
+    for (i, word) in words.iter().enumerate() {
+        let id = now + i as i64;
+        let chapter = word.chapter.as_deref();
+        let tags = chapter.map_or_else(|| metadata.tags.clone(), |chapter| metadata.for_chapter(chapter).tags);
+        let tags: Vec<String> = tags.iter().map(|tag| tag.split_whitespace().collect::<Vec<_>>().join("_")).filter(|tag| !tag.is_empty()).collect();
+        let tags = if tags.is_empty() { String::new() } else { format!(" {} ", tags.join(" ")) };
+        let flds = [
+            escape_html(&word.word),
+            escape_html(&word.meaning),
+            escape_html(word.phonetic.as_deref().unwrap_or_default()),
+            escape_html(word.example.as_deref().unwrap_or_default()),
+        ]
+        .join("\u{1f}");
+        let csum = i64::from_str_radix(&sha1_smol::Sha1::from(word.word.as_bytes()).digest().to_string()[..8], 16).unwrap_or_default();
+        conn.execute(
+            "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+            params![id, format!("bbdc{:x}", id), model_id, now / 1000, tags, flds, word.word, csum],
+        )
+        .map_err(sql_error)?;
+        let deck = deck_of(chapter);
+        let did = decks.iter().find(|(name, _)| *name == deck).map_or(decks[0].1, |(_, id)| *id);
+        conn.execute(
+            "INSERT INTO cards VALUES (?1, ?1, ?2, 0, ?3, -1, 0, 0, ?4, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+            params![id, did, now / 1000, i as i64 + 1],
+        )
+        .map_err(sql_error)?;
+    }
+
+    Ok(())
+}
+
+/// 转义字段中的 HTML 特殊字符，Anki 按 HTML 显示字段
+fn escape_html(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_read_legacy_collection() {
+        let path = std::env::temp_dir().join(format!("bbdc_apkg_test_{}.sqlite", std::process::id()));
+        {
+            let conn = Connection::open(&path).unwrap();
+            conn.execute_batch(
+                r#"CREATE TABLE col (models TEXT);
+                   INSERT INTO col VALUES ('{"1": {"flds": [{"name": "Back", "ord": 1}, {"name": "Front", "ord": 0}]}}');
+                   CREATE TABLE notes (id INTEGER, mid INTEGER, flds TEXT);
+                   INSERT INTO notes VALUES (2, 1, 'abandon' || char(31) || '<b>放弃</b>&nbsp;[sound:a.mp3]');
+                   INSERT INTO notes VALUES (1, 1, 'ability' || char(31) || '能力<br>才能');"#,
+            )
+            .unwrap();
+        }
+        let db = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        let mut apkg = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        apkg.start_file("collection.anki2", zip::write::FileOptions::default()).unwrap();
+        apkg.write_all(&db).unwrap();
+        let apkg = apkg.finish().unwrap().into_inner();
+
+        let notes = read_notes(&apkg).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].field("front"), Some("ability"));
+        assert_eq!(notes[0].field("2"), Some("能力 才能"));
+        assert_eq!(notes[1].field("Back"), Some("放弃"));
+    }
+
+    #[test]
+    fn test_write_deck_with_chapters() {
+        let word = |word: &str, meaning: &str, chapter: Option<&str>| Word {
+            number: "1".to_string(),
+            word: word.to_string(),
+            meaning: meaning.to_string(),
+            phonetic: None,
+            example: None,
+            pos: Vec::new(),
+            line_number: None,
+            page: None,
+            frequency: None,
+            difficulty: None,
+            level: None,
+            chapter: chapter.map(str::to_string),
+            source: None,
+        };
+        let words = [word("abandon", "放弃", Some("Unit 1")), word("a<b", "能力", None)];
+        let metadata = BookMetadata { title: "新概念".to_string(), tags: vec!["英语".to_string()], ..Default::default() };
+
+        let apkg = write_deck(&words, &metadata).unwrap();
+        let notes = read_notes(&apkg).unwrap();
+        assert_eq!(notes[0].field("单词"), Some("abandon"));
+        assert_eq!(notes[0].field("2"), Some("放弃"));
+        assert_eq!(notes[1].field("1"), Some("a<b"));
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(apkg)).unwrap();
+        let mut db = Vec::new();
+        archive.by_name("collection.anki2").unwrap().read_to_end(&mut db).unwrap();
+        let path = std::env::temp_dir().join(format!("bbdc_apkg_export_test_{}.sqlite", std::process::id()));
+        std::fs::write(&path, db).unwrap();
+        let conn = Connection::open(&path).unwrap();
+        let tags: Vec<String> = conn
+            .prepare("SELECT tags FROM notes ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        let decks: String = conn.query_row("SELECT decks FROM col", [], |row| row.get(0)).unwrap();
+        drop(conn);
+        std::fs::remove_file(&path).ok();
+        assert_eq!(tags, [" 英语 Unit_1 ", " 英语 "]);
+        assert!(decks.contains("新概念::Unit 1"));
+    }
+}
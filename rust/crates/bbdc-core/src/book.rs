@@ -0,0 +1,279 @@
+//! 词书模块
+//!
+//! [`Book`] 是提取、核对和导出之间传递的词书：名称、元数据、有序的词条和章节。
+//! 只能通过校验过的构造函数修改，始终满足以下约束：
+//! - 名称和章节标题不为空
+//! - 词条的单词不为空、至少含一个字母、不含换行，且不重复（不区分大小写）
+//! - 章节按词条顺序排列，每个章节至少包含一个词条
+
+use crate::{text, BookMetadata, PartOfSpeech, CheckResult, Error, ExtractResult, Result, Word};
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// 核对未通过的词条带有的标签
+pub const UNRECOGNIZED_TAG: &str = "unrecognized";
+
+/// 词条
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Entry {
+    word: String,
+    meaning: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    phonetic: Option<String>,
+    /// 词性缩写、核对结果等标签
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+}
+
+impl Entry {
+    /// 创建词条，单词去掉首尾空白后必须含有字母且不含换行
+    pub fn new(word: &str, meaning: &str) -> Result<Self> {
+        let word = word.trim();
+        if !word.chars().any(text::is_letter) || word.contains(['\n', '\r']) {
+            return Err(Error::Parse(format!("无效的单词: {:?}", word)));
+        }
+        Ok(Self { word: word.to_string(), meaning: meaning.trim().to_string(), phonetic: None, tags: Vec::new() })
+    }
+
+    pub fn with_phonetic(mut self, phonetic: Option<String>) -> Self {
+        self.phonetic = phonetic.filter(|p| !p.trim().is_empty());
+        self
+    }
+
+    /// 添加标签，已有或为空时忽略
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.add_tag(tag);
+        self
+    }
+
+    fn add_tag(&mut self, tag: &str) {
+        let tag = tag.trim();
+        if !tag.is_empty() && !self.tags.iter().any(|t| t == tag) {
+            self.tags.push(tag.to_string());
+        }
+    }
+
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    pub fn meaning(&self) -> &str {
+        &self.meaning
+    }
+
+    pub fn phonetic(&self) -> Option<&str> {
+        self.phonetic.as_deref()
+    }
+
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+}
+
+/// 章节：从 `start` 开始到下一章节开始之前的词条
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Chapter {
+    pub title: String,
+    /// 第一个词条的下标
+    pub start: usize,
+}
+
+/// 词书
+#[derive(Debug, Clone, Serialize)]
+pub struct Book {
+    name: String,
+    metadata: BookMetadata,
+    entries: Vec<Entry>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    chapters: Vec<Chapter>,
+    /// 已有单词的规范形式，用于去重
+    #[serde(skip)]
+    keys: HashSet<String>,
+}
+
+impl Book {
+    /// 创建空词书，名称不能为空
+    pub fn new(name: &str, metadata: BookMetadata) -> Result<Self> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(Error::Parse("词书名称不能为空".to_string()));
+        }
+        Ok(Self { name: name.to_string(), metadata, entries: Vec::new(), chapters: Vec::new(), keys: HashSet::new() })
+    }
+
+    /// 由提取结果创建：单词按顺序成为词条，词性缩写作为标签，重复的单词只保留第一个；
+    /// 单词所在的 Markdown 章节成为词书的章节，没有章节时批量提取的每个源文件成为一个章节
+    pub fn from_extract(name: &str, metadata: BookMetadata, result: &ExtractResult) -> Result<Self> {
+        let mut book = Self::new(name, metadata)?;
+        for word in &result.words {
+            let source = word.source.as_deref().filter(|s| result.files.iter().any(|f| f == s));
+            if let Some(title) = word.chapter.as_deref().or(source) {
+                if book.chapters.last().map_or(true, |c| c.title != title) {
+                    book.start_chapter(title)?;
+                }
+            }
+            let entry = word.pos.iter().fold(
+                Entry::new(&word.word, &word.meaning)?.with_phonetic(word.phonetic.clone()),
+                |entry, pos| entry.with_tag(pos.abbreviation()),
+            );
+            if !book.push(entry) {
+                log::debug!("词书中已有 {}，跳过", word.word);
+            }
+        }
+        book.drop_empty_chapter();
+        Ok(book)
+    }
+
+    /// 由核对结果创建：识别成功的单词按顺序成为词条（没有释义），即不背单词可以导入的词书
+    pub fn from_check(name: &str, metadata: BookMetadata, check: &CheckResult) -> Result<Self> {
+        let mut book = Self::new(name, metadata)?;
+        for word in &check.recognized_words {
+            book.push(Entry::new(word, "")?);
+        }
+        Ok(book)
+    }
+
+    /// 追加词条，单词已存在时不追加并返回 false
+    pub fn push(&mut self, entry: Entry) -> bool {
+        if !self.keys.insert(text::normalize_key(&entry.word)) {
+            return false;
+        }
+        self.entries.push(entry);
+        true
+    }
+
+    /// 开始新章节，之后追加的词条属于该章节；上一章节没有词条时替换它
+    pub fn start_chapter(&mut self, title: &str) -> Result<()> {
+        let title = title.trim();
+        if title.is_empty() {
+            return Err(Error::Parse("章节标题不能为空".to_string()));
+        }
+        self.drop_empty_chapter();
+        self.chapters.push(Chapter { title: title.to_string(), start: self.entries.len() });
+        Ok(())
+    }
+
+    /// 去掉末尾没有词条的章节
+    fn drop_empty_chapter(&mut self) {
+        if self.chapters.last().is_some_and(|c| c.start == self.entries.len()) {
+            self.chapters.pop();
+        }
+    }
+
+    /// 为核对未通过的词条加上 [`UNRECOGNIZED_TAG`] 标签，返回标记的数量
+    pub fn apply_check(&mut self, check: &CheckResult) -> usize {
+        let unrecognized: HashSet<String> = check.unrecognized_words.iter().map(|w| text::normalize_key(w)).collect();
+        let mut tagged = 0;
+        for entry in &mut self.entries {
+            if unrecognized.contains(&text::normalize_key(&entry.word)) && !entry.has_tag(UNRECOGNIZED_TAG) {
+                entry.add_tag(UNRECOGNIZED_TAG);
+                tagged += 1;
+            }
+        }
+        tagged
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn metadata(&self) -> &BookMetadata {
+        &self.metadata
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 各章节的标题和词条；第一个章节之前的词条不属于任何章节
+    pub fn chapters(&self) -> Vec<(&str, &[Entry])> {
+        self.chapters
+            .iter()
+            .enumerate()
+            .map(|(i, chapter)| {
+                let end = self.chapters.get(i + 1).map_or(self.entries.len(), |next| next.start);
+                (chapter.title.as_str(), &self.entries[chapter.start..end])
+            })
+            .collect()
+    }
+}
+
+impl From<&Book> for ExtractResult {
+    /// 转换为提取结果供导出使用：序号从 1 开始，出处为所在章节，词性缩写标签还原为词性
+    fn from(book: &Book) -> Self {
+        let chapter_of = |i: usize| book.chapters.iter().rev().find(|c| c.start <= i).map(|c| c.title.clone());
+        let words: Vec<Word> = book
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| Word {
+                number: (i + 1).to_string(),
+                word: entry.word.clone(),
+                meaning: entry.meaning.clone(),
+                phonetic: entry.phonetic.clone(),
+                example: None,
+                pos: entry.tags.iter().filter_map(|t| PartOfSpeech::from_abbreviation(t.trim_end_matches('.'))).collect(),
+                line_number: None,
+                page: None,
+                frequency: None,
+                difficulty: None,
+                level: None,
+                chapter: None,
+                source: chapter_of(i),
+            })
+            .collect();
+        ExtractResult {
+            total_words: words.len(),
+            total_phrases: 0,
+            words,
+            phrases: Vec::new(),
+            skipped: Vec::new(),
+            cleaned: Vec::new(),
+            files: book.chapters.iter().map(|c| c.title.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_book_invariants() {
+        assert!(Book::new("  ", BookMetadata::default()).is_err());
+        assert!(Entry::new("123", "数字").is_err());
+
+        let mut book = Book::new("unit1", BookMetadata::default()).unwrap();
+        book.start_chapter("Unit 1").unwrap();
+        book.start_chapter("Unit 1A").unwrap();
+        assert!(book.push(Entry::new("abandon", "放弃").unwrap()));
+        assert!(!book.push(Entry::new("Abandon", "抛弃").unwrap()));
+        book.start_chapter("Unit 2").unwrap();
+        book.push(Entry::new("abilty", "能力").unwrap());
+
+        let chapters = book.chapters();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[0].0, "Unit 1A");
+        assert_eq!(chapters[1].1[0].word(), "abilty");
+
+        let check = CheckResult::from_lists(["abandon"], ["abilty"]);
+        assert_eq!(book.apply_check(&check), 1);
+        assert!(book.entries()[1].has_tag(UNRECOGNIZED_TAG));
+
+        let result = ExtractResult::from(&book);
+        assert_eq!(result.words[1].source.as_deref(), Some("Unit 2"));
+        assert_eq!(Book::from_check("recognized", BookMetadata::default(), &check).unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,321 @@
+//! 核对结果
+//!
+//! 与具体核对后端无关的结果类型，供报告等模块使用
+
+use crate::{text, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 核对结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheckResult {
+    pub recognized_words: Vec<String>,
+    pub unrecognized_words: Vec<String>,
+    pub recognized_count: usize,
+    pub unrecognized_count: usize,
+    pub total_count: usize,
+    /// 不背单词识别为其他写法的单词：提交的写法 → 不背单词保存的写法（如 `Colour` → `colour`）
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub normalized: BTreeMap<String, String>,
+    /// 上传前去掉的空行和重复单词（不区分大小写）数，不计入 `total_count`
+    #[serde(default)]
+    pub removed_count: usize,
+    /// 识别失败、但换一种写法（小写、原形、去掉派生后缀）即可识别的单词：原词 → 识别成功的写法
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variants: BTreeMap<String, String>,
+}
+
+/// 两次核对结果的差异，见 [`CheckResult::diff`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CheckDiff {
+    /// 两次的识别成功数
+    pub recognized_count: (usize, usize),
+    /// 两次核对的单词数
+    pub total_count: (usize, usize),
+    /// 前一次识别失败、后一次识别成功的单词
+    pub newly_recognized: Vec<String>,
+    /// 前一次识别成功、后一次识别失败的单词
+    pub newly_unrecognized: Vec<String>,
+    /// 两次都识别失败的单词
+    pub still_unrecognized: Vec<String>,
+    /// 只在后一次核对的单词（如更正后的写法）中识别成功的
+    pub added_recognized: Vec<String>,
+    /// 只在后一次核对的单词中识别失败的
+    pub added_unrecognized: Vec<String>,
+    /// 只在前一次核对的单词（如被更正掉的写法）
+    pub removed: Vec<String>,
+}
+
+impl CheckDiff {
+    /// 两次核对的单词和识别结果是否完全相同
+    pub fn is_identical(&self) -> bool {
+        self.newly_recognized.is_empty()
+            && self.newly_unrecognized.is_empty()
+            && self.added_recognized.is_empty()
+            && self.added_unrecognized.is_empty()
+            && self.removed.is_empty()
+    }
+}
+
+/// 单个来源（文件、章节）的核对统计
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SourceCheck {
+    pub source: String,
+    pub total_count: usize,
+    pub recognized_count: usize,
+    pub unrecognized_words: Vec<String>,
+}
+
+impl SourceCheck {
+    /// 把单独核对的一个来源的结果作为该来源的统计
+    pub fn new(source: &str, result: &CheckResult) -> Self {
+        Self {
+            source: source.to_string(),
+            total_count: result.total_count,
+            recognized_count: result.recognized_count,
+            unrecognized_words: result.unrecognized_words.clone(),
+        }
+    }
+
+    /// 识别成功率（0~1），没有单词时为 1
+    pub fn recognition_rate(&self) -> f64 {
+        if self.total_count == 0 {
+            1.0
+        } else {
+            self.recognized_count as f64 / self.total_count as f64
+        }
+    }
+}
+
+impl CheckResult {
+    /// 由识别成功和识别失败的单词创建核对结果，按两个列表计算各项数量
+    pub fn from_lists<R, U>(recognized_words: R, unrecognized_words: U) -> Self
+    where
+        R: IntoIterator,
+        R::Item: Into<String>,
+        U: IntoIterator,
+        U::Item: Into<String>,
+    {
+        let recognized_words: Vec<String> = recognized_words.into_iter().map(Into::into).collect();
+        let unrecognized_words: Vec<String> = unrecognized_words.into_iter().map(Into::into).collect();
+        CheckResult {
+            recognized_count: recognized_words.len(),
+            unrecognized_count: unrecognized_words.len(),
+            total_count: recognized_words.len() + unrecognized_words.len(),
+            recognized_words,
+            unrecognized_words,
+            normalized: BTreeMap::new(),
+            removed_count: 0,
+            variants: BTreeMap::new(),
+        }
+    }
+
+    /// 按来源拆分核对结果，`sources` 为单词及其来源；按识别成功率从低到高排列，
+    /// 成功率相同时按来源首次出现的顺序
+    pub fn by_source<'a, I>(&self, sources: I) -> Vec<SourceCheck>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let unrecognized: HashSet<String> = self.unrecognized_words.iter().map(|w| text::normalize_key(w)).collect();
+        let mut groups: Vec<SourceCheck> = Vec::new();
+        for (word, source) in sources {
+            let index = match groups.iter().position(|g| g.source == source) {
+                Some(index) => index,
+                None => {
+                    groups.push(SourceCheck {
+                        source: source.to_string(),
+                        total_count: 0,
+                        recognized_count: 0,
+                        unrecognized_words: Vec::new(),
+                    });
+                    groups.len() - 1
+                }
+            };
+            let group = &mut groups[index];
+            group.total_count += 1;
+            if unrecognized.contains(&text::normalize_key(word)) {
+                group.unrecognized_words.push(word.to_string());
+            } else {
+                group.recognized_count += 1;
+            }
+        }
+        groups.sort_by(|a, b| a.recognition_rate().total_cmp(&b.recognition_rate()));
+        groups
+    }
+
+    /// 对照提交的单词找出不背单词改写过的写法，记录到 [`normalized`](Self::normalized)
+    ///
+    /// 只有大小写不同时直接对应；响应中没有出现的提交单词与响应中多出的识别单词按顺序一一对应
+    /// （接口按提交顺序返回单词），两边数量不同时多出的部分不记录
+    pub fn detect_normalized(&mut self, submitted: &[&str]) {
+        let returned: HashMap<String, &String> =
+            self.recognized_words.iter().chain(&self.unrecognized_words).map(|w| (text::normalize_key(w), w)).collect();
+        let submitted_keys: HashSet<String> = submitted.iter().map(|w| text::normalize_key(w)).collect();
+
+        let mut seen = HashSet::new();
+        let mut missing = Vec::new();
+        for word in submitted.iter().map(|w| w.trim()).filter(|w| seen.insert(text::normalize_key(w))) {
+            match returned.get(&text::normalize_key(word)) {
+                Some(form) if form.as_str() != word => {
+                    self.normalized.insert(word.to_string(), form.to_string());
+                }
+                Some(_) => {}
+                None => missing.push(word),
+            }
+        }
+        let extra = self.recognized_words.iter().filter(|w| !submitted_keys.contains(&text::normalize_key(w)));
+        for (word, form) in missing.into_iter().zip(extra) {
+            self.normalized.insert(word.to_string(), form.clone());
+        }
+    }
+
+    /// 输入文件对应的识别成功、识别失败单词文件：与输入文件同目录的
+    /// `<文件名>_recognized.txt` 和 `<文件名>_unrecognized.txt`
+    pub fn word_list_paths<P: AsRef<Path>>(input: P) -> (PathBuf, PathBuf) {
+        let input = input.as_ref();
+        let stem = input.file_stem().and_then(|s| s.to_str()).unwrap_or("words");
+        (
+            input.with_file_name(format!("{}_recognized.txt", stem)),
+            input.with_file_name(format!("{}_unrecognized.txt", stem)),
+        )
+    }
+
+    /// 把识别成功和识别失败的单词分别保存到 [`word_list_paths`](Self::word_list_paths)，
+    /// 每行一个单词，可以直接再次核对；返回两个文件的路径
+    pub fn save_word_lists<P: AsRef<Path>>(&self, input: P) -> Result<(PathBuf, PathBuf)> {
+        let (recognized, unrecognized) = Self::word_list_paths(input);
+        let lines = |words: &[String]| words.iter().map(|w| format!("{}\n", w)).collect::<String>();
+        fs::write(&recognized, lines(&self.recognized_words))?;
+        fs::write(&unrecognized, lines(&self.unrecognized_words))?;
+        Ok((recognized, unrecognized))
+    }
+
+    /// 与后一次核对结果 `after` 比较（不区分大小写），单词按各自结果中的顺序和写法列出。
+    /// 应用更正后再次核对时，被更正的单词出现在 `removed`，更正后的写法出现在 `added_*`
+    pub fn diff(&self, after: &CheckResult) -> CheckDiff {
+        let keys = |words: &[String]| words.iter().map(|w| text::normalize_key(w)).collect::<HashSet<String>>();
+        let (before_recognized, before_unrecognized) = (keys(&self.recognized_words), keys(&self.unrecognized_words));
+        let (after_recognized, after_unrecognized) = (keys(&after.recognized_words), keys(&after.unrecognized_words));
+        let select = |words: &[String], keep: &dyn Fn(&str) -> bool| -> Vec<String> {
+            words.iter().filter(|w| keep(&text::normalize_key(w))).cloned().collect()
+        };
+        let in_before = |key: &str| before_recognized.contains(key) || before_unrecognized.contains(key);
+        let in_after = |key: &str| after_recognized.contains(key) || after_unrecognized.contains(key);
+
+        let mut removed = select(&self.recognized_words, &|key| !in_after(key));
+        removed.extend(select(&self.unrecognized_words, &|key| !in_after(key)));
+        CheckDiff {
+            recognized_count: (self.recognized_count, after.recognized_count),
+            total_count: (self.total_count, after.total_count),
+            newly_recognized: select(&after.recognized_words, &|key| before_unrecognized.contains(key)),
+            newly_unrecognized: select(&after.unrecognized_words, &|key| before_recognized.contains(key)),
+            still_unrecognized: select(&after.unrecognized_words, &|key| before_unrecognized.contains(key)),
+            added_recognized: select(&after.recognized_words, &|key| !in_before(key)),
+            added_unrecognized: select(&after.unrecognized_words, &|key| !in_before(key)),
+            removed,
+        }
+    }
+
+    /// 读取保存的核对结果：`check --json` 的输出，核对多个文件时取其中的 `overall`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if let Some(overall) = value.get_mut("overall") {
+            value = overall.take();
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// 以 JSON 保存核对结果（与 `check --json` 单个文件时的输出相同），可用 [`load`](Self::load) 读取
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 合并多份核对结果（如分别核对的多个文件）
+    pub fn merge(results: &[CheckResult]) -> CheckResult {
+        let mut merged = CheckResult::from_lists(
+            results.iter().flat_map(|result| result.recognized_words.iter().cloned()),
+            results.iter().flat_map(|result| result.unrecognized_words.iter().cloned()),
+        );
+        for result in results {
+            merged.normalized.extend(result.normalized.iter().map(|(k, v)| (k.clone(), v.clone())));
+            merged.removed_count += result.removed_count;
+            merged.variants.extend(result.variants.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_source() {
+        let check = CheckResult::from_lists(["abandon", "ability"], ["abroed"]);
+        let sources = [("abandon", "unit1.md"), ("Abroed", "unit2.md"), ("ability", "unit2.md")];
+
+        let groups = check.by_source(sources);
+        assert_eq!(groups[0].source, "unit2.md");
+        assert_eq!((groups[0].total_count, groups[0].recognized_count), (2, 1));
+        assert_eq!(groups[0].unrecognized_words, ["Abroed"]);
+        assert_eq!(groups[1].recognition_rate(), 1.0);
+
+        let merged = CheckResult::merge(&[check.clone(), check]);
+        assert_eq!((merged.total_count, merged.unrecognized_words.len()), (6, 2));
+    }
+
+    #[test]
+    fn test_detect_normalized() {
+        let mut check = CheckResult::from_lists(["colour", "teacher", "abroad"], Vec::<String>::new());
+        check.detect_normalized(&["Colour", "teacher's", "abroad"]);
+
+        assert_eq!(check.normalized.len(), 2);
+        assert_eq!(check.normalized["Colour"], "colour");
+        assert_eq!(check.normalized["teacher's"], "teacher");
+    }
+
+    #[test]
+    fn test_diff() {
+        let before = CheckResult::from_lists(["abandon", "abroad"], ["abilty", "Colour", "xyzzy"]);
+        let after = CheckResult::from_lists(["abandon", "ability", "colour"], ["abroad", "xyzzy"]);
+
+        let diff = before.diff(&after);
+        assert_eq!(diff.newly_recognized, ["colour"]);
+        assert_eq!(diff.newly_unrecognized, ["abroad"]);
+        assert_eq!(diff.still_unrecognized, ["xyzzy"]);
+        assert_eq!(diff.added_recognized, ["ability"]);
+        assert!(diff.added_unrecognized.is_empty());
+        assert_eq!(diff.removed, ["abilty"]);
+        assert_eq!(diff.recognized_count, (2, 3));
+        assert!(!diff.is_identical());
+        assert!(after.diff(&after).is_identical());
+
+        let path = std::env::temp_dir().join(format!("bbdc_check_diff_{}.json", std::process::id()));
+        fs::write(&path, serde_json::json!({ "overall": after, "by_source": [] }).to_string()).unwrap();
+        let loaded = CheckResult::load(&path).unwrap();
+        assert_eq!(loaded.recognized_words, after.recognized_words);
+        before.save(&path).unwrap();
+        let loaded = CheckResult::load(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(before.diff(&loaded).is_identical());
+    }
+
+    #[test]
+    fn test_save_word_lists() {
+        let dir = std::env::temp_dir().join(format!("bbdc_word_lists_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let check = CheckResult::from_lists(["abandon", "ability"], ["abroed"]);
+
+        let (recognized, unrecognized) = check.save_word_lists(dir.join("unit1.txt")).unwrap();
+        assert_eq!(unrecognized, dir.join("unit1_unrecognized.txt"));
+        assert_eq!(fs::read_to_string(&recognized).unwrap(), "abandon\nability\n");
+        assert_eq!(fs::read_to_string(&unrecognized).unwrap(), "abroed\n");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
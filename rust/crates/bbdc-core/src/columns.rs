@@ -0,0 +1,332 @@
+//! 表格列映射模块
+//!
+//! 默认的表格布局为「序号、单词/短语、词义」。[`ColumnMapping`] 按列序号或表头名
+//! 指定各字段所在的列，把其他布局的表格（单词在前、多出音标列等）转换为默认布局，
+//! 指定了音标列时音标作为第 4 列，指定了例句列时例句作为第 5 列。
+//! 单词和释义写在同一个单元格中（`abandon - 放弃`、`abandon (放弃)`、换行分隔）时，
+//! 用 [`with_split`](ColumnMapping::with_split) 按分隔符拆开；一行并排多组单词（6 列的表格
+//! 左右各一组序号、单词、词义）时，用 [`with_groups`](ColumnMapping::with_groups) 拆成独立的条目
+
+use crate::{Error, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// 列引用
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+    /// 列序号（从 0 开始）
+    Index(usize),
+    /// 表头名（忽略大小写和首尾空白）
+    Header(String),
+}
+
+impl FromStr for Column {
+    type Err = Error;
+
+    /// 数字解析为从 1 开始的列序号，其余解析为表头名
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        match s.parse::<usize>() {
+            Ok(0) => Err(Error::Parse("列序号从 1 开始".to_string())),
+            Ok(n) => Ok(Column::Index(n - 1)),
+            Err(_) if s.is_empty() => Err(Error::Parse("列名不能为空".to_string())),
+            Err(_) => Ok(Column::Header(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Column::Index(i) => write!(f, "{}", i + 1),
+            Column::Header(name) => f.write_str(name),
+        }
+    }
+}
+
+/// 表格列映射
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnMapping {
+    /// 序号列，None 时按行自动编号
+    pub number: Option<Column>,
+    pub word: Column,
+    /// 词义列，None 时词义为空
+    pub meaning: Option<Column>,
+    /// 音标列，None 时不读取音标
+    pub phonetic: Option<Column>,
+    /// 例句列，None 时不读取例句
+    pub example: Option<Column>,
+    /// 单词单元格中单词与释义之间的分隔符，None 时不拆分
+    pub split: Option<String>,
+    /// 每行并排的组数，1 为不拆分
+    pub groups: usize,
+}
+
+impl Default for ColumnMapping {
+    /// 序号、单词/短语、词义依次为第 1、2、3 列
+    fn default() -> Self {
+        Self {
+            number: Some(Column::Index(0)),
+            word: Column::Index(1),
+            meaning: Some(Column::Index(2)),
+            phonetic: None,
+            example: None,
+            split: None,
+            groups: 1,
+        }
+    }
+}
+
+impl FromStr for ColumnMapping {
+    type Err = Error;
+
+    /// 解析 `word=2,meaning=释义,number=1,phonetic=音标,example=例句` 形式的配置
+    ///
+    /// `word` 必须指定；未指定 `number` 时自动编号，未指定 `meaning` 时词义为空
+    fn from_str(s: &str) -> Result<Self> {
+        let mut number = None;
+        let mut word = None;
+        let mut meaning = None;
+        let mut phonetic = None;
+        let mut example = None;
+
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| Error::Parse(format!("列映射格式应为 字段=列: {}", part)))?;
+            let column = Some(value.parse()?);
+            match key.trim().to_lowercase().as_str() {
+                "number" | "no" => number = column,
+                "word" => word = column,
+                "meaning" => meaning = column,
+                "phonetic" | "ipa" => phonetic = column,
+                "example" | "sentence" => example = column,
+                other => return Err(Error::Parse(format!("未知的列映射字段: {}（可用 number、word、meaning、phonetic、example）", other))),
+            }
+        }
+
+        Ok(Self {
+            number,
+            word: word.ok_or_else(|| Error::Parse("列映射必须指定 word 列".to_string()))?,
+            meaning,
+            phonetic,
+            example,
+            split: None,
+            groups: 1,
+        })
+    }
+}
+
+/// 表示换行的分隔符名称，同时匹配单元格中的换行和 `<br>`
+pub const NEWLINE_SEPARATOR: &str = "newline";
+
+/// 成对的括号：以左括号为分隔符时去掉释义末尾的右括号
+const BRACKETS: [(&str, char); 4] = [("(", ')'), ("（", '）'), ("[", ']'), ("【", '】')];
+
+/// 按分隔符拆开单词和释义，没有分隔符时返回 None
+fn split_cell<'a>(cell: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let (word, meaning) = if separator == NEWLINE_SEPARATOR {
+        ["\n", "<br>", "<br/>", "<br />"].iter().filter_map(|sep| cell.split_once(sep)).min_by_key(|(word, _)| word.len())?
+    } else {
+        cell.split_once(separator.trim()).filter(|_| !separator.trim().is_empty())?
+    };
+    let meaning = match BRACKETS.iter().find(|(open, _)| *open == separator.trim()) {
+        Some((_, close)) => meaning.trim().strip_suffix(*close).unwrap_or(meaning),
+        None => meaning,
+    };
+    let (word, meaning) = (word.trim(), meaning.trim());
+    (!word.is_empty()).then_some((word, meaning))
+}
+
+/// 把每行平均分为 `groups` 组，每组成为独立的行
+///
+/// 连续的数据行先输出各行的第一组，再输出第二组……，与书中先读左栏、再读右栏的顺序一致；
+/// 第一个单元格不是序号的行（表头、分区标题、没有序号列的表格）结束当前一段并按组依次输出
+fn split_groups(rows: Vec<Vec<String>>, groups: usize) -> Vec<Vec<String>> {
+    fn flush(output: &mut Vec<Vec<String>>, columns: &mut [Vec<Vec<String>>]) {
+        for column in columns.iter_mut() {
+            output.append(column);
+        }
+    }
+
+    let mut output = Vec::new();
+    let mut columns: Vec<Vec<Vec<String>>> = vec![Vec::new(); groups];
+    for row in rows {
+        let width = ((row.len() + groups - 1) / groups).max(1);
+        let numbered = row.first().is_some_and(|c| crate::text::is_number(c.trim()));
+        if !numbered {
+            flush(&mut output, &mut columns);
+        }
+        for (i, group) in row.chunks(width).enumerate() {
+            columns[i].push(group.to_vec());
+        }
+        if !numbered {
+            flush(&mut output, &mut columns);
+        }
+    }
+    flush(&mut output, &mut columns);
+    output
+}
+
+/// 常见的单词列表头，按列序号映射时跳过这些表头行
+const WORD_HEADERS: [&str; 7] = ["word", "words", "vocabulary", "单词", "短语", "词汇", "英文"];
+
+/// 解析后的列序号
+#[derive(Clone, Copy)]
+struct Resolved {
+    number: Option<usize>,
+    word: usize,
+    meaning: Option<usize>,
+    phonetic: Option<usize>,
+    example: Option<usize>,
+}
+
+impl ColumnMapping {
+    /// 设置单词单元格的分隔符：分隔符之前为单词，之后为释义（词义列不为空时追加在其后）；
+    /// 分隔符为左括号时去掉末尾的右括号，[`NEWLINE_SEPARATOR`] 按换行或 `<br>` 拆分
+    pub fn with_split(mut self, separator: Option<String>) -> Self {
+        self.split = separator;
+        self
+    }
+
+    /// 设置每行并排的组数，各组的列布局相同
+    pub fn with_groups(mut self, groups: usize) -> Self {
+        self.groups = groups.max(1);
+        self
+    }
+
+    /// 是否按表头名引用列
+    fn uses_headers(&self) -> bool {
+        [self.number.as_ref(), Some(&self.word), self.meaning.as_ref(), self.phonetic.as_ref(), self.example.as_ref()]
+            .into_iter()
+            .flatten()
+            .any(|c| matches!(c, Column::Header(_)))
+    }
+
+    /// 在表头行中查找各列，任一表头名不在该行中时返回 None
+    fn resolve(&self, row: &[String]) -> Option<Resolved> {
+        let find = |column: &Column| match column {
+            Column::Index(i) => Some(*i),
+            Column::Header(name) => row.iter().position(|cell| cell.trim().eq_ignore_ascii_case(name.trim())),
+        };
+        Some(Resolved {
+            number: match &self.number {
+                Some(column) => Some(find(column)?),
+                None => None,
+            },
+            word: find(&self.word)?,
+            meaning: match &self.meaning {
+                Some(column) => Some(find(column)?),
+                None => None,
+            },
+            phonetic: match &self.phonetic {
+                Some(column) => Some(find(column)?),
+                None => None,
+            },
+            example: match &self.example {
+                Some(column) => Some(find(column)?),
+                None => None,
+            },
+        })
+    }
+
+    /// 把表格行转换为默认布局 `[序号, 单词/短语, 词义]`，指定了音标列时追加 `/…/` 形式的音标，
+    /// 指定了例句列时再追加例句（没有音标时音标列留空）
+    ///
+    /// 按表头名映射时，匹配的表头行之后的行才会被转换，表头行本身被跳过；
+    /// 遇到新的表头行（如多个表格）时重新定位各列
+    pub fn apply(&self, rows: Vec<Vec<String>>) -> Vec<Vec<String>> {
+        let rows = if self.groups > 1 { split_groups(rows, self.groups) } else { rows };
+        if *self == Self::default().with_groups(self.groups) {
+            return rows;
+        }
+
+        let uses_headers = self.uses_headers();
+        let mut resolved = if uses_headers { None } else { self.resolve(&[]) };
+        let mut output = Vec::new();
+
+        for row in rows {
+            if uses_headers {
+                if let Some(header) = self.resolve(&row) {
+                    resolved = Some(header);
+                    continue;
+                }
+            }
+            let Some(columns) = resolved else { continue };
+
+            let cell = |i: usize| row.get(i).map(|c| c.trim().to_string()).unwrap_or_default();
+            let mut word = cell(columns.word);
+            if word.is_empty() || WORD_HEADERS.iter().any(|h| word.eq_ignore_ascii_case(h)) {
+                continue;
+            }
+            let number = match columns.number {
+                Some(i) => cell(i),
+                None => (output.len() + 1).to_string(),
+            };
+            let mut meaning = columns.meaning.map(cell).unwrap_or_default();
+            if let Some((term, gloss)) = self.split.as_deref().and_then(|sep| split_cell(&word, sep)) {
+                meaning = [gloss, meaning.as_str()].iter().filter(|m| !m.is_empty()).copied().collect::<Vec<_>>().join("；");
+                word = term.to_string();
+            }
+            let mut row = vec![number, word, meaning];
+            let phonetic = columns.phonetic.and_then(|i| crate::phonetics::normalize(&cell(i)));
+            let example = columns.example.map(cell).filter(|e| !e.is_empty());
+            match (phonetic, example) {
+                (phonetic, Some(example)) => row.extend([phonetic.unwrap_or_default(), example]),
+                (Some(phonetic), None) => row.push(phonetic),
+                (None, None) => {}
+            }
+            output.push(row);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter().map(|r| r.iter().map(|c| c.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn test_mapping_by_index_and_header() {
+        let table = rows(&[
+            &["Word", "Phonetic", "Meaning"],
+            &["abandon", "/əˈbændən/", "放弃"],
+            &["ability", "/əˈbɪləti/", "能力"],
+        ]);
+
+        let by_header: ColumnMapping = "word=word, meaning=Meaning".parse().unwrap();
+        assert_eq!(by_header.number, None);
+        assert_eq!(by_header.apply(table.clone()), rows(&[&["1", "abandon", "放弃"], &["2", "ability", "能力"]]));
+
+        let by_index: ColumnMapping = "word=1,meaning=3".parse().unwrap();
+        assert_eq!(by_index.apply(table.clone())[0], ["1", "abandon", "放弃"]);
+
+        let with_phonetic: ColumnMapping = "word=1,meaning=3,phonetic=2".parse().unwrap();
+        assert_eq!(with_phonetic.apply(table.clone())[0], ["1", "abandon", "放弃", "/əˈbændən/"]);
+
+        let with_example: ColumnMapping = "word=1,example=3".parse().unwrap();
+        assert_eq!(with_example.apply(table)[0], ["1", "abandon", "", "", "放弃"]);
+
+        let combined = rows(&[&["1", "abandon (放弃)", ""], &["2", "ability<br>能力", ""], &["3", "abroad", "在国外"]]);
+        let split = ColumnMapping::default().with_split(Some("(".to_string()));
+        assert_eq!(split.apply(combined.clone())[0], ["1", "abandon", "放弃"]);
+        let split = ColumnMapping::default().with_split(Some(NEWLINE_SEPARATOR.to_string()));
+        assert_eq!(split.apply(combined), rows(&[&["1", "abandon (放弃)", ""], &["2", "ability", "能力"], &["3", "abroad", "在国外"]]));
+
+        let side_by_side = rows(&[
+            &["NO.", "单词", "词义", "NO.", "单词", "词义"],
+            &["1", "abandon", "放弃", "3", "abroad", "在国外"],
+            &["2", "ability", "能力", "", "", ""],
+        ]);
+        let numbers: Vec<_> = ColumnMapping::default().with_groups(2).apply(side_by_side).into_iter().map(|row| row[0].clone()).collect();
+        assert_eq!(numbers, ["NO.", "NO.", "1", "2", "3", ""]);
+
+        assert!("meaning=2".parse::<ColumnMapping>().is_err());
+        assert!("word=0".parse::<ColumnMapping>().is_err());
+    }
+}
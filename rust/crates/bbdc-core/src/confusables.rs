@@ -0,0 +1,198 @@
+//! 易混词模块
+//!
+//! 按拼写（编辑距离，含相邻字母换位）和发音（辅音骨架）找出词书中容易混淆的单词对，
+//! 如 affect/effect、adapt/adopt、quite/quiet
+
+use crate::{text, Word};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// 参与比较的最短单词长度，更短的单词之间编辑距离普遍很小
+const MIN_LENGTH: usize = 4;
+
+/// 一对易混词
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ConfusablePair {
+    pub first: String,
+    pub second: String,
+    /// 编辑距离（相邻字母换位计为 1）
+    pub edit_distance: usize,
+    /// 拼写相近
+    pub similar_spelling: bool,
+    /// 发音相近
+    pub similar_sound: bool,
+}
+
+impl ConfusablePair {
+    /// 混淆原因，如 `拼写相近、发音相近`
+    pub fn reason(&self) -> String {
+        let mut reasons = Vec::new();
+        if self.similar_spelling {
+            reasons.push("拼写相近");
+        }
+        if self.similar_sound {
+            reasons.push("发音相近");
+        }
+        reasons.join("、")
+    }
+}
+
+/// 找出易混词对，按单词在词书中出现的顺序排列
+///
+/// 短语、短于 4 个字母的单词以及只差词尾的变形（play/plays）不参与比较
+pub fn confusable_pairs(words: &[Word]) -> Vec<ConfusablePair> {
+    let mut seen = HashSet::new();
+    let candidates: Vec<(String, String)> = words
+        .iter()
+        .map(|w| text::normalize_key(&w.word))
+        .filter(|w| w.chars().count() >= MIN_LENGTH && !text::is_phrase(w) && seen.insert(w.clone()))
+        .map(|w| {
+            let key = sound_key(&w);
+            (w, key)
+        })
+        .collect();
+
+    let mut pairs = Vec::new();
+    for (i, (a, a_key)) in candidates.iter().enumerate() {
+        for (b, b_key) in &candidates[i + 1..] {
+            let (a_len, b_len) = (a.chars().count(), b.chars().count());
+            if a_len.abs_diff(b_len) > 2 || a.starts_with(b.as_str()) || b.starts_with(a.as_str()) {
+                continue;
+            }
+
+            let distance = edit_distance(a, b);
+            let max_distance = if a_len.min(b_len) >= 7 { 2 } else { 1 };
+            let similar_spelling = distance <= max_distance;
+            let similar_sound = a_key == b_key && a_key.len() >= 3 && distance <= a_len.max(b_len) / 2;
+
+            if similar_spelling || similar_sound {
+                pairs.push(ConfusablePair {
+                    first: a.clone(),
+                    second: b.clone(),
+                    edit_distance: distance,
+                    similar_spelling,
+                    similar_sound,
+                });
+            }
+        }
+    }
+
+    pairs
+}
+
+/// 易混词的 Markdown 段落，没有易混词时返回空字符串
+///
+/// `render` 决定单词的显示方式，如原样输出或渲染为词典链接
+pub fn markdown_section<F>(pairs: &[ConfusablePair], render: F) -> String
+where
+    F: Fn(&str) -> String,
+{
+    if pairs.is_empty() {
+        return String::new();
+    }
+
+    let mut content = String::from("\n## 易混词\n\n");
+    for pair in pairs {
+        content.push_str(&format!("- {} / {}（{}）\n", render(&pair.first), render(&pair.second), pair.reason()));
+    }
+    content
+}
+
+/// 编辑距离（Optimal String Alignment：插入、删除、替换和相邻换位各计 1）
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+/// 发音键：开头的元音记为 `V`，辅音按 Soundex 分组，忽略其余元音并合并相邻的同组辅音
+fn sound_key(word: &str) -> String {
+    fn group(c: char) -> Option<char> {
+        match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let mut key = String::new();
+    let mut last = None;
+    for (i, c) in word.chars().filter(|c| c.is_ascii_alphabetic()).enumerate() {
+        let code = group(c);
+        if i == 0 && code.is_none() && "aeiouy".contains(c) {
+            key.push('V');
+        }
+        if let Some(code) = code {
+            if last != Some(code) {
+                key.push(code);
+            }
+        }
+        // h、w 不隔断同组辅音，元音会隔断
+        if !matches!(c, 'h' | 'w') {
+            last = code;
+        }
+    }
+    key
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[&str]) -> Vec<Word> {
+        list.iter()
+            .map(|w| Word {
+                number: String::new(),
+                word: w.to_string(),
+                meaning: String::new(),
+                phonetic: None,
+                example: None,
+                pos: Vec::new(),
+                line_number: None,
+                page: None,
+                frequency: None,
+                difficulty: None,
+                level: None,
+                chapter: None,
+                source: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_confusable_pairs() {
+        let pairs = confusable_pairs(&words(&["affect", "adapt", "effect", "play", "plays", "adopt", "quite", "quiet", "accept", "except", "banana"]));
+        let found: Vec<(&str, &str)> = pairs.iter().map(|p| (p.first.as_str(), p.second.as_str())).collect();
+        assert_eq!(found, [("affect", "effect"), ("adapt", "adopt"), ("quite", "quiet"), ("accept", "except")]);
+
+        let accept = &pairs[3];
+        assert!(accept.similar_sound && !accept.similar_spelling);
+        assert_eq!(pairs[2].edit_distance, 1);
+        assert!(markdown_section(&pairs, str::to_string).contains("- affect / effect（拼写相近、发音相近）"));
+    }
+}
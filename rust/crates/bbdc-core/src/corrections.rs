@@ -0,0 +1,172 @@
+//! 更正记录模块
+//!
+//! 以 JSONL 文件保存每一次更正建议及用户是否采纳，作为项目的更正数据库，
+//! 供导出微调/评测数据集等后续处理使用
+
+use crate::{EnvLoader, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// 更正置信度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Confidence {
+    /// 未能给出更正（LLM 未启用或响应无法解析）
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for Confidence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Confidence::None => "none",
+            Confidence::Low => "low",
+            Confidence::Medium => "medium",
+            Confidence::High => "high",
+        })
+    }
+}
+
+/// 一条更正记录
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrectionRecord {
+    pub original: String,
+    pub corrected: String,
+    #[serde(default)]
+    pub meaning: String,
+    pub confidence: Confidence,
+    /// 用户是否采纳，未确认时为 None
+    #[serde(default)]
+    pub accepted: Option<bool>,
+    /// 给出建议的来源（模型名称等）
+    #[serde(default)]
+    pub source: String,
+    #[serde(default)]
+    pub reason: String,
+    /// 记录时间（RFC 3339）
+    pub recorded_at: String,
+}
+
+impl CorrectionRecord {
+    /// 以当前时间创建记录
+    pub fn new(original: &str, corrected: &str, confidence: Confidence) -> Self {
+        Self {
+            original: original.to_string(),
+            corrected: corrected.to_string(),
+            meaning: String::new(),
+            confidence,
+            accepted: None,
+            source: String::new(),
+            reason: String::new(),
+            recorded_at: chrono::Local::now().to_rfc3339(),
+        }
+    }
+}
+
+/// 更正记录文件
+pub struct CorrectionLog {
+    path: PathBuf,
+}
+
+impl CorrectionLog {
+    /// 默认记录文件：`BBDC_CORRECTIONS_FILE`，否则为 `~/.bbdc_word_tool/corrections.jsonl`
+    pub fn open_default() -> Result<Self> {
+        let path = EnvLoader::get_optional("BBDC_CORRECTIONS_FILE")
+            .map(PathBuf::from)
+            .or_else(|| EnvLoader::data_dir().map(|dir| dir.join("corrections.jsonl")))
+            .ok_or_else(|| Error::Other("无法确定更正记录文件位置，请设置 BBDC_CORRECTIONS_FILE".to_string()))?;
+        Ok(Self::open(path))
+    }
+
+    /// 使用指定的记录文件
+    pub fn open<P: AsRef<Path>>(path: P) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+
+    /// 记录文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 追加一条记录
+    pub fn append(&self, record: &CorrectionRecord) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    /// 读取全部记录，文件不存在时返回空列表；无法解析的行会被跳过
+    pub fn records(&self) -> Result<Vec<CorrectionRecord>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut records = Vec::new();
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(record) => records.push(record),
+                Err(e) => log::warn!("⚠️  跳过更正记录第 {} 行: {}", i + 1, e),
+            }
+        }
+        Ok(records)
+    }
+
+    /// 每个（原词, 更正）组合的最新决定，按首次出现的顺序排列
+    pub fn decisions(&self) -> Result<Vec<CorrectionRecord>> {
+        let mut latest: Vec<CorrectionRecord> = Vec::new();
+        for record in self.records()? {
+            match latest
+                .iter_mut()
+                .find(|r| r.original == record.original && r.corrected == record.corrected)
+            {
+                // 未确认的记录不覆盖已有的决定
+                Some(existing) if record.accepted.is_some() || existing.accepted.is_none() => *existing = record,
+                Some(_) => {}
+                None => latest.push(record),
+            }
+        }
+        Ok(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_decision_wins() {
+        let path = std::env::temp_dir().join(format!("bbdc_corrections_test_{}.jsonl", std::process::id()));
+        let log = CorrectionLog::open(&path);
+
+        let mut record = CorrectionRecord::new("acess", "access", Confidence::High);
+        log.append(&record).unwrap();
+        record.accepted = Some(true);
+        log.append(&record).unwrap();
+        record.accepted = None;
+        log.append(&record).unwrap();
+        log.append(&CorrectionRecord::new("abundnat", "abundant", Confidence::Medium)).unwrap();
+
+        assert_eq!(log.records().unwrap().len(), 4);
+        let decisions = log.decisions().unwrap();
+        assert_eq!(decisions.len(), 2);
+        assert_eq!(decisions[0].accepted, Some(true));
+        assert_eq!(decisions[1].accepted, None);
+
+        fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,145 @@
+//! DOCX 读取模块
+//!
+//! 从 Word 文档的 `word/document.xml` 中读取表格行和表格外的段落
+
+use crate::{Error, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::io::{Cursor, Read};
+
+/// 文档内容
+#[derive(Debug, Default)]
+pub struct DocxContent {
+    /// 所有表格行（含嵌套表格），每行为去除首尾空白的单元格文本
+    pub table_rows: Vec<Vec<String>>,
+    /// 表格外的段落；带自动编号的段落以 `- ` 开头，便于按列表处理
+    pub paragraphs: Vec<String>,
+}
+
+/// 正在读取的表格
+#[derive(Default)]
+struct TableState {
+    row: Vec<String>,
+    cell: String,
+    in_cell: bool,
+}
+
+/// 读取 DOCX 文件内容
+pub fn read_docx(data: &[u8]) -> Result<DocxContent> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| Error::Parse(format!("无法打开 DOCX: {}", e)))?;
+    let mut xml = String::new();
+    archive
+        .by_name("word/document.xml")
+        .map_err(|e| Error::Parse(format!("DOCX 中缺少 word/document.xml: {}", e)))?
+        .read_to_string(&mut xml)?;
+    parse_document(&xml)
+}
+
+/// 解析 `word/document.xml`
+fn parse_document(xml: &str) -> Result<DocxContent> {
+    let mut reader = Reader::from_str(xml);
+    let mut content = DocxContent::default();
+    let mut tables: Vec<TableState> = Vec::new();
+    let mut paragraph = String::new();
+    let mut numbered = false;
+    let mut in_text = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| Error::Parse(format!("DOCX 解析错误（位置 {}）: {}", reader.buffer_position(), e)))?;
+        match event {
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"tbl" => tables.push(TableState::default()),
+                b"tc" => {
+                    if let Some(table) = tables.last_mut() {
+                        table.cell.clear();
+                        table.in_cell = true;
+                    }
+                }
+                b"p" => {
+                    paragraph.clear();
+                    numbered = false;
+                }
+                b"t" => in_text = true,
+                _ => {}
+            },
+            Event::Empty(e) => match e.local_name().as_ref() {
+                b"numPr" => numbered = true,
+                b"tab" => paragraph.push('\t'),
+                b"br" => paragraph.push(' '),
+                _ => {}
+            },
+            Event::Text(e) if in_text => {
+                let text = e
+                    .unescape()
+                    .map_err(|e| Error::Parse(format!("DOCX 文本解析错误: {}", e)))?;
+                paragraph.push_str(&text);
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"numPr" => numbered = true,
+                b"p" => {
+                    let text = paragraph.trim();
+                    match tables.last_mut() {
+                        Some(table) if table.in_cell => {
+                            if !table.cell.is_empty() && !text.is_empty() {
+                                table.cell.push(' ');
+                            }
+                            table.cell.push_str(text);
+                        }
+                        _ if !text.is_empty() => {
+                            let prefix = if numbered { "- " } else { "" };
+                            content.paragraphs.push(format!("{}{}", prefix, text));
+                        }
+                        _ => {}
+                    }
+                    paragraph.clear();
+                }
+                b"tc" => {
+                    if let Some(table) = tables.last_mut() {
+                        let cell = std::mem::take(&mut table.cell);
+                        table.row.push(cell.trim().to_string());
+                        table.in_cell = false;
+                    }
+                }
+                b"tr" => {
+                    if let Some(table) = tables.last_mut() {
+                        content.table_rows.push(std::mem::take(&mut table.row));
+                    }
+                }
+                b"tbl" => {
+                    tables.pop();
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tables_and_paragraphs() {
+        let xml = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:r><w:t>Unit 1</w:t></w:r></w:p>
+<w:tbl>
+<w:tr><w:tc><w:p><w:r><w:t>NO.</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>单词</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>释义</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>1</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>hel</w:t></w:r><w:r><w:t>lo</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>你好 &amp; 问候</w:t></w:r></w:p></w:tc></w:tr>
+</w:tbl>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>abandon</w:t></w:r><w:r><w:tab/><w:t>放弃</w:t></w:r></w:p>
+</w:body></w:document>"#;
+
+        let content = parse_document(xml).unwrap();
+        assert_eq!(content.table_rows.len(), 2);
+        assert_eq!(content.table_rows[1], ["1", "hello", "你好 & 问候"]);
+        assert_eq!(content.paragraphs, ["Unit 1", "- abandon\t放弃"]);
+    }
+}
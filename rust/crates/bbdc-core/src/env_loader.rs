@@ -51,6 +51,13 @@ impl EnvLoader {
         env::var(key).is_ok()
     }
     
+    /// 本工具的数据目录 `~/.bbdc_word_tool`，无法确定用户目录时返回 None
+    pub fn data_dir() -> Option<PathBuf> {
+        env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map(|home| PathBuf::from(home).join(".bbdc_word_tool"))
+    }
+    
     /// 获取搜索路径列表
     fn get_search_paths() -> Vec<PathBuf> {
         let mut paths = Vec::new();
@@ -0,0 +1,32 @@
+//! EPUB 读取模块
+//!
+//! 读取电子书中的 XHTML 章节，按文件名顺序返回，单词表通常以 HTML 表格的形式出现在章节中
+
+use crate::{Error, Result};
+use std::io::{Cursor, Read};
+
+/// 读取 EPUB 中所有 XHTML/HTML 章节的内容
+pub fn read_chapters(data: &[u8]) -> Result<Vec<String>> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| Error::Parse(format!("无法打开 EPUB: {}", e)))?;
+    let mut names: Vec<String> = archive
+        .file_names()
+        .filter(|name| {
+            let name = name.to_lowercase();
+            name.ends_with(".xhtml") || name.ends_with(".html") || name.ends_with(".htm")
+        })
+        .map(str::to_string)
+        .collect();
+    names.sort();
+
+    let mut chapters = Vec::with_capacity(names.len());
+    for name in names {
+        let mut chapter = String::new();
+        archive
+            .by_name(&name)
+            .map_err(|e| Error::Parse(format!("读取 EPUB 章节 {} 失败: {}", name, e)))?
+            .read_to_string(&mut chapter)?;
+        chapters.push(chapter);
+    }
+    Ok(chapters)
+}
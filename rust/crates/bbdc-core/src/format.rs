@@ -0,0 +1,179 @@
+//! 输入格式识别模块
+//!
+//! 先按扩展名、再按文件开头的内容判断输入格式，决定使用哪种方式提取单词。
+//! 识别有误时可以用命令行的 `--format` 指定
+
+use crate::{kindle, Result};
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// 内容识别时读取的文件开头字节数
+const SNIFF_LEN: usize = 8192;
+
+/// 输入格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum InputFormat {
+    /// Markdown（HTML 表格、管道表格、列表）
+    #[cfg_attr(feature = "clap", value(alias = "md"))]
+    Markdown,
+    /// HTML 网页中的表格
+    #[cfg_attr(feature = "clap", value(alias = "htm"))]
+    Html,
+    /// CSV/TSV 表格
+    #[cfg_attr(feature = "clap", value(alias = "tsv"))]
+    Csv,
+    /// 纯文本，按 Markdown 规则提取（编号行兜底）
+    #[cfg_attr(feature = "clap", value(alias = "txt"))]
+    Text,
+    /// Kindle 标注（`My Clippings.txt`）
+    Clippings,
+    /// SRT/VTT 字幕
+    #[cfg_attr(feature = "clap", value(aliases = ["srt", "vtt"]))]
+    Subtitle,
+    /// PDF，需要先由 Mineru 转换为 Markdown
+    Pdf,
+    /// Word 文档
+    Docx,
+    /// Excel/ODS 工作簿
+    #[cfg_attr(feature = "clap", value(aliases = ["xls", "ods"]))]
+    Xlsx,
+    /// Anki 牌组
+    Apkg,
+    /// EPUB 电子书
+    Epub,
+}
+
+impl InputFormat {
+    /// 按扩展名判断格式，`.txt` 和未知扩展名返回 None（需要检查内容）
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        Some(match extension.as_str() {
+            "md" | "markdown" => InputFormat::Markdown,
+            "html" | "htm" | "xhtml" => InputFormat::Html,
+            "csv" | "tsv" => InputFormat::Csv,
+            "srt" | "vtt" => InputFormat::Subtitle,
+            "pdf" => InputFormat::Pdf,
+            "docx" => InputFormat::Docx,
+            "xlsx" | "xlsm" | "xls" | "ods" => InputFormat::Xlsx,
+            "apkg" => InputFormat::Apkg,
+            "epub" => InputFormat::Epub,
+            _ => return None,
+        })
+    }
+
+    /// 按文件开头的内容判断格式
+    pub fn sniff(head: &[u8]) -> Self {
+        let contains = |needle: &[u8]| head.windows(needle.len()).any(|w| w == needle);
+        if head.starts_with(b"%PDF") {
+            return InputFormat::Pdf;
+        }
+        if head.starts_with(b"PK\x03\x04") {
+            return if contains(b"application/epub+zip") {
+                InputFormat::Epub
+            } else if contains(b"collection.anki") {
+                InputFormat::Apkg
+            } else if contains(b"xl/") {
+                InputFormat::Xlsx
+            } else {
+                InputFormat::Docx
+            };
+        }
+        // 旧版 Excel（OLE 复合文档）
+        if head.starts_with(b"\xD0\xCF\x11\xE0") {
+            return InputFormat::Xlsx;
+        }
+
+        let text = String::from_utf8_lossy(head);
+        let lower = text.trim_start_matches('\u{feff}').trim_start().to_lowercase();
+        if lower.starts_with("<!doctype html") || lower.starts_with("<html") {
+            InputFormat::Html
+        } else if lower.starts_with("webvtt") || text.lines().any(|line| line.contains("-->") && line.contains(':')) {
+            InputFormat::Subtitle
+        } else if kindle::is_clippings(&text) {
+            InputFormat::Clippings
+        } else if lower.contains("<table") || text.lines().any(|line| line.trim_start().starts_with(['|', '#'])) {
+            InputFormat::Markdown
+        } else if is_csv(&text) {
+            InputFormat::Csv
+        } else {
+            InputFormat::Text
+        }
+    }
+
+    /// 识别文件格式：扩展名已知时按扩展名，否则读取文件开头检查内容
+    ///
+    /// `.txt` 为 Kindle 标注时识别为 [`Clippings`](InputFormat::Clippings)，其余为纯文本
+    pub fn detect<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if let Some(format) = Self::from_extension(path) {
+            return Ok(format);
+        }
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        File::open(path)?.take(SNIFF_LEN as u64).read_to_end(&mut head)?;
+        let is_txt = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("txt"));
+        Ok(match Self::sniff(&head) {
+            InputFormat::Clippings => InputFormat::Clippings,
+            _ if is_txt => InputFormat::Text,
+            format => format,
+        })
+    }
+
+    /// 是否为文本格式（可以从标准输入读取）
+    pub fn is_text(self) -> bool {
+        matches!(
+            self,
+            InputFormat::Markdown | InputFormat::Html | InputFormat::Csv | InputFormat::Text | InputFormat::Clippings | InputFormat::Subtitle
+        )
+    }
+}
+
+impl fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            InputFormat::Markdown => "markdown",
+            InputFormat::Html => "html",
+            InputFormat::Csv => "csv",
+            InputFormat::Text => "text",
+            InputFormat::Clippings => "clippings",
+            InputFormat::Subtitle => "subtitle",
+            InputFormat::Pdf => "pdf",
+            InputFormat::Docx => "docx",
+            InputFormat::Xlsx => "xlsx",
+            InputFormat::Apkg => "apkg",
+            InputFormat::Epub => "epub",
+        })
+    }
+}
+
+/// 前几行是否以相同数量的逗号或制表符分隔（至少两行、两列）
+fn is_csv(text: &str) -> bool {
+    let lines: Vec<&str> = text.lines().filter(|l| !l.trim().is_empty()).take(5).collect();
+    lines.len() >= 2
+        && [',', '\t'].into_iter().any(|delimiter| {
+            let count = lines[0].matches(delimiter).count();
+            count > 0 && lines.iter().all(|line| line.matches(delimiter).count() == count)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(InputFormat::sniff(b"%PDF-1.7\n"), InputFormat::Pdf);
+        assert_eq!(InputFormat::sniff(b"PK\x03\x04....mimetypeapplication/epub+zip"), InputFormat::Epub);
+        assert_eq!(InputFormat::sniff(b"PK\x03\x04....[Content_Types].xml....word/document.xml"), InputFormat::Docx);
+        assert_eq!(InputFormat::sniff(b"<!DOCTYPE html><html><table>"), InputFormat::Html);
+        assert_eq!(InputFormat::sniff(b"1\n00:00:01,000 --> 00:00:02,000\nHello\n"), InputFormat::Subtitle);
+        assert_eq!(InputFormat::sniff("| NO. | 单词 | 词义 |\n|---|---|---|\n".as_bytes()), InputFormat::Markdown);
+        assert_eq!(InputFormat::sniff("1,abandon,放弃\n2,ability,能力\n".as_bytes()), InputFormat::Csv);
+        assert_eq!(InputFormat::sniff(b"Just some prose, nothing more.\nAnother line.\n"), InputFormat::Text);
+
+        assert_eq!(InputFormat::from_extension(Path::new("unit1.TSV")), Some(InputFormat::Csv));
+        assert_eq!(InputFormat::from_extension(Path::new("notes.txt")), None);
+    }
+}
@@ -0,0 +1,113 @@
+//! 可读格式模块
+//!
+//! 把耗时、文件大小、数量和费用格式化为适合阅读的文本，供命令行输出和报告共用。
+//! 语言由 `BBDC_LOCALE` 或系统的 `LC_ALL`/`LC_MESSAGES`/`LANG` 决定
+
+use crate::EnvLoader;
+use std::time::Duration;
+
+/// 输出语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 中文（默认）
+    #[default]
+    Zh,
+    /// 英文
+    En,
+}
+
+impl Locale {
+    /// 从环境变量识别语言；未设置、为 `C`/`POSIX` 或以 `zh` 开头时为中文
+    pub fn current() -> Self {
+        let value = ["BBDC_LOCALE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .find_map(|key| EnvLoader::get_optional(key).filter(|v| !v.is_empty()));
+        match value {
+            Some(v) if !v.to_lowercase().starts_with("zh") && !matches!(v.as_str(), "C" | "POSIX") && !v.starts_with("C.") => {
+                Locale::En
+            }
+            _ => Locale::Zh,
+        }
+    }
+}
+
+/// 格式化耗时，如 `850 毫秒`、`12.3 秒`、`1 分 23 秒`、`2 小时 5 分`
+pub fn format_duration(duration: Duration, locale: Locale) -> String {
+    let secs = duration.as_secs();
+    match locale {
+        Locale::Zh => match secs {
+            0 => format!("{} 毫秒", duration.as_millis()),
+            1..=59 => format!("{:.1} 秒", duration.as_secs_f64()),
+            60..=3599 => format!("{} 分 {} 秒", secs / 60, secs % 60),
+            _ => format!("{} 小时 {} 分", secs / 3600, secs % 3600 / 60),
+        },
+        Locale::En => match secs {
+            0 => format!("{} ms", duration.as_millis()),
+            1..=59 => format!("{:.1} s", duration.as_secs_f64()),
+            60..=3599 => format!("{}m {}s", secs / 60, secs % 60),
+            _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+        },
+    }
+}
+
+/// 格式化文件大小（1024 进制），如 `512 字节`、`12.3 KB`、`1.5 MB`
+pub fn format_size(bytes: u64, locale: Locale) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return match locale {
+            Locale::Zh => format!("{} 字节", bytes),
+            Locale::En => format!("{} B", bytes),
+        };
+    }
+    let mut value = bytes as f64;
+    let mut unit = "";
+    for u in UNITS {
+        value /= 1024.0;
+        unit = u;
+        if value < 1024.0 {
+            break;
+        }
+    }
+    format!("{:.1} {}", value, unit)
+}
+
+/// 带千位分隔符的整数，如 `12,345`
+pub fn format_number(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// 格式化人民币费用，不足 1 元时保留 4 位小数，如 `¥0.0123`、`¥12.35`
+pub fn format_cost(yuan: f64, locale: Locale) -> String {
+    let amount = if yuan.abs() < 1.0 { format!("{:.4}", yuan) } else { format!("{:.2}", yuan) };
+    match locale {
+        Locale::Zh => format!("¥{}", amount),
+        Locale::En => format!("CNY {}", amount),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_formats() {
+        assert_eq!(format_duration(Duration::from_millis(850), Locale::Zh), "850 毫秒");
+        assert_eq!(format_duration(Duration::from_millis(12_345), Locale::Zh), "12.3 秒");
+        assert_eq!(format_duration(Duration::from_secs(83), Locale::Zh), "1 分 23 秒");
+        assert_eq!(format_duration(Duration::from_secs(83), Locale::En), "1m 23s");
+        assert_eq!(format_duration(Duration::from_secs(7500), Locale::En), "2h 5m");
+
+        assert_eq!(format_size(512, Locale::Zh), "512 字节");
+        assert_eq!(format_size(1536 * 1024, Locale::En), "1.5 MB");
+        assert_eq!(format_number(1_234_567), "1,234,567");
+        assert_eq!(format_cost(0.01234, Locale::Zh), "¥0.0123");
+    }
+}
@@ -0,0 +1,95 @@
+//! Kindle 标注模块
+//!
+//! 解析 Kindle 的 `My Clippings.txt`，取出标注（高亮）的文本及其所在的书和位置，
+//! 忽略笔记和书签。同时支持英文和中文界面的 Kindle
+
+/// 条目分隔行
+const SEPARATOR: &str = "==========";
+
+/// 一条标注
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clipping {
+    /// 书名（含作者）
+    pub book: String,
+    /// 页码和位置，如 `page 12 · Location 180-181`
+    pub location: String,
+    pub text: String,
+}
+
+/// 内容是否像 Kindle 标注文件（含有条目分隔行）
+pub fn is_clippings(content: &str) -> bool {
+    content.lines().any(|line| line.trim() == SEPARATOR)
+}
+
+/// 解析标注文件，只返回标注条目
+pub fn parse_clippings(content: &str) -> Vec<Clipping> {
+    content
+        .split(SEPARATOR)
+        .filter_map(|entry| {
+            let mut lines = entry.lines().map(|l| l.trim_start_matches('\u{feff}').trim()).skip_while(|l| l.is_empty());
+            let book = lines.next()?.to_string();
+            let meta = lines.next()?;
+            if !meta.contains("Highlight") && !meta.contains("标注") {
+                return None;
+            }
+
+            let text = lines.filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+            if text.is_empty() {
+                return None;
+            }
+
+            Some(Clipping { book, location: location(meta), text })
+        })
+        .collect()
+}
+
+/// 从 `- Your Highlight on page 12 | Location 180-181 | Added on ...` 中取出位置
+fn location(meta: &str) -> String {
+    meta.trim_start_matches('-')
+        .split('|')
+        .map(str::trim)
+        .filter(|part| !part.starts_with("Added on") && !part.starts_with("添加于"))
+        .map(|part| {
+            let part = ["Your Highlight on ", "Your Highlight at ", "您在"]
+                .iter()
+                .find_map(|prefix| part.strip_prefix(prefix))
+                .unwrap_or(part);
+            part.strip_suffix("的标注").unwrap_or(part).trim()
+        })
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_clippings() {
+        let content = "\u{feff}The Great Gatsby (F. Scott Fitzgerald)
+- Your Highlight on page 12 | Location 180-181 | Added on Monday, 1 May 2023 10:00:00
+
+ephemeral,
+==========
+The Great Gatsby (F. Scott Fitzgerald)
+- Your Bookmark on page 13 | Location 190 | Added on Monday, 1 May 2023 10:05:00
+
+
+==========
+活着 (余华)
+- 您在位置 #120-121的标注 | 添加于 2023年5月1日星期一 上午10:00:00
+
+in the long run
+==========
+";
+
+        let clippings = parse_clippings(content);
+        assert!(is_clippings(content));
+        assert_eq!(clippings.len(), 2);
+        assert_eq!(clippings[0].book, "The Great Gatsby (F. Scott Fitzgerald)");
+        assert_eq!(clippings[0].location, "page 12 · Location 180-181");
+        assert_eq!(clippings[0].text, "ephemeral,");
+        assert_eq!(clippings[1].location, "位置 #120-121");
+    }
+}
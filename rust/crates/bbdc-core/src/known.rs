@@ -0,0 +1,135 @@
+//! 已掌握单词模块
+//!
+//! 提供一份已经掌握的单词，提取时只输出新词，每次从新材料中增量挖掘生词，
+//! 而不是重复导出全部单词。已掌握的单词按原形匹配：掌握了 `abandon` 时
+//! `abandoned` 也不再输出
+
+use crate::{text, ExtractResult, Lemmatizer, Result, Word};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// 已掌握的单词和短语
+#[derive(Debug, Clone)]
+pub struct KnownWords {
+    /// 单词（小写）
+    words: HashSet<String>,
+    /// 单词的原形
+    lemmas: HashSet<String>,
+    /// 以已掌握的单词为已知原形
+    lemmatizer: Lemmatizer,
+    /// 是否按原形匹配
+    by_lemma: bool,
+}
+
+impl KnownWords {
+    /// 解析单词列表
+    ///
+    /// 每行一个单词或短语，`#` 开头的行为注释；行首的序号（`12.`）和制表符之后的释义会被忽略，
+    /// 因此可以直接使用之前导出的 with_meaning 文本
+    pub fn parse(content: &str) -> Self {
+        let words: Vec<String> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let line = line.split('\t').next().unwrap_or_default();
+                let line = match line.split_once(". ") {
+                    Some((number, rest)) if number.chars().all(|c| c.is_ascii_digit()) => rest,
+                    _ => line,
+                };
+                text::normalize_key(line)
+            })
+            .filter(|w| w.chars().any(text::is_letter))
+            .collect();
+        Self::from_words(words)
+    }
+
+    /// 读取已掌握单词文件：`.json` 为保存的提取结果或单词数组（单词和短语都算作已掌握），
+    /// `.jsonl` 为每行一个单词，其他为 [`parse`](Self::parse) 的单词列表
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        let known = if extension == "json" {
+            let result = ExtractResult::load(path)?;
+            let words = result.words.iter().map(|w| &w.word).chain(result.phrases.iter().map(|p| &p.phrase));
+            Self::from_words(words.map(|w| text::normalize_key(w)).collect())
+        } else if extension == "jsonl" {
+            let mut words = Vec::new();
+            for line in fs::read_to_string(path)?.lines().filter(|l| !l.trim().is_empty()) {
+                words.push(text::normalize_key(&serde_json::from_str::<Word>(line)?.word));
+            }
+            Self::from_words(words)
+        } else {
+            Self::parse(&fs::read_to_string(path)?)
+        };
+        log::info!("已掌握 {} 个单词", known.words.len());
+        Ok(known)
+    }
+
+    fn from_words(words: Vec<String>) -> Self {
+        let lemmatizer = Lemmatizer::new().with_wordlist(&words.join("\n"));
+        let lemmas = words.iter().map(|w| lemmatizer.lemma(w, &HashSet::new())).collect();
+        Self { words: words.into_iter().collect(), lemmas, lemmatizer, by_lemma: true }
+    }
+
+    /// 只按原词匹配，不按原形匹配，用于和已有的输出文件去重
+    pub fn exact(mut self) -> Self {
+        self.by_lemma = false;
+        self
+    }
+
+    /// 单词或短语是否已掌握
+    pub fn contains(&self, word: &str) -> bool {
+        let key = text::normalize_key(word);
+        if self.words.contains(&key) {
+            return true;
+        }
+        if !self.by_lemma {
+            return false;
+        }
+        let lemma = self.lemmatizer.lemma(&key, &HashSet::new());
+        self.lemmas.contains(&key) || self.words.contains(&lemma) || self.lemmas.contains(&lemma)
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+impl ExtractResult {
+    /// 去掉已掌握的单词和短语，返回去掉的数量
+    pub fn remove_known(&mut self, known: &KnownWords) -> usize {
+        let before = self.words.len() + self.phrases.len();
+        self.words.retain(|w| !known.contains(&w.word));
+        self.phrases.retain(|p| !known.contains(&p.phrase));
+        self.total_words = self.words.len();
+        self.total_phrases = self.phrases.len();
+        before - self.words.len() - self.phrases.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_known() {
+        let known = KnownWords::parse("# 上次导出\n1. abandon\t放弃\nAbility\ngive up\n");
+        assert!(known.contains("abandoned"));
+        assert!(known.contains("ABILITY"));
+        assert!(!known.contains("abroad"));
+
+        let rows = [["1", "abandoned", "被抛弃的"], ["2", "ability", "能力"], ["3", "abroad", "在国外"], ["4", "give up", "放弃"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let mut result = crate::WordExtractor::new(true, true).extract_from_rows(rows);
+        assert_eq!(result.remove_known(&known), 3);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["abroad"]);
+        assert_eq!(result.total_phrases, 0);
+    }
+}
@@ -0,0 +1,311 @@
+//! 词形还原模块
+//!
+//! 把 `running`、`ran`、`runs` 等屈折形式还原为 `run`。不规则形式查内置表或词表，
+//! 规则形式（复数、第三人称、-ed、-ing）按后缀规则还原；提供了已知原形
+//! （词表或词书中的其他单词）时优先选择已知的候选，否则只做没有歧义的还原
+
+use crate::{text, ExtractResult, Result, Word};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// 常见的不规则形式
+const IRREGULAR: [(&str, &str); 96] = [
+    ("am", "be"), ("is", "be"), ("are", "be"), ("was", "be"), ("were", "be"), ("been", "be"),
+    ("has", "have"), ("had", "have"), ("does", "do"), ("did", "do"), ("done", "do"),
+    ("went", "go"), ("gone", "go"), ("goes", "go"), ("ran", "run"), ("began", "begin"), ("begun", "begin"),
+    ("came", "come"), ("became", "become"), ("saw", "see"), ("seen", "see"), ("took", "take"), ("taken", "take"),
+    ("gave", "give"), ("given", "give"), ("got", "get"), ("gotten", "get"), ("made", "make"), ("knew", "know"),
+    ("known", "know"), ("thought", "think"), ("brought", "bring"), ("bought", "buy"), ("caught", "catch"),
+    ("taught", "teach"), ("fought", "fight"), ("sought", "seek"), ("found", "find"), ("told", "tell"),
+    ("sold", "sell"), ("held", "hold"), ("stood", "stand"), ("understood", "understand"), ("left", "leave"),
+    ("felt", "feel"), ("kept", "keep"), ("slept", "sleep"), ("meant", "mean"), ("met", "meet"), ("led", "lead"),
+    ("fed", "feed"), ("fell", "fall"), ("fallen", "fall"), ("wrote", "write"), ("written", "write"),
+    ("spoke", "speak"), ("spoken", "speak"), ("broke", "break"), ("broken", "break"), ("chose", "choose"),
+    ("chosen", "choose"), ("drove", "drive"), ("driven", "drive"), ("rode", "ride"), ("ridden", "ride"),
+    ("rose", "rise"), ("risen", "rise"), ("ate", "eat"), ("eaten", "eat"), ("drank", "drink"), ("drunk", "drink"),
+    ("sang", "sing"), ("sung", "sing"), ("swam", "swim"), ("swum", "swim"), ("wore", "wear"), ("worn", "wear"),
+    ("grew", "grow"), ("grown", "grow"), ("threw", "throw"), ("thrown", "throw"), ("flew", "fly"), ("flown", "fly"),
+    ("forgot", "forget"), ("forgotten", "forget"), ("children", "child"), ("men", "man"), ("women", "woman"),
+    ("feet", "foot"), ("teeth", "tooth"), ("mice", "mouse"), ("geese", "goose"), ("better", "good"),
+    ("best", "good"), ("worse", "bad"), ("worst", "bad"),
+];
+
+/// 看起来像屈折形式、实际是原形的常见词
+const NOT_INFLECTED: [&str; 28] = [
+    "something", "nothing", "anything", "everything", "morning", "evening", "ceiling", "wedding", "during",
+    "pudding", "darling", "sibling", "hundred", "kindred", "sacred", "naked", "wicked", "rugged", "bus",
+    "gas", "lens", "news", "series", "species", "physics", "mathematics", "always", "perhaps",
+];
+
+/// 常见的派生后缀，核对失败时去掉后重新核对（`happily` → `happy`、`kindness` → `kind`）
+const DERIVATIONAL_SUFFIXES: [&str; 7] = ["ly", "ness", "ment", "ful", "less", "est", "er"];
+
+/// 词形还原器
+#[derive(Debug, Clone)]
+pub struct Lemmatizer {
+    /// 屈折形式 → 原形
+    forms: HashMap<String, String>,
+    /// 已知原形
+    lemmas: HashSet<String>,
+}
+
+impl Default for Lemmatizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lemmatizer {
+    /// 使用内置的不规则形式表
+    pub fn new() -> Self {
+        let forms = IRREGULAR.iter().map(|(form, lemma)| (form.to_string(), lemma.to_string())).collect();
+        Self { forms, lemmas: HashSet::new() }
+    }
+
+    /// 读取词表并合并到内置表
+    ///
+    /// 每行为 `run -> ran,running,runs`（原形及其屈折形式）或单独的原形，`#` 开头的行为注释
+    pub fn with_wordlist(mut self, content: &str) -> Self {
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (lemma, forms) = line.split_once("->").unwrap_or((line, ""));
+            let lemma = text::normalize_key(lemma);
+            for form in forms.split([',', ' ']).map(text::normalize_key).filter(|f| !f.is_empty()) {
+                self.forms.insert(form, lemma.clone());
+            }
+            self.lemmas.insert(lemma);
+        }
+        self
+    }
+
+    /// 读取词表文件，见 [`with_wordlist`](Self::with_wordlist)
+    pub fn load_wordlist<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let lemmatizer = self.with_wordlist(&content);
+        log::info!("词形还原词表包含 {} 个屈折形式", lemmatizer.forms.len());
+        Ok(lemmatizer)
+    }
+
+    /// 还原单词（小写）
+    ///
+    /// `known` 为额外的已知原形（如词书中的其他单词）
+    pub fn lemma(&self, word: &str, known: &HashSet<String>) -> String {
+        let word = text::normalize_key(word);
+        if let Some(lemma) = self.forms.get(&word) {
+            return lemma.clone();
+        }
+        if word.chars().count() < 4 || self.lemmas.contains(&word) || NOT_INFLECTED.contains(&word.as_str()) {
+            return word;
+        }
+
+        let candidates = candidates(&word);
+        let is_known = |c: &String| self.lemmas.contains(c) || known.contains(c);
+        if let Some(lemma) = candidates.iter().find(|c| is_known(c)) {
+            return lemma.clone();
+        }
+        if !self.lemmas.is_empty() {
+            // 有词表时只接受词表中的原形
+            return word;
+        }
+        unambiguous(&word).unwrap_or(word)
+    }
+
+    /// 单词的其他候选写法，用于重新核对识别失败的单词：小写、可能的原形（复数、-ed、-ing 等）
+    /// 和去掉常见派生后缀后的词干，越靠前越可能；不含单词本身
+    pub fn variants(&self, word: &str) -> Vec<String> {
+        let word = word.trim();
+        let lower = text::normalize_key(word);
+        let mut variants = vec![lower.clone()];
+        if let Some(lemma) = self.forms.get(&lower) {
+            variants.push(lemma.clone());
+        }
+        // 后缀规则按字节切分，只用于 ASCII 单词
+        if lower.is_ascii() && lower.len() >= 4 && !NOT_INFLECTED.contains(&lower.as_str()) {
+            variants.extend(candidates(&lower));
+            for suffix in DERIVATIONAL_SUFFIXES {
+                let Some(stem) = lower.strip_suffix(suffix).filter(|s| s.len() >= 3) else { continue };
+                if let Some(stem) = stem.strip_suffix('i') {
+                    variants.push(format!("{}y", stem));
+                }
+                variants.push(stem.to_string());
+            }
+        }
+
+        let mut seen = HashSet::new();
+        variants.retain(|v| v != word && seen.insert(v.clone()));
+        variants
+    }
+}
+
+fn is_vowel(c: u8) -> bool {
+    matches!(c, b'a' | b'e' | b'i' | b'o' | b'u')
+}
+
+/// 按后缀规则生成的候选原形，越靠前越可能
+fn candidates(word: &str) -> Vec<String> {
+    fn push_stem(candidates: &mut Vec<String>, stem: &str) {
+        // seed、thing 等去掉后缀只剩两个字母的不是屈折形式
+        if stem.len() < 3 {
+            return;
+        }
+        let bytes = stem.as_bytes();
+        let n = bytes.len();
+        // running → run
+        if bytes[n - 1] == bytes[n - 2] && !is_vowel(bytes[n - 1]) && !matches!(bytes[n - 1], b'l' | b's' | b'z') {
+            candidates.push(stem[..n - 1].to_string());
+        }
+        candidates.push(stem.to_string());
+        candidates.push(format!("{}e", stem));
+    }
+
+    let mut candidates = Vec::new();
+    for suffix in ["ies", "ied"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            candidates.push(format!("{}y", stem));
+        }
+    }
+    if let Some(stem) = word.strip_suffix("ying") {
+        candidates.push(format!("{}ie", stem));
+        candidates.push(format!("{}y", stem));
+    }
+    for suffix in ["ing", "ed"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            push_stem(&mut candidates, stem);
+        }
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        candidates.push(stem.to_string());
+    }
+    if let Some(stem) = word.strip_suffix('s').filter(|_| !word.ends_with("ss")) {
+        candidates.push(stem.to_string());
+    }
+    candidates
+}
+
+/// 不依赖已知原形、没有歧义的还原；无法确定时返回 None
+///
+/// -ing、-ed 只在词干以双写辅音（running）或两个辅音（walked）结尾时还原，
+/// `making`、`visited` 这类需要判断是否补 e 的词保持原样
+fn unambiguous(word: &str) -> Option<String> {
+    if let Some(stem) = word.strip_suffix("ies").filter(|s| s.len() >= 2) {
+        return Some(format!("{}y", stem));
+    }
+    if let Some(stem) = word.strip_suffix("ied").filter(|s| s.len() >= 2) {
+        return Some(format!("{}y", stem));
+    }
+    for suffix in ["sses", "ches", "shes", "xes", "zzes"] {
+        if word.ends_with(suffix) {
+            return Some(word[..word.len() - 2].to_string());
+        }
+    }
+    for suffix in ["ing", "ed"] {
+        let Some(stem) = word.strip_suffix(suffix) else { continue };
+        let bytes = stem.as_bytes();
+        let n = bytes.len();
+        if n < 3 || !bytes.iter().any(|&c| is_vowel(c) || c == b'y') {
+            return None;
+        }
+        let (last, previous) = (bytes[n - 1], bytes[n - 2]);
+        if last == previous && !is_vowel(last) && !matches!(last, b'l' | b's' | b'z') {
+            return Some(stem[..n - 1].to_string());
+        }
+        if !is_vowel(last) && last != b'y' && (!is_vowel(previous) || is_vowel(bytes[n - 3])) {
+            return Some(stem.to_string());
+        }
+        return None;
+    }
+    if word.ends_with('s') && !word.ends_with("ss") && !word.ends_with("us") && !word.ends_with("is") {
+        return Some(word[..word.len() - 1].to_string());
+    }
+    None
+}
+
+impl ExtractResult {
+    /// 把单词还原为原形后合并重复项，返回被合并的单词数
+    ///
+    /// 合并时保留先出现的单词，追加其他形式中不同的释义并累加词频（按词频提取的结果随后
+    /// 重新排名）；词书中的其他单词作为已知原形参与判断。短语不变
+    pub fn lemmatize(&mut self, lemmatizer: &Lemmatizer) -> usize {
+        let known: HashSet<String> = self.words.iter().map(|w| text::normalize_key(&w.word)).collect();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut words: Vec<Word> = Vec::with_capacity(self.words.len());
+
+        for mut word in std::mem::take(&mut self.words) {
+            let lemma = lemmatizer.lemma(&word.word, &known);
+            match index.get(&lemma) {
+                Some(&i) => {
+                    let kept = &mut words[i];
+                    let meaning = word.meaning.trim();
+                    if !meaning.is_empty() && !kept.meaning.contains(meaning) {
+                        if !kept.meaning.is_empty() {
+                            kept.meaning.push('；');
+                        }
+                        kept.meaning.push_str(meaning);
+                    }
+                    if let (Some(total), Some(count)) = (kept.frequency.as_mut(), word.frequency) {
+                        *total += count;
+                    }
+                }
+                None => {
+                    if lemma != text::normalize_key(&word.word) {
+                        log::debug!("词形还原: {} → {}", word.word, lemma);
+                        word.word = lemma.clone();
+                    }
+                    index.insert(lemma, words.len());
+                    words.push(word);
+                }
+            }
+        }
+
+        // 按词频提取的结果合并后重新排名
+        if words.iter().all(|w| w.frequency.is_some()) {
+            words.sort_by_key(|w| std::cmp::Reverse(w.frequency));
+            for (rank, word) in words.iter_mut().enumerate() {
+                word.number = (rank + 1).to_string();
+            }
+        }
+
+        let merged = self.total_words.saturating_sub(words.len());
+        self.words = words;
+        self.total_words = self.words.len();
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lemmatize() {
+        let lemmatizer = Lemmatizer::new();
+        let none = HashSet::new();
+        for (form, lemma) in [("running", "run"), ("ran", "run"), ("runs", "run"), ("walked", "walk"), ("studies", "study"), ("boxes", "box"), ("making", "making"), ("morning", "morning"), ("bus", "bus")] {
+            assert_eq!(lemmatizer.lemma(form, &none), lemma, "{}", form);
+        }
+
+        let known: HashSet<String> = ["make".to_string()].into();
+        assert_eq!(lemmatizer.lemma("making", &known), "make");
+
+        let lemmatizer = Lemmatizer::new().with_wordlist("# 注释\nvisit -> visited,visiting\nhope\n");
+        assert_eq!(lemmatizer.lemma("Visited", &none), "visit");
+        assert_eq!(lemmatizer.lemma("hoped", &none), "hope");
+        assert_eq!(lemmatizer.lemma("jumped", &none), "jumped");
+    }
+
+    #[test]
+    fn test_variants() {
+        let lemmatizer = Lemmatizer::new();
+        assert_eq!(lemmatizer.variants("Apple"), ["apple"]);
+        let apples = lemmatizer.variants("Apples");
+        assert_eq!(apples[0], "apples");
+        assert!(apples.contains(&"apple".to_string()));
+        assert!(lemmatizer.variants("happily").contains(&"happy".to_string()));
+        assert!(lemmatizer.variants("kindness").contains(&"kind".to_string()));
+        assert_eq!(lemmatizer.variants("children"), ["child"]);
+        assert!(lemmatizer.variants("abroad").is_empty());
+    }
+}
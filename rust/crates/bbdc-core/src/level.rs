@@ -0,0 +1,240 @@
+//! 词汇分级模块
+//!
+//! 按分级词表为单词标注 CEFR 等级（A1–C2）或词频档位（1 为最常用），之后可以按等级
+//! 排序和筛选。内置词表只收录各等级的常见词，完整的分级请提供自己的词表；
+//! 词表中查不到的屈折形式（`abandoned`）按原形（`abandon`）查找
+
+use crate::{text, Error, ExtractResult, Lemmatizer, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// CEFR 等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Cefr {
+    A1,
+    A2,
+    B1,
+    B2,
+    C1,
+    C2,
+}
+
+/// 词汇等级：CEFR 等级或词频档位
+///
+/// 序列化为 `"B1"` 或档位数字；CEFR 等级排在所有档位之前，两种等级不要混用
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(untagged)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Level {
+    Cefr(Cefr),
+    /// 词频档位，从 1 开始，越小越常用
+    Band(u8),
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Level::Cefr(cefr) => write!(f, "{:?}", cefr),
+            Level::Band(band) => write!(f, "第{}档", band),
+        }
+    }
+}
+
+impl std::str::FromStr for Level {
+    type Err = Error;
+
+    /// 解析 `A1`–`C2`（不区分大小写）或从 1 开始的档位数字
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let cefr = match s.to_uppercase().as_str() {
+            "A1" => Cefr::A1,
+            "A2" => Cefr::A2,
+            "B1" => Cefr::B1,
+            "B2" => Cefr::B2,
+            "C1" => Cefr::C1,
+            "C2" => Cefr::C2,
+            _ => {
+                return match s.parse::<u8>() {
+                    Ok(band) if band > 0 => Ok(Level::Band(band)),
+                    _ => Err(Error::Parse(format!("无效的等级: {}（可用 A1–C2 或从 1 开始的档位）", s))),
+                };
+            }
+        };
+        Ok(Level::Cefr(cefr))
+    }
+}
+
+/// 内置词表：各等级的常见词
+const BUILTIN: [(Cefr, &[&str]); 5] = [
+    (
+        Cefr::A1,
+        &[
+            "apple", "bag", "bed", "big", "book", "brother", "car", "cat", "chair", "city", "day", "dog", "door",
+            "drink", "eat", "family", "father", "food", "friend", "green", "happy", "house", "milk", "morning",
+            "mother", "music", "name", "night", "play", "read", "red", "run", "school", "sister", "small", "swim",
+            "table", "teacher", "today", "walk", "water", "week", "window", "write", "year",
+        ],
+    ),
+    (
+        Cefr::A2,
+        &[
+            "advice", "airport", "angry", "bridge", "camera", "careful", "cheap", "culture", "dangerous", "dream",
+            "exam", "famous", "fashion", "future", "guess", "healthy", "hotel", "island", "journey", "kitchen",
+            "medicine", "message", "ocean", "passenger", "prefer", "quiet", "recipe", "remember", "science",
+            "several", "traffic", "wallet", "weather",
+        ],
+    ),
+    (
+        Cefr::B1,
+        &[
+            "achieve", "advantage", "afford", "announce", "anxious", "attitude", "available", "behaviour",
+            "confident", "consider", "contain", "convince", "decade", "deliver", "depend", "despite", "emotion",
+            "encourage", "enormous", "environment", "evidence", "experience", "ability", "opinion", "improve",
+            "influence", "opportunity", "pollution", "purpose", "responsible", "suggest",
+        ],
+    ),
+    (
+        Cefr::B2,
+        &[
+            "abandon", "accurate", "acknowledge", "adequate", "analyse", "anticipate", "assess", "assume",
+            "bias", "capable", "collapse", "commitment", "consequence", "controversial", "crucial", "dedicate",
+            "demonstrate", "distinguish", "dominate", "eliminate", "emphasis", "enhance", "evaluate",
+            "inevitable", "legislation", "perceive", "reluctant", "sufficient", "tendency", "vulnerable",
+        ],
+    ),
+    (
+        Cefr::C1,
+        &[
+            "ambiguous", "arbitrary", "coherent", "compel", "consensus", "contemplate", "deteriorate",
+            "discrepancy", "empirical", "explicit", "inherent", "integrity", "notion", "paradigm", "plausible",
+            "pragmatic", "prevalent", "scrutiny", "subsequent", "undermine",
+        ],
+    ),
+];
+
+/// 分级词表
+#[derive(Debug, Clone)]
+pub struct LevelList {
+    /// 单词（小写）→ 等级
+    levels: HashMap<String, Level>,
+    /// 以词表中的单词为已知原形
+    lemmatizer: Lemmatizer,
+}
+
+impl Default for LevelList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LevelList {
+    /// 使用内置词表
+    pub fn new() -> Self {
+        let levels: HashMap<String, Level> = BUILTIN
+            .iter()
+            .flat_map(|(cefr, words)| words.iter().map(|w| (w.to_string(), Level::Cefr(*cefr))))
+            .collect();
+        let lemmas: Vec<&str> = levels.keys().map(String::as_str).collect();
+        let lemmatizer = Lemmatizer::new().with_wordlist(&lemmas.join("\n"));
+        Self { levels, lemmatizer }
+    }
+
+    /// 读取词表并合并到内置词表（优先于内置词表）
+    ///
+    /// 每行为单词和等级，以空格、制表符或逗号分隔，如 `abandon B2`、`the,1`；`#` 开头的行为注释
+    pub fn with_wordlist(mut self, content: &str) -> Result<Self> {
+        for (i, line) in content.lines().map(str::trim).enumerate() {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((word, level)) = line.rsplit_once([' ', '\t', ',']) else {
+                return Err(Error::Parse(format!("分级词表第 {} 行缺少等级: {}", i + 1, line)));
+            };
+            let word = text::normalize_key(word.trim_end_matches([' ', '\t', ',']));
+            if !word.is_empty() {
+                self.lemmatizer = self.lemmatizer.with_wordlist(&word);
+                self.levels.insert(word, level.parse()?);
+            }
+        }
+        Ok(self)
+    }
+
+    /// 读取词表文件，见 [`with_wordlist`](Self::with_wordlist)
+    pub fn load_wordlist<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let list = self.with_wordlist(&content)?;
+        log::info!("分级词表包含 {} 个单词", list.levels.len());
+        Ok(list)
+    }
+
+    /// 单词的等级，查不到时按原形查找
+    pub fn level(&self, word: &str) -> Option<Level> {
+        let key = text::normalize_key(word);
+        self.levels
+            .get(&key)
+            .or_else(|| self.levels.get(&self.lemmatizer.lemma(&key, &HashSet::new())))
+            .copied()
+    }
+}
+
+impl ExtractResult {
+    /// 按分级词表标注单词的等级，返回标注的数量；查不到的单词保留原有等级
+    pub fn tag_levels(&mut self, list: &LevelList) -> usize {
+        let mut tagged = 0;
+        for word in &mut self.words {
+            if let Some(level) = list.level(&word.word) {
+                word.level = Some(level);
+                tagged += 1;
+            }
+        }
+        tagged
+    }
+
+    /// 按等级从低到高排序，未标注的单词排在最后，同等级保持原有顺序
+    pub fn sort_by_level(&mut self) {
+        self.words.sort_by_key(|w| (w.level.is_none(), w.level));
+    }
+
+    /// 只保留等级在 `min` 和 `max` 之间（含）的单词，返回去掉的数量；未标注的单词保留
+    pub fn retain_levels(&mut self, min: Option<Level>, max: Option<Level>) -> usize {
+        let before = self.words.len();
+        self.words.retain(|w| {
+            w.level.map_or(true, |level| min.map_or(true, |min| level >= min) && max.map_or(true, |max| level <= max))
+        });
+        self.total_words = self.words.len();
+        before - self.words.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_and_filter_levels() {
+        assert_eq!("b2".parse::<Level>().unwrap(), Level::Cefr(Cefr::B2));
+        assert_eq!("3".parse::<Level>().unwrap(), Level::Band(3));
+        assert!("0".parse::<Level>().is_err());
+
+        let list = LevelList::new().with_wordlist("# 自定义\nserendipity C2\n").unwrap();
+        assert_eq!(list.level("Abandoned"), Some(Level::Cefr(Cefr::B2)));
+        assert_eq!(list.level("serendipity"), Some(Level::Cefr(Cefr::C2)));
+        assert!(LevelList::new().with_wordlist("abandon").is_err());
+
+        let rows = [["1", "ambiguous", "模棱两可的"], ["2", "xylograph", "木版"], ["3", "apple", "苹果"], ["4", "abandon", "放弃"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let mut result = crate::WordExtractor::new(true, false).extract_from_rows(rows);
+        assert_eq!(result.tag_levels(&list), 3);
+        result.sort_by_level();
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["apple", "abandon", "ambiguous", "xylograph"]);
+
+        assert_eq!(result.retain_levels(Some(Level::Cefr(Cefr::B1)), Some(Level::Cefr(Cefr::B2))), 2);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["abandon", "xylograph"]);
+    }
+}
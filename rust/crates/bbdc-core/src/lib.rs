@@ -0,0 +1,237 @@
+//! BBDC Word Tool 核心库
+//!
+//! 从 Markdown 文件中提取单词，并提供词书元数据、报告等与网络无关的类型。
+//! 核对、LLM 更正与 PDF 转换等需要访问外部服务的后端位于 `bbdc-providers`。
+//!
+//! 可选特性：
+//! - `html`（默认）：HTML 表格解析
+//! - `http`：为 HTTP 错误提供 `Error::Http`，并支持从网址提取（`WordExtractor::extract_from_url`）；由 `bbdc-providers` 启用
+//! - `clap`：为公开枚举派生 `clap::ValueEnum`
+//! - `schema`：为公开类型派生 `schemars::JsonSchema`，用于校验 JSON 输出
+//! - `xlsx`：读取 Excel/ODS 单词表，导出 Excel 工作簿
+//! - `docx`：读取 Word 文档中的表格和段落
+//! - `apkg`：读取和导出 Anki 牌组
+//! - `epub`：读取 EPUB 电子书中的章节
+//!
+//! `text` 模块（分词、规范化）不依赖标准库以外的任何东西，始终可用。
+//!
+//! 只需要提取功能时直接依赖本 crate，不会引入 HTTP/TLS 依赖：
+//!
+//! ```toml
+//! bbdc-core = "0.0"
+//! ```
+
+extern crate alloc;
+
+pub mod text;
+pub mod env_loader;
+pub mod word_extractor;
+pub mod format;
+pub mod columns;
+pub mod confusables;
+pub mod phonetics;
+pub mod pos;
+pub mod lemma;
+pub mod level;
+pub mod known;
+pub mod spelling;
+pub mod sanitize;
+pub mod humanize;
+pub mod pages;
+pub mod subtitle;
+pub mod kindle;
+#[cfg(feature = "docx")]
+pub mod docx;
+#[cfg(feature = "apkg")]
+pub mod apkg;
+#[cfg(feature = "xlsx")]
+pub mod xlsx;
+#[cfg(feature = "epub")]
+pub mod epub;
+pub mod check;
+pub mod corrections;
+pub mod metadata;
+pub mod book;
+pub mod report;
+pub mod manifest;
+
+// 重新导出常用类型
+pub use env_loader::EnvLoader;
+pub use word_extractor::{WordExtractor, Word, ExtractMode, ExtractResult};
+pub use format::InputFormat;
+pub use columns::{Column, ColumnMapping};
+pub use confusables::ConfusablePair;
+pub use phonetics::PronouncingDictionary;
+pub use pos::PartOfSpeech;
+pub use lemma::Lemmatizer;
+pub use level::{Level, LevelList};
+pub use known::KnownWords;
+pub use spelling::{Spelling, SpellingNormalizer};
+pub use pages::PageIndex;
+pub use check::{CheckDiff, CheckResult, SourceCheck};
+pub use corrections::{Confidence, CorrectionLog, CorrectionRecord};
+pub use metadata::BookMetadata;
+pub use book::{Book, Entry};
+pub use report::Report;
+pub use manifest::RunManifest;
+
+/// 错误类型
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("文件读取错误: {0}")]
+    FileRead(#[from] std::io::Error),
+    
+    #[cfg(feature = "http")]
+    #[error("HTTP请求错误: {0}")]
+    Http(#[from] reqwest::Error),
+    
+    #[error("JSON解析错误: {0}")]
+    JsonParse(#[from] serde_json::Error),
+    
+    #[error("环境变量错误: {0}")]
+    EnvVar(String),
+    
+    #[error("解析错误: {0}")]
+    Parse(String),
+    
+    #[error("没有提取到单词: {0}")]
+    Empty(String),
+    
+    #[error("API 请求失败: {0}")]
+    Api(ApiError),
+    
+    /// 接口返回 HTTP 429，请求过于频繁
+    #[error("API 请求过于频繁，请稍后重试: {0}")]
+    RateLimited(ApiError),
+    
+    /// 接口返回 HTTP 401、403，通常是请求被拦截或登录态失效
+    #[error("API 拒绝了请求（可能被拦截或登录态失效）: {0}")]
+    Forbidden(ApiError),
+    
+    /// 响应不是 JSON 或字段类型无法识别，`body` 为截断后的响应正文
+    #[error("无法识别接口的响应: {message}\n  响应正文: {body}")]
+    BadResponse { message: String, body: String },
+    
+    /// 响应中没有 data_body 或其中没有单词列表，接口格式可能已变化；`missing` 为缺少的字段，
+    /// `fields` 为响应（或 data_body）实际包含的字段
+    #[error("接口的响应中没有单词列表（缺少 {missing}），接口格式可能已变化；实际包含的字段: {}\n  响应正文: {body}", join_fields(.fields))]
+    EmptyDataBody { missing: String, fields: Vec<String>, body: String },
+    
+    /// 不背单词要求输入验证码，但没有设置求解回调或用户放弃
+    #[error("不背单词要求输入验证码，请在浏览器中打开 {image_url} 完成验证后重试")]
+    CaptchaRequired { image_url: String },
+    
+    /// 连续多次输入的验证码都未通过
+    #[error("验证码连续 {attempts} 次未通过")]
+    CaptchaFailed { attempts: usize },
+    
+    #[error("其他错误: {0}")]
+    Other(String),
+}
+
+impl Error {
+    /// 进程退出码：没有提取到单词时为 3，其余错误为 1
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Empty(_) => 3,
+            _ => 1,
+        }
+    }
+    
+    /// 是否为服务端限流或故障（HTTP 429、5xx），此时应放慢请求
+    pub fn is_throttled(&self) -> bool {
+        match self {
+            Error::Api(e) => matches!(e.status, 429 | 500..=599),
+            Error::RateLimited(_) => true,
+            _ => false,
+        }
+    }
+    
+    /// 无法识别的响应，`body` 截断为 [`ApiError::MAX_BODY_CHARS`] 个字符
+    pub fn bad_response(message: impl Into<String>, body: &str) -> Self {
+        Error::BadResponse { message: message.into(), body: truncate_body(body) }
+    }
+    
+    /// 缺少单词列表的响应，`fields` 为响应实际包含的字段
+    pub fn empty_data_body(missing: impl Into<String>, fields: Vec<String>, body: &str) -> Self {
+        Error::EmptyDataBody { missing: missing.into(), fields, body: truncate_body(body) }
+    }
+    
+    /// 是否为无法连接服务（连接失败、超时），此时可以改用离线核对
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            #[cfg(feature = "http")]
+            Error::Http(e) => e.is_connect() || e.is_timeout(),
+            _ => false,
+        }
+    }
+}
+
+/// 接口的异常响应（非 2xx 状态或无法识别的正文），保留响应头和截断的正文便于排查
+/// 被拦截（如返回验证页面）或接口格式变化的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// HTTP 状态码
+    pub status: u16,
+    /// 错误说明
+    pub message: String,
+    /// 响应头（不含 `Set-Cookie`）
+    pub headers: Vec<(String, String)>,
+    /// 响应正文，超过 [`MAX_BODY_CHARS`](Self::MAX_BODY_CHARS) 个字符的部分被截断
+    pub body: String,
+}
+
+impl ApiError {
+    /// 保留的响应正文字符数
+    pub const MAX_BODY_CHARS: usize = 500;
+    
+    pub fn new(status: u16, message: impl Into<String>, headers: Vec<(String, String)>, body: &str) -> Self {
+        let headers = headers.into_iter().filter(|(name, _)| !name.eq_ignore_ascii_case("set-cookie")).collect();
+        Self { status, message: message.into(), headers, body: truncate_body(body) }
+    }
+}
+
+/// 按状态码分类：429 为 [`Error::RateLimited`]，401、403 为 [`Error::Forbidden`]，其余为 [`Error::Api`]
+impl From<ApiError> for Error {
+    fn from(e: ApiError) -> Self {
+        match e.status {
+            429 => Error::RateLimited(e),
+            401 | 403 => Error::Forbidden(e),
+            _ => Error::Api(e),
+        }
+    }
+}
+
+/// 以逗号连接字段名，没有字段时为“（无）”
+fn join_fields(fields: &[String]) -> String {
+    if fields.is_empty() {
+        return "（无）".to_string();
+    }
+    fields.join(", ")
+}
+
+/// 去掉两端空白，保留前 [`ApiError::MAX_BODY_CHARS`] 个字符，截断时以 `…` 结尾
+fn truncate_body(body: &str) -> String {
+    let body = body.trim();
+    let mut truncated: String = body.chars().take(ApiError::MAX_BODY_CHARS).collect();
+    if body.chars().nth(ApiError::MAX_BODY_CHARS).is_some() {
+        truncated.push('…');
+    }
+    truncated
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}（HTTP {}）", self.message, self.status)?;
+        if !self.headers.is_empty() {
+            let headers: Vec<String> = self.headers.iter().map(|(name, value)| format!("{}: {}", name, value)).collect();
+            write!(f, "\n  响应头: {}", headers.join("; "))?;
+        }
+        if !self.body.is_empty() {
+            write!(f, "\n  响应正文: {}", self.body)?;
+        }
+        Ok(())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,245 @@
+//! 运行清单模块
+//!
+//! 记录一次提取的输入、参数、用时、输出的单词以及核对和更正结果，保存为 JSON。
+//! 比较两份清单可以在升级工具或更换模型后，用同一本参考词书检查结果是否退化
+
+use crate::{BookMetadata, CheckResult, CorrectionRecord, ExtractResult, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// 一次运行的清单
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// 工具版本
+    pub version: String,
+    /// 运行时间（RFC 3339）
+    pub created_at: String,
+    pub input: String,
+    pub output: String,
+    /// 词书元数据，旧版本的清单中没有时为空
+    #[serde(default)]
+    pub metadata: BookMetadata,
+    /// 命令行参数
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 使用的 LLM 模型，没有调用 LLM 时为 None
+    #[serde(default)]
+    pub model: Option<String>,
+    pub elapsed_ms: u64,
+    /// 输出的单词
+    pub words: Vec<String>,
+    #[serde(default)]
+    pub total_phrases: usize,
+    #[serde(default)]
+    pub check: Option<CheckResult>,
+    #[serde(default)]
+    pub corrections: Vec<CorrectionRecord>,
+}
+
+impl RunManifest {
+    /// 以当前时间创建清单
+    pub fn new(input: &Path, output: &Path, result: &ExtractResult, elapsed: Duration) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            created_at: chrono::Local::now().to_rfc3339(),
+            input: input.display().to_string(),
+            output: output.display().to_string(),
+            metadata: BookMetadata::default(),
+            args: Vec::new(),
+            model: None,
+            elapsed_ms: elapsed.as_millis() as u64,
+            words: result.words.iter().map(|w| w.word.clone()).collect(),
+            total_phrases: result.total_phrases,
+            check: None,
+            corrections: Vec::new(),
+        }
+    }
+
+    /// 记录词书元数据
+    pub fn with_metadata(mut self, metadata: &BookMetadata) -> Self {
+        self.metadata = metadata.clone();
+        self
+    }
+
+    /// 记录命令行参数
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
+    }
+
+    /// 记录核对结果
+    pub fn with_check(mut self, check: &CheckResult) -> Self {
+        self.check = Some(check.clone());
+        self
+    }
+
+    /// 记录 LLM 更正建议
+    pub fn with_corrections(mut self, corrections: &[CorrectionRecord]) -> Self {
+        self.corrections = corrections.to_vec();
+        self
+    }
+
+    /// 记录使用的 LLM 模型
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = Some(model.to_string());
+        self
+    }
+
+    /// 识别成功率（0~1），没有核对时为 None
+    pub fn recognition_rate(&self) -> Option<f64> {
+        self.check
+            .as_ref()
+            .filter(|c| c.total_count > 0)
+            .map(|c| c.recognized_count as f64 / c.total_count as f64)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// 同一单词在两次运行中的更正建议
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CorrectionChange {
+    pub original: String,
+    /// 前一次的建议，没有建议时为 None
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// 两次运行的差异
+#[derive(Debug, Clone, Serialize)]
+pub struct RunComparison {
+    pub elapsed_ms: (u64, u64),
+    pub recognition_rate: (Option<f64>, Option<f64>),
+    pub model: (Option<String>, Option<String>),
+    /// 只在后一次输出的单词
+    pub added_words: Vec<String>,
+    /// 只在前一次输出的单词
+    pub removed_words: Vec<String>,
+    /// 前一次识别成功、后一次识别失败的单词
+    pub newly_unrecognized: Vec<String>,
+    /// 前一次识别失败、后一次识别成功的单词
+    pub newly_recognized: Vec<String>,
+    pub changed_corrections: Vec<CorrectionChange>,
+}
+
+impl RunComparison {
+    /// 输出的单词、核对结果和更正建议是否完全相同（不比较用时）
+    pub fn is_identical(&self) -> bool {
+        self.added_words.is_empty()
+            && self.removed_words.is_empty()
+            && self.newly_unrecognized.is_empty()
+            && self.newly_recognized.is_empty()
+            && self.changed_corrections.is_empty()
+    }
+}
+
+/// 比较两次运行
+pub fn compare(before: &RunManifest, after: &RunManifest) -> RunComparison {
+    // 保持原有顺序的差集
+    fn difference(a: &[String], b: &[String]) -> Vec<String> {
+        let b: HashSet<&String> = b.iter().collect();
+        a.iter().filter(|w| !b.contains(w)).cloned().collect()
+    }
+
+    let unrecognized = |m: &RunManifest| m.check.as_ref().map(|c| c.unrecognized_words.clone()).unwrap_or_default();
+    let (before_failed, after_failed) = (unrecognized(before), unrecognized(after));
+    // 两次都核对过才比较识别结果，且只比较两次都输出的单词
+    let both_checked = before.check.is_some() && after.check.is_some();
+    let common: HashSet<&String> = before.words.iter().filter(|w| after.words.contains(w)).collect();
+    let (newly_unrecognized, newly_recognized) = if both_checked {
+        let keep = |words: Vec<String>| words.into_iter().filter(|w| common.contains(w)).collect();
+        (keep(difference(&after_failed, &before_failed)), keep(difference(&before_failed, &after_failed)))
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    let mut suggestions: BTreeMap<&str, (Option<&str>, Option<&str>)> = BTreeMap::new();
+    for record in &before.corrections {
+        suggestions.entry(&record.original).or_default().0 = Some(&record.corrected);
+    }
+    for record in &after.corrections {
+        suggestions.entry(&record.original).or_default().1 = Some(&record.corrected);
+    }
+    let changed_corrections = suggestions
+        .into_iter()
+        .filter(|(_, (b, a))| b != a)
+        .map(|(original, (b, a))| CorrectionChange {
+            original: original.to_string(),
+            before: b.map(str::to_string),
+            after: a.map(str::to_string),
+        })
+        .collect();
+
+    RunComparison {
+        elapsed_ms: (before.elapsed_ms, after.elapsed_ms),
+        recognition_rate: (before.recognition_rate(), after.recognition_rate()),
+        model: (before.model.clone(), after.model.clone()),
+        added_words: difference(&after.words, &before.words),
+        removed_words: difference(&before.words, &after.words),
+        newly_unrecognized,
+        newly_recognized,
+        changed_corrections,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Confidence;
+
+    fn manifest(words: &[&str], unrecognized: &[&str], corrections: &[(&str, &str)]) -> RunManifest {
+        let check = CheckResult::from_lists(words.iter().copied().filter(|w| !unrecognized.contains(w)), unrecognized.iter().copied());
+        RunManifest {
+            version: "0.0.0".to_string(),
+            created_at: String::new(),
+            input: "unit1.md".to_string(),
+            output: "unit1.txt".to_string(),
+            metadata: BookMetadata::default(),
+            args: Vec::new(),
+            model: None,
+            elapsed_ms: 1000,
+            check: Some(check),
+            words: words.iter().map(|w| w.to_string()).collect(),
+            total_phrases: 0,
+            corrections: corrections.iter().map(|(o, c)| CorrectionRecord::new(o, c, Confidence::High)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_compare_runs() {
+        let before = manifest(&["abandon", "abilty", "abroad"], &["abilty"], &[("abilty", "ability")]);
+        let after = manifest(&["abandon", "abilty", "absorb"], &["abandon", "abilty"], &[("abilty", "agility")]);
+
+        let diff = compare(&before, &after);
+        assert_eq!(diff.added_words, ["absorb"]);
+        assert_eq!(diff.removed_words, ["abroad"]);
+        assert_eq!(diff.newly_unrecognized, ["abandon"]);
+        assert!(diff.newly_recognized.is_empty());
+        assert_eq!(diff.changed_corrections[0].after.as_deref(), Some("agility"));
+        assert!(!diff.is_identical());
+        assert!(compare(&before, &before).is_identical());
+    }
+
+    #[test]
+    fn test_manifest_metadata() {
+        let metadata = BookMetadata { title: "新概念".to_string(), ..Default::default() };
+        let run = manifest(&["abandon"], &[], &[]).with_metadata(&metadata);
+        let loaded: RunManifest = serde_json::from_str(&serde_json::to_string(&run).unwrap()).unwrap();
+        assert_eq!(loaded.metadata, metadata);
+
+        let mut old = serde_json::to_value(&run).unwrap();
+        old.as_object_mut().unwrap().remove("metadata");
+        let loaded: RunManifest = serde_json::from_value(old).unwrap();
+        assert_eq!(loaded.metadata, BookMetadata::default());
+    }
+}
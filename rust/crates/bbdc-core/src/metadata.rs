@@ -0,0 +1,122 @@
+//! 词书元数据模块
+//!
+//! 词书的标题、描述、标签和作者，支持用源文件名和日期做模板
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// 词书元数据
+///
+/// 各字段支持以下占位符：
+/// - `{name}`：源文件名（不含扩展名）
+/// - `{file}`：源文件名
+/// - `{date}`：当天日期（YYYY-MM-DD）
+/// - `{chapter}`：章节标题（按章节拆分时）
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BookMetadata {
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub author: String,
+}
+
+impl BookMetadata {
+    /// 以源文件和当天日期展开模板
+    pub fn render<P: AsRef<Path>>(&self, source: P) -> Self {
+        let date = chrono::Local::now().format("%Y-%m-%d").to_string();
+        self.render_with_date(source, &date)
+    }
+
+    /// 生成某一章节的元数据
+    ///
+    /// 展开 `{chapter}` 占位符并把章节标题加入标签，供按章节拆分的导出沿用；
+    /// 标题中没有占位符时追加 ` - 章节` 以区分各章
+    pub fn for_chapter(&self, chapter: &str) -> Self {
+        let expand = |template: &str| template.replace("{chapter}", chapter);
+
+        let title = if self.title.contains("{chapter}") {
+            expand(&self.title)
+        } else {
+            format!("{} - {}", self.title, chapter)
+        };
+
+        let mut tags: Vec<String> = self.tags.iter().map(|t| expand(t)).collect();
+        if !tags.iter().any(|t| t == chapter) {
+            tags.push(chapter.to_string());
+        }
+
+        Self {
+            title,
+            description: expand(&self.description),
+            tags,
+            author: self.author.clone(),
+        }
+    }
+
+    /// 以指定日期展开模板
+    pub fn render_with_date<P: AsRef<Path>>(&self, source: P, date: &str) -> Self {
+        let source = source.as_ref();
+        let name = source
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let file = source
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+
+        let expand = |template: &str| {
+            template
+                .replace("{name}", name)
+                .replace("{file}", file)
+                .replace("{date}", date)
+        };
+
+        Self {
+            title: expand(&self.title),
+            description: expand(&self.description),
+            tags: self.tags.iter().map(|t| expand(t)).collect(),
+            author: expand(&self.author),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_template() {
+        let metadata = BookMetadata {
+            title: "{name} 词汇".to_string(),
+            description: "整理自 {file}，{date}".to_string(),
+            tags: vec!["{name}".to_string(), "考研".to_string()],
+            author: String::new(),
+        };
+
+        let rendered = metadata.render_with_date("books/Unit1.md", "2024-05-01");
+
+        assert_eq!(rendered.title, "Unit1 词汇");
+        assert_eq!(rendered.description, "整理自 Unit1.md，2024-05-01");
+        assert_eq!(rendered.tags, vec!["Unit1", "考研"]);
+    }
+
+    #[test]
+    fn test_for_chapter() {
+        let metadata = BookMetadata {
+            title: "新概念".to_string(),
+            tags: vec!["英语".to_string()],
+            ..Default::default()
+        };
+
+        let chapter = metadata.for_chapter("Lesson 1");
+        assert_eq!(chapter.title, "新概念 - Lesson 1");
+        assert_eq!(chapter.tags, vec!["英语", "Lesson 1"]);
+
+        let templated = BookMetadata { title: "{chapter}（新概念）".to_string(), ..Default::default() }.for_chapter("Lesson 2");
+        assert_eq!(templated.title, "Lesson 2（新概念）");
+    }
+}
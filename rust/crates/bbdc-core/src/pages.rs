@@ -0,0 +1,166 @@
+//! 页面来源模块
+//!
+//! 读取 Mineru 解压目录中的 `*_content_list.json`，记录每段文字、表格所在的页码和截图，
+//! 用于标注 PDF 来源的单词出自哪一页，并在报告中把原文截图放在可疑单词旁边供人工核对
+
+use crate::{text, Result, Word};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// `content_list.json` 中的一个内容块
+#[derive(Debug, Deserialize)]
+struct RawBlock {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    table_body: Option<String>,
+    #[serde(default)]
+    img_path: Option<String>,
+    page_idx: usize,
+}
+
+/// 带页码的内容块
+#[derive(Debug, Clone)]
+struct Block {
+    /// 页码（从 1 开始）
+    page: usize,
+    /// 小写的文字内容（表格去掉 HTML 标签）
+    text: String,
+    /// 块内的单词，用于整词匹配
+    tokens: HashSet<String>,
+    /// 表格、图片的截图
+    image: Option<PathBuf>,
+}
+
+/// PDF 各页的内容索引
+#[derive(Debug, Clone, Default)]
+pub struct PageIndex {
+    blocks: Vec<Block>,
+}
+
+impl PageIndex {
+    /// 解析 `content_list.json`，截图路径相对于 `base_dir`
+    pub fn parse(json: &str, base_dir: &Path) -> Result<Self> {
+        let raw: Vec<RawBlock> = serde_json::from_str(json)?;
+        let blocks = raw
+            .into_iter()
+            .map(|block| {
+                let content = match (block.text, block.table_body) {
+                    (_, Some(table)) => strip_tags(&table),
+                    (Some(text), None) => text,
+                    (None, None) => String::new(),
+                };
+                let text = content.to_lowercase();
+                let tokens = text::tokenize(&text).map(str::to_string).collect();
+                Block {
+                    page: block.page_idx + 1,
+                    text,
+                    tokens,
+                    image: block.img_path.filter(|p| !p.is_empty()).map(|p| base_dir.join(p)),
+                }
+            })
+            .collect();
+        Ok(Self { blocks })
+    }
+
+    /// 在 Mineru 解压目录中查找并读取 `*_content_list.json`，没有时返回 None
+    pub fn find<P: AsRef<Path>>(dir: P) -> Result<Option<Self>> {
+        let dir = dir.as_ref();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            let is_content_list = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.ends_with("content_list.json"));
+            if is_content_list {
+                let index = Self::parse(&fs::read_to_string(&path)?, dir)?;
+                log::info!("读取页面索引: {:?}（{} 页）", path, index.page_count());
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 单词或短语首次出现的页码，优先在表格中查找
+    pub fn page_of(&self, word: &str) -> Option<usize> {
+        let key = text::normalize_key(word);
+        if key.is_empty() {
+            return None;
+        }
+        let matches = |block: &&Block| {
+            if text::is_phrase(&key) {
+                block.text.contains(&key)
+            } else {
+                block.tokens.contains(&key)
+            }
+        };
+        let tables = self.blocks.iter().filter(|b| b.image.is_some()).find(matches);
+        tables.or_else(|| self.blocks.iter().find(matches)).map(|b| b.page)
+    }
+
+    /// 页面上的表格、图片截图
+    pub fn images(&self, page: usize) -> Vec<&Path> {
+        self.blocks
+            .iter()
+            .filter(|b| b.page == page)
+            .filter_map(|b| b.image.as_deref())
+            .collect()
+    }
+
+    /// 总页数
+    pub fn page_count(&self) -> usize {
+        self.blocks.iter().map(|b| b.page).max().unwrap_or(0)
+    }
+}
+
+/// 去掉 HTML 标签，标签替换为空格
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// 为没有页码的单词标注页码，返回标注的数量
+pub fn fill_pages(words: &mut [Word], index: &PageIndex) -> usize {
+    let mut filled = 0;
+    for word in words.iter_mut().filter(|w| w.page.is_none()) {
+        word.page = index.page_of(&word.word);
+        filled += usize::from(word.page.is_some());
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_index() {
+        let json = r#"[
+            {"type": "text", "text": "Unit 1 abandon", "text_level": 1, "page_idx": 0},
+            {"type": "table", "img_path": "images/t1.jpg", "table_body": "<table><tr><td>1</td><td>abandon</td><td>放弃</td></tr></table>", "page_idx": 1},
+            {"type": "table", "img_path": "images/t2.jpg", "table_body": "<table><tr><td>take off</td></tr></table>", "page_idx": 2}
+        ]"#;
+        let index = PageIndex::parse(json, Path::new("out")).unwrap();
+
+        assert_eq!(index.page_of("Abandon"), Some(2));
+        assert_eq!(index.page_of("unit"), Some(1));
+        assert_eq!(index.page_of("take off"), Some(3));
+        assert_eq!(index.page_of("aband"), None);
+        assert_eq!(index.images(2), [Path::new("out/images/t1.jpg")]);
+        assert_eq!(index.page_count(), 3);
+    }
+}
@@ -0,0 +1,243 @@
+//! 音标模块
+//!
+//! 为缺少音标的单词补充 IPA（美式）：读取 CMUdict 格式的发音词典并把 ARPAbet 转换为 IPA，
+//! 或调用本机安装的 eSpeak NG（`espeak-ng --ipa`）。两者都不需要网络
+
+use crate::{Result, Word};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// CMUdict 格式的发音词典
+///
+/// 每行为 `WORD  AH0 B AE1 N D AH0 N`，`;;;` 开头的行为注释，`WORD(1)` 等为备选发音
+#[derive(Debug, Clone, Default)]
+pub struct PronouncingDictionary {
+    entries: HashMap<String, String>,
+}
+
+impl PronouncingDictionary {
+    /// 解析词典内容，每个单词只保留第一个发音
+    pub fn parse(content: &str) -> Self {
+        let mut entries = HashMap::new();
+        for line in content.lines() {
+            if line.starts_with(";;;") {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let Some(word) = parts.next() else { continue };
+            if word.contains('(') {
+                continue;
+            }
+            let phones: Vec<&str> = parts.take_while(|p| !p.starts_with('#')).collect();
+            if phones.is_empty() {
+                continue;
+            }
+            entries.entry(word.to_lowercase()).or_insert_with(|| arpabet_to_ipa(&phones));
+        }
+        Self { entries }
+    }
+
+    /// 读取词典文件（CMUdict 为 Latin-1 编码，非 UTF-8 字节按原样替换）
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let data = fs::read(path)?;
+        let dict = Self::parse(&String::from_utf8_lossy(&data));
+        log::info!("发音词典包含 {} 个单词", dict.len());
+        Ok(dict)
+    }
+
+    /// 查询单词的 IPA（不含斜线）
+    pub fn lookup(&self, word: &str) -> Option<&str> {
+        self.entries.get(&word.trim().to_lowercase()).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// ARPAbet 音素对应的 IPA，非重读的 AH、ER 分别为 ə、ɚ
+fn phone_ipa(phone: &str, stressed: bool) -> Option<&'static str> {
+    Some(match phone {
+        "AA" => "ɑ",
+        "AE" => "æ",
+        "AH" if stressed => "ʌ",
+        "AH" => "ə",
+        "AO" => "ɔ",
+        "AW" => "aʊ",
+        "AY" => "aɪ",
+        "EH" => "ɛ",
+        "ER" if stressed => "ɝ",
+        "ER" => "ɚ",
+        "EY" => "eɪ",
+        "IH" => "ɪ",
+        "IY" => "i",
+        "OW" => "oʊ",
+        "OY" => "ɔɪ",
+        "UH" => "ʊ",
+        "UW" => "u",
+        "B" => "b",
+        "CH" => "tʃ",
+        "D" => "d",
+        "DH" => "ð",
+        "F" => "f",
+        "G" => "ɡ",
+        "HH" => "h",
+        "JH" => "dʒ",
+        "K" => "k",
+        "L" => "l",
+        "M" => "m",
+        "N" => "n",
+        "NG" => "ŋ",
+        "P" => "p",
+        "R" => "r",
+        "S" => "s",
+        "SH" => "ʃ",
+        "T" => "t",
+        "TH" => "θ",
+        "V" => "v",
+        "W" => "w",
+        "Y" => "j",
+        "Z" => "z",
+        "ZH" => "ʒ",
+        _ => return None,
+    })
+}
+
+/// 两个辅音能否一起作为音节开头（如 pl、tr、st）
+fn is_onset_cluster(first: &str, second: &str) -> bool {
+    matches!(second, "L" | "R" | "W" | "Y") && matches!(first, "P" | "B" | "T" | "D" | "K" | "G" | "F" | "TH" | "SH" | "S")
+        || first == "S" && matches!(second, "P" | "T" | "K" | "M" | "N")
+}
+
+/// 把 ARPAbet 音素序列转换为 IPA
+///
+/// 多音节词在重读音节前加 `ˈ`（主重音）或 `ˌ`（次重音），音节开头取重读元音前的辅音
+pub fn arpabet_to_ipa(phones: &[&str]) -> String {
+    // 拆出音素和重音标记
+    let phones: Vec<(&str, Option<char>)> = phones
+        .iter()
+        .map(|p| match p.strip_suffix(['0', '1', '2']) {
+            Some(base) => (base, p.chars().last()),
+            None => (*p, None),
+        })
+        .collect();
+    let syllables = phones.iter().filter(|(_, stress)| stress.is_some()).count();
+
+    let mut marks = vec![None; phones.len()];
+    let mut previous_vowel = None;
+    for (i, (_, stress)) in phones.iter().enumerate() {
+        let Some(stress) = stress else { continue };
+        let mark = match stress {
+            '1' => Some('ˈ'),
+            '2' => Some('ˌ'),
+            _ => None,
+        };
+        if let (Some(mark), true) = (mark, syllables > 1) {
+            let consonants = i - previous_vowel.map_or(0, |v| v + 1);
+            let onset = match (previous_vowel, consonants) {
+                (None, n) => n,
+                (_, 0) => 0,
+                (_, n) if n >= 2 && is_onset_cluster(phones[i - 2].0, phones[i - 1].0) => 2,
+                _ => 1,
+            };
+            marks[i - onset] = Some(mark);
+        }
+        previous_vowel = Some(i);
+    }
+
+    let mut ipa = String::new();
+    for (i, (phone, stress)) in phones.iter().enumerate() {
+        if let Some(mark) = marks[i] {
+            ipa.push(mark);
+        }
+        match phone_ipa(phone, matches!(stress, Some('1' | '2'))) {
+            Some(symbol) => ipa.push_str(symbol),
+            None => ipa.push_str(&phone.to_lowercase()),
+        }
+    }
+    ipa
+}
+
+/// 用 eSpeak NG 生成 IPA（不含斜线），未安装或输出为空时返回 None
+pub fn espeak_ipa(word: &str) -> Option<String> {
+    ["espeak-ng", "espeak"].into_iter().find_map(|program| {
+        let output = Command::new(program).args(["-q", "--ipa", "-v", "en-us", word]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let ipa = String::from_utf8_lossy(&output.stdout).split_whitespace().collect::<Vec<_>>().join(" ");
+        (!ipa.is_empty()).then_some(ipa)
+    })
+}
+
+/// 规范化音标单元格：去掉已有的 `/…/` 或 `[…]` 后统一加上斜线，空白单元格返回 None
+pub fn normalize(cell: &str) -> Option<String> {
+    let ipa = cell.trim().trim_matches(|c| matches!(c, '/' | '[' | ']')).trim();
+    (!ipa.is_empty()).then(|| format!("/{}/", ipa))
+}
+
+/// 单元格是否为用 `/…/` 或 `[…]` 包围的音标
+pub fn is_phonetic(cell: &str) -> bool {
+    let cell = cell.trim();
+    cell.chars().count() > 2
+        && (cell.starts_with('/') && cell.ends_with('/') || cell.starts_with('[') && cell.ends_with(']'))
+}
+
+/// 为没有音标的单词补充音标，`lookup` 返回不含斜线的 IPA；返回补充的数量
+///
+/// 词组按词逐个查询，任一词查不到时跳过
+pub fn fill_phonetics<F>(words: &mut [Word], mut lookup: F) -> usize
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut filled = 0;
+    for word in words.iter_mut().filter(|w| w.phonetic.is_none()) {
+        let ipa: Option<Vec<String>> = word.word.split_whitespace().map(&mut lookup).collect();
+        if let Some(ipa) = ipa.filter(|parts| !parts.is_empty()) {
+            word.phonetic = normalize(&ipa.join(" "));
+            filled += 1;
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dictionary_to_ipa() {
+        let dict = PronouncingDictionary::parse(
+            ";;; comment\nABANDON  AH0 B AE1 N D AH0 N\nABILITY  AH0 B IH1 L AH0 T IY0\nCAT  K AE1 T\nCAT(1)  K AA1 T\nEXTRA  EH1 K S T R AH0\n",
+        );
+        assert_eq!(dict.lookup("Abandon"), Some("əˈbændən"));
+        assert_eq!(dict.lookup("ability"), Some("əˈbɪləti"));
+        assert_eq!(dict.lookup("cat"), Some("kæt"));
+        assert_eq!(dict.lookup("extra"), Some("ˈɛkstrə"));
+
+        let mut words = vec![Word {
+            number: "1".to_string(),
+            word: "cat".to_string(),
+            meaning: "猫".to_string(),
+            phonetic: None,
+            example: None,
+            pos: Vec::new(),
+            line_number: None,
+            page: None,
+            frequency: None,
+            difficulty: None,
+            level: None,
+            chapter: None,
+            source: None,
+        }];
+        assert_eq!(fill_phonetics(&mut words, |w| dict.lookup(w).map(str::to_string)), 1);
+        assert_eq!(words[0].phonetic.as_deref(), Some("/kæt/"));
+        assert!(is_phonetic("[kæt]") && !is_phonetic("例句"));
+    }
+}
@@ -0,0 +1,159 @@
+//! 词性模块
+//!
+//! 从中文释义中的词性缩写（`n.`、`vt.`、`adj.` 等）读取词性；释义中没有缩写时，
+//! 可以按常见后缀（-tion、-ly、-ous 等）推测。输出时可以按词性分组
+
+use crate::Word;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 词性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum PartOfSpeech {
+    Noun,
+    Verb,
+    Adjective,
+    Adverb,
+    Pronoun,
+    Preposition,
+    Conjunction,
+    Interjection,
+    Numeral,
+    Article,
+}
+
+impl PartOfSpeech {
+    /// 按词典中的缩写识别词性（不区分大小写，不含句点）
+    pub fn from_abbreviation(abbreviation: &str) -> Option<Self> {
+        Some(match abbreviation.to_lowercase().as_str() {
+            "n" => PartOfSpeech::Noun,
+            "v" | "vt" | "vi" | "aux" => PartOfSpeech::Verb,
+            "adj" | "a" => PartOfSpeech::Adjective,
+            "adv" | "ad" => PartOfSpeech::Adverb,
+            "pron" => PartOfSpeech::Pronoun,
+            "prep" => PartOfSpeech::Preposition,
+            "conj" => PartOfSpeech::Conjunction,
+            "int" | "interj" => PartOfSpeech::Interjection,
+            "num" => PartOfSpeech::Numeral,
+            "art" => PartOfSpeech::Article,
+            _ => return None,
+        })
+    }
+
+    /// 常用缩写，如 `n.`
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            PartOfSpeech::Noun => "n.",
+            PartOfSpeech::Verb => "v.",
+            PartOfSpeech::Adjective => "adj.",
+            PartOfSpeech::Adverb => "adv.",
+            PartOfSpeech::Pronoun => "pron.",
+            PartOfSpeech::Preposition => "prep.",
+            PartOfSpeech::Conjunction => "conj.",
+            PartOfSpeech::Interjection => "int.",
+            PartOfSpeech::Numeral => "num.",
+            PartOfSpeech::Article => "art.",
+        }
+    }
+}
+
+impl fmt::Display for PartOfSpeech {
+    /// 中文名称，如 `名词`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PartOfSpeech::Noun => "名词",
+            PartOfSpeech::Verb => "动词",
+            PartOfSpeech::Adjective => "形容词",
+            PartOfSpeech::Adverb => "副词",
+            PartOfSpeech::Pronoun => "代词",
+            PartOfSpeech::Preposition => "介词",
+            PartOfSpeech::Conjunction => "连词",
+            PartOfSpeech::Interjection => "感叹词",
+            PartOfSpeech::Numeral => "数词",
+            PartOfSpeech::Article => "冠词",
+        })
+    }
+}
+
+/// 读取释义中的词性缩写，按出现顺序去重，如 `n. 能力；v. 使能够` → 名词、动词
+pub fn parse_meaning(meaning: &str) -> Vec<PartOfSpeech> {
+    let mut tags = Vec::new();
+    for (dot, _) in meaning.match_indices('.') {
+        let before = &meaning[..dot];
+        // 句点前连续的 ASCII 字母，前面是开头或其他字符（空格、分号、中文等）
+        let start = before.trim_end_matches(|c: char| c.is_ascii_alphabetic()).len();
+        if let Some(pos) = PartOfSpeech::from_abbreviation(&before[start..]) {
+            if !tags.contains(&pos) {
+                tags.push(pos);
+            }
+        }
+    }
+    tags
+}
+
+/// 按后缀推测词性，无法判断时返回 None
+pub fn guess_from_suffix(word: &str) -> Option<PartOfSpeech> {
+    const SUFFIXES: [(&[&str], PartOfSpeech); 4] = [
+        (&["tion", "sion", "ment", "ness", "ity", "ism", "ship", "hood", "ance", "ence"], PartOfSpeech::Noun),
+        (&["ous", "ful", "less", "able", "ible", "ive", "ical", "ic"], PartOfSpeech::Adjective),
+        (&["ize", "ise", "ify"], PartOfSpeech::Verb),
+        (&["ly"], PartOfSpeech::Adverb),
+    ];
+    let word = word.trim().to_lowercase();
+    if word.chars().count() < 5 || word.contains(' ') {
+        return None;
+    }
+    SUFFIXES
+        .iter()
+        .find(|(suffixes, _)| suffixes.iter().any(|s| word.ends_with(s)))
+        .map(|(_, pos)| *pos)
+}
+
+/// 为没有词性的单词标注词性：先读取释义中的缩写，`guess` 为 true 时再按后缀推测；
+/// 返回标注的数量
+pub fn fill_pos(words: &mut [Word], guess: bool) -> usize {
+    let mut filled = 0;
+    for word in words.iter_mut().filter(|w| w.pos.is_empty()) {
+        word.pos = parse_meaning(&word.meaning);
+        if word.pos.is_empty() && guess {
+            word.pos.extend(guess_from_suffix(&word.word));
+        }
+        filled += usize::from(!word.pos.is_empty());
+    }
+    filled
+}
+
+/// 按第一个词性分组，组按词性顺序排列，没有词性的单词在最后一组
+pub fn group_by_pos(words: &[Word]) -> Vec<(Option<PartOfSpeech>, Vec<&Word>)> {
+    let mut groups: Vec<(Option<PartOfSpeech>, Vec<&Word>)> = Vec::new();
+    for word in words {
+        let key = word.pos.first().copied();
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, group)) => group.push(word),
+            None => groups.push((key, vec![word])),
+        }
+    }
+    // None 排在所有词性之后
+    groups.sort_by_key(|(key, _)| (key.is_none(), *key));
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_meaning_and_guess() {
+        use PartOfSpeech::*;
+        assert_eq!(parse_meaning("n. 能力；vt.使能够 v. 启用"), [Noun, Verb]);
+        assert_eq!(parse_meaning("adj.&adv. 很快"), [Adjective, Adverb]);
+        assert_eq!(parse_meaning("放弃"), []);
+        assert_eq!(parse_meaning("e.g. 例如"), []);
+
+        assert_eq!(guess_from_suffix("information"), Some(Noun));
+        assert_eq!(guess_from_suffix("quickly"), Some(Adverb));
+        assert_eq!(guess_from_suffix("run"), None);
+    }
+}
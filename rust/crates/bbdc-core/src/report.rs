@@ -0,0 +1,414 @@
+//! 报告模块
+//!
+//! 将一次处理的元数据、提取和核对结果整理为 Markdown 或 HTML 报告。设置链接模板后，
+//! 报告中的单词渲染为指向不背单词或在线词典的链接
+
+use crate::humanize::{format_duration, Locale};
+use crate::word_extractor::SkippedRow;
+use crate::{confusables, sanitize, BookMetadata, CheckResult, ConfusablePair, CorrectionRecord, ExtractResult, PageIndex, Result};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// 默认的单词链接模板（有道词典），`{word}` 替换为 URL 编码后的单词
+pub const DEFAULT_LINK_TEMPLATE: &str = "https://dict.youdao.com/result?word={word}&lang=en";
+
+/// 按模板生成单词链接，`{word}` 替换为 URL 编码后的单词
+pub fn word_url(template: &str, word: &str) -> String {
+    let mut encoded = String::new();
+    for byte in word.trim().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    template.replace("{word}", &encoded)
+}
+
+/// 报告中原文截图的目录，位于报告文件旁边
+pub const PAGE_ASSETS_DIR: &str = "report_pages";
+
+/// 处理报告
+pub struct Report<'a> {
+    metadata: &'a BookMetadata,
+    extract: Option<&'a ExtractResult>,
+    check: Option<&'a CheckResult>,
+    confusables: &'a [ConfusablePair],
+    corrections: &'a [CorrectionRecord],
+    link_template: Option<&'a str>,
+    elapsed: Option<Duration>,
+    pages: Option<&'a PageIndex>,
+}
+
+impl<'a> Report<'a> {
+    /// 创建报告
+    pub fn new(metadata: &'a BookMetadata) -> Self {
+        Self {
+            metadata,
+            extract: None,
+            check: None,
+            confusables: &[],
+            corrections: &[],
+            link_template: None,
+            elapsed: None,
+            pages: None,
+        }
+    }
+
+    /// 附加提取结果
+    pub fn with_extract(mut self, extract: &'a ExtractResult) -> Self {
+        self.extract = Some(extract);
+        self
+    }
+
+    /// 附加核对结果
+    pub fn with_check(mut self, check: &'a CheckResult) -> Self {
+        self.check = Some(check);
+        self
+    }
+
+    /// 附加易混词
+    pub fn with_confusables(mut self, pairs: &'a [ConfusablePair]) -> Self {
+        self.confusables = pairs;
+        self
+    }
+
+    /// 附加 LLM 更正建议
+    pub fn with_corrections(mut self, corrections: &'a [CorrectionRecord]) -> Self {
+        self.corrections = corrections;
+        self
+    }
+
+    /// 把单词渲染为链接，模板中的 `{word}` 替换为单词，见 [`DEFAULT_LINK_TEMPLATE`]
+    pub fn with_link_template(mut self, template: &'a str) -> Self {
+        self.link_template = Some(template);
+        self
+    }
+
+    /// 附加处理用时
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = Some(elapsed);
+        self
+    }
+
+    /// 附加 PDF 页面索引：识别失败和被更正的单词旁显示所在页的表格截图，
+    /// 保存时截图复制到报告旁的 [`PAGE_ASSETS_DIR`] 目录
+    pub fn with_pages(mut self, pages: &'a PageIndex) -> Self {
+        self.pages = Some(pages);
+        self
+    }
+
+    /// 可疑单词（识别失败或被 LLM 更正）及其所在页码和截图
+    fn suspect_pages(&self) -> Vec<(&str, usize, Vec<&Path>)> {
+        let Some(pages) = self.pages else { return Vec::new() };
+        let unrecognized = self.check.map(|c| c.unrecognized_words.as_slice()).unwrap_or_default();
+        let originals = self.corrections.iter().map(|r| r.original.as_str());
+        let mut seen = std::collections::HashSet::new();
+        unrecognized
+            .iter()
+            .map(String::as_str)
+            .chain(originals)
+            .filter(|word| seen.insert(*word))
+            .filter_map(|word| {
+                let recorded = self.extract.and_then(|e| e.words.iter().find(|w| w.word == word)).and_then(|w| w.page);
+                let page = recorded.or_else(|| pages.page_of(word))?;
+                Some((word, page, pages.images(page)))
+            })
+            .collect()
+    }
+
+    /// 单词在原文中的位置（批量提取时含源文件），见 [`Word::location`](crate::Word::location)
+    fn location(&self, word: &str) -> Option<String> {
+        self.extract?.words.iter().find(|w| w.word == word)?.location(None)
+    }
+
+    /// 单词的显示文本：设置了链接模板时为 Markdown 链接
+    fn render_word(&self, word: &str) -> String {
+        match self.link_template {
+            Some(template) => format!("[{}]({})", word, word_url(template, word)),
+            None => word.to_string(),
+        }
+    }
+
+    /// 生成 Markdown 文本
+    pub fn to_markdown(&self) -> String {
+        let mut content = String::new();
+
+        // 报告头
+        content.push_str(&format!("# {}\n\n", self.metadata.title));
+        if !self.metadata.description.is_empty() {
+            content.push_str(&format!("{}\n\n", self.metadata.description));
+        }
+        if !self.metadata.author.is_empty() {
+            content.push_str(&format!("- 作者: {}\n", self.metadata.author));
+        }
+        if !self.metadata.tags.is_empty() {
+            content.push_str(&format!("- 标签: {}\n", self.metadata.tags.join(", ")));
+        }
+
+        if let Some(extract) = self.extract {
+            content.push_str("\n## 提取结果\n\n");
+            content.push_str(&format!("- 单词数: {}\n", extract.total_words));
+            content.push_str(&format!("- 短语数: {}\n", extract.total_phrases));
+            if let Some(elapsed) = self.elapsed {
+                content.push_str(&format!("- 用时: {}\n", format_duration(elapsed, Locale::current())));
+            }
+
+            let per_file = extract.words_per_file();
+            if !per_file.is_empty() {
+                content.push_str("\n### 来源文件\n\n");
+                for (file, count) in per_file {
+                    content.push_str(&format!("- {}: {} 个单词\n", file, count));
+                }
+            }
+        }
+
+        if let Some(check) = self.check {
+            content.push_str("\n## 核对结果\n\n");
+            content.push_str(&format!("- 总单词数: {}\n", check.total_count));
+            content.push_str(&format!("- 识别成功: {}\n", check.recognized_count));
+            content.push_str(&format!("- 识别失败: {}\n", check.unrecognized_count));
+            if check.total_count > 0 {
+                let rate = check.recognized_count as f64 / check.total_count as f64 * 100.0;
+                content.push_str(&format!("- 识别成功率: {:.1}%\n", rate));
+            }
+
+            let per_file = self.extract.map(|e| check.by_source(e.word_files())).unwrap_or_default();
+            if !per_file.is_empty() {
+                content.push_str("\n### 各文件识别率\n\n");
+                for group in &per_file {
+                    content.push_str(&format!(
+                        "- {}: {:.1}%（{}/{}）\n",
+                        group.source,
+                        group.recognition_rate() * 100.0,
+                        group.recognized_count,
+                        group.total_count
+                    ));
+                }
+            }
+
+            if !check.unrecognized_words.is_empty() {
+                content.push_str("\n### 识别失败的单词\n\n");
+                for word in &check.unrecognized_words {
+                    match self.location(word) {
+                        Some(location) => content.push_str(&format!("- {}（{}）\n", self.render_word(word), location)),
+                        None => content.push_str(&format!("- {}\n", self.render_word(word))),
+                    }
+                }
+            }
+        }
+
+        if !self.corrections.is_empty() {
+            content.push_str("\n### LLM 更正建议\n\n");
+            for record in self.corrections {
+                content.push_str(&format!(
+                    "- {} → {}（{}）\n",
+                    self.render_word(&record.original),
+                    self.render_word(&record.corrected),
+                    record.confidence
+                ));
+            }
+        }
+
+        let suspects = self.suspect_pages();
+        if !suspects.is_empty() {
+            content.push_str("\n### 原文截图\n\n");
+            for (word, page, images) in suspects {
+                content.push_str(&format!("- {}（第 {} 页）\n", self.render_word(word), page));
+                for image in images {
+                    let name = image.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+                    content.push_str(&format!("\n![第 {} 页]({}/{})\n", page, PAGE_ASSETS_DIR, name));
+                }
+            }
+        }
+
+        content.push_str(&confusables::markdown_section(self.confusables, |word| self.render_word(word)));
+        content.push_str(&sanitize::markdown_section(self.extract.map(|e| e.cleaned.as_slice()).unwrap_or_default()));
+        content.push_str(&skipped_section(self.extract.map(|e| e.skipped.as_slice()).unwrap_or_default()));
+
+        content
+    }
+
+    /// 生成 HTML 页面，内容与 Markdown 报告相同
+    pub fn to_html(&self) -> String {
+        let mut body = String::new();
+        let mut in_list = false;
+
+        for line in self.to_markdown().lines() {
+            let item = line.strip_prefix("- ");
+            if in_list && item.is_none() {
+                body.push_str("</ul>\n");
+                in_list = false;
+            }
+
+            if let Some(item) = item {
+                if !in_list {
+                    body.push_str("<ul>\n");
+                    in_list = true;
+                }
+                body.push_str(&format!("<li>{}</li>\n", inline_html(item)));
+            } else if let Some((alt, src)) = image(line) {
+                body.push_str(&format!("<p><img src=\"{}\" alt=\"{}\" loading=\"lazy\"></p>\n", escape_html(src), escape_html(alt)));
+            } else if let Some((level, heading)) = heading(line) {
+                body.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inline_html(heading)));
+            } else if !line.trim().is_empty() {
+                body.push_str(&format!("<p>{}</p>\n", inline_html(line)));
+            }
+        }
+        if in_list {
+            body.push_str("</ul>\n");
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"zh-CN\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+            escape_html(&self.metadata.title),
+            HTML_STYLE,
+            body
+        )
+    }
+
+    /// 保存报告，扩展名为 `.html`/`.htm` 时保存为 HTML，否则为 Markdown
+    ///
+    /// 附加了页面索引时同时把截图复制到报告旁的 [`PAGE_ASSETS_DIR`] 目录
+    pub fn save<P: AsRef<Path>>(&self, output_path: P) -> Result<()> {
+        let output_path = output_path.as_ref();
+        let suspects = self.suspect_pages();
+        if suspects.iter().any(|(_, _, images)| !images.is_empty()) {
+            let assets = output_path.parent().unwrap_or_else(|| Path::new(".")).join(PAGE_ASSETS_DIR);
+            fs::create_dir_all(&assets)?;
+            for image in suspects.iter().flat_map(|(_, _, images)| images) {
+                if let Some(name) = image.file_name() {
+                    if let Err(e) = fs::copy(image, assets.join(name)) {
+                        log::warn!("⚠️  无法复制截图 {:?}: {}", image, e);
+                    }
+                }
+            }
+        }
+        let is_html = output_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| e.eq_ignore_ascii_case("html") || e.eq_ignore_ascii_case("htm"));
+        let content = if is_html { self.to_html() } else { self.to_markdown() };
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+}
+
+const HTML_STYLE: &str = "body{font-family:-apple-system,'PingFang SC','Microsoft YaHei',sans-serif;max-width:48rem;margin:2rem auto;padding:0 1rem;line-height:1.6}a{color:#1a73e8;text-decoration:none}a:hover{text-decoration:underline}img{max-width:100%;max-height:16rem;border:1px solid #ddd}";
+
+/// 解析单独成行的 Markdown 图片 `![说明](路径)`，返回说明和路径
+/// 提取时跳过的可疑表格行，没有时返回空字符串
+fn skipped_section(skipped: &[SkippedRow]) -> String {
+    if skipped.is_empty() {
+        return String::new();
+    }
+
+    let mut content = format!("\n## 可疑的表格行（{}）\n\n这些行没有提取出单词，请对照原文检查：\n\n", skipped.len());
+    for row in skipped {
+        let source = row.source.as_deref().map(|s| format!("，{}", s)).unwrap_or_default();
+        content.push_str(&format!("- `{}`（{}{}）\n", row.row, row.reason, source));
+    }
+    content
+}
+
+fn image(line: &str) -> Option<(&str, &str)> {
+    let (alt, src) = line.trim().strip_prefix("![")?.strip_suffix(')')?.split_once("](")?;
+    Some((alt, src))
+}
+
+/// 解析 Markdown 标题行，返回级别和标题文本
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.len() - line.trim_start_matches('#').len();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then_some((level, text))
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// 转义文本并把 `[文本](链接)` 转换为超链接
+fn inline_html(text: &str) -> String {
+    let mut html = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        let link = rest[start..].find("](").and_then(|mid| {
+            let end = rest[start + mid..].find(')')?;
+            Some((mid, start + mid + end))
+        });
+        let Some((mid, end)) = link else { break };
+        html.push_str(&escape_html(&rest[..start]));
+        html.push_str(&format!(
+            "<a href=\"{}\" target=\"_blank\">{}</a>",
+            escape_html(&rest[start + mid + 2..end]),
+            escape_html(&rest[start + 1..start + mid])
+        ));
+        rest = &rest[end + 1..];
+    }
+    html.push_str(&escape_html(rest));
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_header() {
+        let metadata = BookMetadata {
+            title: "Unit 1".to_string(),
+            description: "第一单元".to_string(),
+            tags: vec!["高考".to_string()],
+            author: "张老师".to_string(),
+        };
+
+        let markdown = Report::new(&metadata).to_markdown();
+
+        assert!(markdown.starts_with("# Unit 1\n\n第一单元\n"));
+        assert!(markdown.contains("- 作者: 张老师"));
+        assert!(markdown.contains("- 标签: 高考"));
+    }
+
+    #[test]
+    fn test_word_links() {
+        let metadata = BookMetadata {
+            title: "Unit 1".to_string(),
+            description: String::new(),
+            tags: Vec::new(),
+            author: String::new(),
+        };
+        let corrections = [CorrectionRecord::new("recieve", "receive", crate::Confidence::High)];
+
+        let markdown = Report::new(&metadata)
+            .with_corrections(&corrections)
+            .with_link_template("https://example.com/{word}")
+            .to_markdown();
+
+        assert!(markdown.contains("- [recieve](https://example.com/recieve) → [receive](https://example.com/receive)"));
+        assert_eq!(word_url(DEFAULT_LINK_TEMPLATE, "take off"), "https://dict.youdao.com/result?word=take%20off&lang=en");
+
+        let html = Report::new(&metadata).with_corrections(&corrections).with_link_template("https://example.com/{word}").to_html();
+        assert!(html.contains("<h3>LLM 更正建议</h3>\n<ul>\n<li><a href=\"https://example.com/recieve\" target=\"_blank\">recieve</a> → "));
+
+        let pages = PageIndex::parse(r#"[{"type": "table", "img_path": "images/t1.jpg", "table_body": "<td>recieve</td>", "page_idx": 0}]"#, Path::new("out")).unwrap();
+        let html = Report::new(&metadata).with_corrections(&corrections).with_pages(&pages).to_html();
+        assert!(html.contains("<li>recieve（第 1 页）</li>\n</ul>\n<p><img src=\"report_pages/t1.jpg\" alt=\"第 1 页\" loading=\"lazy\"></p>"));
+    }
+
+    #[test]
+    fn test_per_file_breakdown() {
+        let metadata = BookMetadata { title: "Book".to_string(), description: String::new(), tags: Vec::new(), author: String::new() };
+        let rows = [["1", "abandon", "放弃"], ["2", "abroed", "在国外"], ["3", "ability", "能力"]].map(|row| row.map(str::to_string).to_vec());
+        let mut extract = crate::WordExtractor::new(true, false).extract_from_rows(rows);
+        for (word, file) in extract.words.iter_mut().zip(["unit1.md", "unit2.md", "unit2.md"]) {
+            word.source = Some(file.to_string());
+        }
+        extract.files = vec!["unit1.md".to_string(), "unit2.md".to_string()];
+        let check = CheckResult::from_lists(["abandon", "ability"], ["abroed"]);
+
+        let markdown = Report::new(&metadata).with_extract(&extract).with_check(&check).to_markdown();
+        assert!(markdown.contains("### 来源文件\n\n- unit1.md: 1 个单词\n- unit2.md: 2 个单词\n"));
+        assert!(markdown.contains("### 各文件识别率\n\n- unit2.md: 50.0%（1/2）\n- unit1.md: 100.0%（1/1）\n"));
+        assert!(markdown.contains("- abroed（unit2.md）"));
+    }
+}
@@ -0,0 +1,164 @@
+//! 单词清理模块
+//!
+//! OCR 和排版残留是单词识别失败的主要原因：`abandon,` 带着末尾标点，`ability1`、`able*`
+//! 带着脚注标记，`abroad (adv.)` 带着括号注释。提取表格时按 [`WordPolicy`](crate::word_extractor::WordPolicy)
+//! 的 `sanitize` 规则清理，清理记录保存在提取结果中，供报告列出
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 清理掉的残留类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Artifact {
+    /// 首尾的标点和引号
+    Punctuation,
+    /// 末尾的脚注标记：数字、上标数字、`*`、`†` 等
+    Footnote,
+    /// 括号中的注释，如 `(adv.)`、`[1]`、`（美）`
+    Annotation,
+}
+
+impl fmt::Display for Artifact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Artifact::Punctuation => "标点",
+            Artifact::Footnote => "脚注标记",
+            Artifact::Annotation => "括号注释",
+        })
+    }
+}
+
+/// 一次清理：原文、清理后的文本和清理掉的残留
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Cleanup {
+    pub original: String,
+    pub cleaned: String,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// 末尾的标点（`.` 单独处理，`e.g.` 这类缩写中的句点保留）
+const TRAILING_PUNCTUATION: [char; 17] = [',', ';', ':', '!', '?', '，', '。', '；', '：', '！', '？', '、', '"', '“', '”', '…', '·'];
+
+/// 开头的引号和列表符号
+const LEADING_PUNCTUATION: [char; 6] = ['"', '“', '”', '‘', '·', '•'];
+
+/// 脚注符号和上标数字
+const FOOTNOTE_MARKS: [char; 14] = ['*', '†', '‡', '§', '⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// 去掉成对括号中的注释；括号不成对或去掉后不含字母时返回 None
+fn strip_annotations(word: &str) -> Option<String> {
+    let mut stripped = String::new();
+    let mut depth = 0usize;
+    for c in word.chars() {
+        match c {
+            '(' | '[' | '{' | '（' | '【' => depth += 1,
+            ')' | ']' | '}' | '）' | '】' => depth = depth.checked_sub(1)?,
+            _ if depth == 0 => stripped.push(c),
+            _ => {}
+        }
+    }
+    let stripped = stripped.split_whitespace().collect::<Vec<_>>().join(" ");
+    (depth == 0 && stripped.chars().any(crate::text::is_letter)).then_some(stripped)
+}
+
+/// 去掉末尾的一到两位数字脚注（`ability1`），前面至少要有三个字母且没有其他数字，
+/// 以免误伤 `mp3` 这类词
+fn strip_footnote_digits(word: &str) -> Option<&str> {
+    let stem = word.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = word.len() - stem.len();
+    let letters = stem.chars().filter(|c| c.is_alphabetic()).count();
+    let valid = (1..=2).contains(&digits)
+        && letters >= 3
+        && stem.ends_with(char::is_alphabetic)
+        && !stem.contains(|c: char| c.is_ascii_digit());
+    valid.then_some(stem)
+}
+
+/// 清理单词或短语，没有需要清理的内容时返回 None
+pub fn sanitize(word: &str) -> Option<Cleanup> {
+    let original = word.trim();
+    let mut artifacts = Vec::new();
+    let note = |artifact: Artifact, artifacts: &mut Vec<Artifact>| {
+        if !artifacts.contains(&artifact) {
+            artifacts.push(artifact);
+        }
+    };
+
+    let mut current = original.to_string();
+    if current.contains(['(', '[', '{', '（', '【']) {
+        if let Some(stripped) = strip_annotations(&current).filter(|s| *s != current) {
+            current = stripped;
+            note(Artifact::Annotation, &mut artifacts);
+        }
+    }
+
+    // 标点和脚注可能交替出现（`able*,`），反复清理直到不再变化
+    loop {
+        let before = current.len();
+        let trimmed = current.trim_start_matches(LEADING_PUNCTUATION).trim_end_matches(TRAILING_PUNCTUATION);
+        // 只有一个句点且在末尾时视为标点
+        let trimmed = match trimmed.strip_suffix('.') {
+            Some(rest) if !rest.contains('.') => rest,
+            _ => trimmed,
+        };
+        if trimmed.len() != current.len() {
+            note(Artifact::Punctuation, &mut artifacts);
+            current = trimmed.trim().to_string();
+        }
+
+        let trimmed = current.trim_end_matches(FOOTNOTE_MARKS);
+        let trimmed = strip_footnote_digits(trimmed).unwrap_or(trimmed);
+        if trimmed.len() != current.len() {
+            note(Artifact::Footnote, &mut artifacts);
+            current = trimmed.trim().to_string();
+        }
+
+        if current.len() == before {
+            break;
+        }
+    }
+
+    (current != original && current.chars().any(crate::text::is_letter)).then(|| Cleanup {
+        original: original.to_string(),
+        cleaned: current,
+        artifacts,
+    })
+}
+
+/// 清理记录的 Markdown 段落，没有记录时返回空字符串
+pub fn markdown_section(cleanups: &[Cleanup]) -> String {
+    if cleanups.is_empty() {
+        return String::new();
+    }
+
+    let mut content = format!("\n## 清理的单词（{}）\n\n", cleanups.len());
+    for cleanup in cleanups {
+        let artifacts: Vec<String> = cleanup.artifacts.iter().map(|a| a.to_string()).collect();
+        content.push_str(&format!("- {} → {}（{}）\n", cleanup.original, cleanup.cleaned, artifacts.join("、")));
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize() {
+        let cleaned = |word: &str| sanitize(word).map(|c| c.cleaned);
+        assert_eq!(cleaned("abandon,").as_deref(), Some("abandon"));
+        assert_eq!(cleaned("ability1").as_deref(), Some("ability"));
+        assert_eq!(cleaned("“able*,”").as_deref(), Some("able"));
+        assert_eq!(cleaned("abroad (adv.)").as_deref(), Some("abroad"));
+        assert_eq!(cleaned("give up [sth]²").as_deref(), Some("give up"));
+        for unchanged in ["mp3", "e.g.", "well-known", "it's", "(1)", "a (b"] {
+            assert_eq!(sanitize(unchanged), None, "{}", unchanged);
+        }
+
+        let cleanup = sanitize("able³ (adj.).").unwrap();
+        assert_eq!(cleanup.artifacts, [Artifact::Annotation, Artifact::Punctuation, Artifact::Footnote]);
+    }
+}
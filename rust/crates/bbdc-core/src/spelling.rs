@@ -0,0 +1,256 @@
+//! 英美拼写转换模块
+//!
+//! 把英式拼写统一为美式（`colour` → `color`、`analyse` → `analyze`），或反过来，
+//! 使词书与不背单词使用的词典一致。内置常见的拼写差异：-our/-or、-ise/-ize、-yse/-yze、
+//! -re/-er、双写 l，以及逐词对应的特例；可以用映射表补充
+
+use crate::{text, ExtractResult, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// 目标拼写
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Spelling {
+    /// 美式拼写
+    Us,
+    /// 英式拼写
+    Uk,
+}
+
+impl std::fmt::Display for Spelling {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Spelling::Us => "美式",
+            Spelling::Uk => "英式",
+        })
+    }
+}
+
+/// 一类有规律的拼写差异：词干 + 英式/美式中缀 + 后缀
+struct Family {
+    uk: &'static str,
+    us: &'static str,
+    stems: &'static [&'static str],
+    suffixes: &'static [&'static str],
+}
+
+/// 有规律的拼写差异，只转换列出的词干，`advertise`、`four` 这类两边相同的词不受影响
+const FAMILIES: [Family; 5] = [
+    Family {
+        uk: "our",
+        us: "or",
+        stems: &[
+            "arm", "behavi", "col", "endeav", "fav", "flav", "harb", "hon", "hum", "lab", "neighb", "od", "parl",
+            "rum", "sav", "splend", "tum", "val", "vap", "vig",
+        ],
+        suffixes: &["", "s", "ed", "ing", "ful", "less", "ite", "ites", "able", "ably", "hood", "er", "ers", "ist", "ists", "y"],
+    },
+    Family {
+        uk: "is",
+        us: "iz",
+        stems: &[
+            "apolog", "author", "capital", "categor", "central", "character", "civil", "critic", "emphas", "final",
+            "general", "global", "industrial", "legal", "local", "maxim", "memor", "minim", "modern", "normal",
+            "optim", "organ", "prior", "real", "recogn", "special", "standard", "summar", "symbol", "sympath",
+            "util", "visual",
+        ],
+        suffixes: &["e", "es", "ed", "ing", "ation", "ations", "er", "ers", "able"],
+    },
+    Family {
+        uk: "ys",
+        us: "yz",
+        stems: &["anal", "catal", "paral"],
+        suffixes: &["e", "es", "ed", "ing", "er", "ers"],
+    },
+    Family {
+        uk: "re",
+        us: "er",
+        stems: &["calib", "cent", "fib", "lit", "lust", "meag", "met", "sab", "somb", "spect", "theat"],
+        suffixes: &["", "s"],
+    },
+    Family {
+        uk: "ll",
+        us: "l",
+        stems: &["cance", "channe", "counse", "dia", "fue", "jewe", "labe", "leve", "marve", "mode", "quarre", "signa", "trave", "tunne"],
+        suffixes: &["ed", "ing", "er", "ers", "or", "ors", "ous"],
+    },
+];
+
+/// 逐词对应的英式、美式拼写
+const WORDS: [(&str, &str); 28] = [
+    ("acknowledgement", "acknowledgment"), ("aeroplane", "airplane"), ("ageing", "aging"), ("aluminium", "aluminum"),
+    ("anaemia", "anemia"), ("analogue", "analog"), ("catalogue", "catalog"), ("cosy", "cozy"), ("defence", "defense"),
+    ("encyclopaedia", "encyclopedia"), ("enrol", "enroll"), ("foetus", "fetus"), ("fulfil", "fulfill"), ("grey", "gray"),
+    ("instalment", "installment"), ("jewellery", "jewelry"), ("judgement", "judgment"), ("licence", "license"),
+    ("manoeuvre", "maneuver"), ("mould", "mold"), ("moustache", "mustache"), ("offence", "offense"),
+    ("paediatric", "pediatric"), ("plough", "plow"), ("pretence", "pretense"), ("pyjamas", "pajamas"),
+    ("sceptical", "skeptical"), ("skilful", "skillful"),
+];
+
+/// 英美拼写转换器
+#[derive(Debug, Clone)]
+pub struct SpellingNormalizer {
+    target: Spelling,
+    /// 映射表中的拼写（小写）→ 目标拼写
+    mapping: HashMap<String, String>,
+}
+
+impl SpellingNormalizer {
+    /// 使用内置规则转换为 `target`
+    pub fn new(target: Spelling) -> Self {
+        Self { target, mapping: HashMap::new() }
+    }
+
+    /// 读取映射表并合并到内置规则（优先于内置规则）
+    ///
+    /// 每行为 `英式 -> 美式`（如 `programme -> program`），`#` 开头的行为注释；
+    /// 目标为英式时反向使用
+    pub fn with_mapping(mut self, content: &str) -> Self {
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((uk, us)) = line.split_once("->") else { continue };
+            let (uk, us) = (text::normalize_key(uk), text::normalize_key(us));
+            if uk.is_empty() || us.is_empty() {
+                continue;
+            }
+            match self.target {
+                Spelling::Us => self.mapping.insert(uk, us),
+                Spelling::Uk => self.mapping.insert(us, uk),
+            };
+        }
+        self
+    }
+
+    /// 读取映射表文件，见 [`with_mapping`](Self::with_mapping)
+    pub fn load_mapping<P: AsRef<Path>>(self, path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let normalizer = self.with_mapping(&content);
+        log::info!("拼写映射表包含 {} 个单词", normalizer.mapping.len());
+        Ok(normalizer)
+    }
+
+    /// 转换为目标拼写，已经是目标拼写或不在规则中时返回 None；首字母大写时保持大写
+    pub fn convert(&self, word: &str) -> Option<String> {
+        let key = text::normalize_key(word);
+        let converted = self.mapping.get(&key).cloned().or_else(|| self.convert_builtin(&key))?;
+        if converted == key {
+            return None;
+        }
+        let mut chars = word.trim().chars();
+        Some(match chars.next() {
+            Some(first) if first.is_uppercase() && chars.all(|c| !c.is_uppercase()) => {
+                let mut rest = converted.chars();
+                rest.next().map(|c| c.to_uppercase().chain(rest).collect()).unwrap_or_default()
+            }
+            _ => converted,
+        })
+    }
+
+    fn convert_builtin(&self, key: &str) -> Option<String> {
+        // (原拼写, 目标拼写)
+        let orient = |uk: &'static str, us: &'static str| match self.target {
+            Spelling::Us => (uk, us),
+            Spelling::Uk => (us, uk),
+        };
+        if let Some((_, target)) = WORDS.iter().map(|&(uk, us)| orient(uk, us)).find(|(source, _)| *source == key) {
+            return Some(target.to_string());
+        }
+        for family in &FAMILIES {
+            let (source, target) = orient(family.uk, family.us);
+            for stem in family.stems {
+                let Some(rest) = key.strip_prefix(stem).and_then(|rest| rest.strip_prefix(source)) else { continue };
+                if family.suffixes.contains(&rest) {
+                    return Some(format!("{}{}{}", stem, target, rest));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl ExtractResult {
+    /// 把单词统一为目标拼写，返回转换的单词数
+    ///
+    /// 转换后与其他单词重复时（同一本书中同时出现 `colour` 和 `color`）合并，
+    /// 保留先出现的单词并追加不同的释义；原本就重复的单词不合并。短语不变
+    pub fn normalize_spelling(&mut self, normalizer: &SpellingNormalizer) -> usize {
+        let mut converted: HashSet<usize> = HashSet::new();
+        for (i, word) in self.words.iter_mut().enumerate() {
+            if let Some(spelling) = normalizer.convert(&word.word) {
+                log::debug!("拼写转换: {} → {}", word.word, spelling);
+                word.word = spelling;
+                converted.insert(i);
+            }
+        }
+
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut merged: HashSet<usize> = HashSet::new();
+        for i in 0..self.words.len() {
+            let key = text::normalize_key(&self.words[i].word);
+            match seen.get(&key) {
+                Some(&first) if converted.contains(&i) || converted.contains(&first) => {
+                    // 按分号拆开，只追加没有出现过的义项
+                    let meaning = self.words[i].meaning.clone();
+                    let kept = &mut self.words[first];
+                    for sense in meaning.split(['；', ';']).map(str::trim).filter(|s| !s.is_empty()) {
+                        if !kept.meaning.split(['；', ';']).any(|s| s.trim() == sense) {
+                            if !kept.meaning.is_empty() {
+                                kept.meaning.push('；');
+                            }
+                            kept.meaning.push_str(sense);
+                        }
+                    }
+                    merged.insert(i);
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(key, i);
+                }
+            }
+        }
+
+        if !merged.is_empty() {
+            self.words = std::mem::take(&mut self.words)
+                .into_iter()
+                .enumerate()
+                .filter(|(i, _)| !merged.contains(i))
+                .map(|(_, word)| word)
+                .collect();
+            self.total_words = self.words.len();
+        }
+        converted.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert_spelling() {
+        let us = SpellingNormalizer::new(Spelling::Us);
+        for (uk, expected) in [("colour", "color"), ("Favourite", "Favorite"), ("analysed", "analyzed"), ("organisation", "organization"), ("centres", "centers"), ("travelling", "traveling"), ("grey", "gray")] {
+            assert_eq!(us.convert(uk).as_deref(), Some(expected), "{}", uk);
+        }
+        for unchanged in ["color", "four", "advertise", "analysis", "hour"] {
+            assert_eq!(us.convert(unchanged), None, "{}", unchanged);
+        }
+
+        let uk = SpellingNormalizer::new(Spelling::Uk).with_mapping("# 注释\nprogramme -> program\n");
+        assert_eq!(uk.convert("realize").as_deref(), Some("realise"));
+        assert_eq!(uk.convert("modeled").as_deref(), Some("modelled"));
+        assert_eq!(uk.convert("program").as_deref(), Some("programme"));
+
+        let rows = [["1", "colour", "颜色"], ["2", "color", "颜色；着色"], ["3", "grey", "灰色"]].map(|row| row.map(str::to_string).to_vec());
+        let mut result = crate::WordExtractor::new(false, false).extract_from_rows(rows);
+        assert_eq!(result.normalize_spelling(&us), 2);
+        assert_eq!(result.words.len(), 2);
+        assert_eq!(result.words[0].meaning, "颜色；着色");
+    }
+}
@@ -0,0 +1,105 @@
+//! 字幕解析模块
+//!
+//! 读取 SRT/VTT 字幕，去掉序号、时间轴、注释块和格式标签，只保留对白文本
+
+use crate::text;
+
+/// 一行对白
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogueLine {
+    /// 在字幕文件中的行号（从 1 开始）
+    pub line_number: usize,
+    pub text: String,
+}
+
+/// 提取字幕中的对白行，同时支持 SRT 和 WebVTT
+pub fn dialogue_lines(content: &str) -> Vec<DialogueLine> {
+    let content = content.trim_start_matches('\u{feff}');
+    let lines: Vec<&str> = content.lines().map(str::trim).collect();
+    let mut dialogue = Vec::new();
+    // WebVTT 文件头以及 NOTE/STYLE/REGION 块一直持续到空行
+    let mut in_block = lines.first().is_some_and(|line| line.starts_with("WEBVTT"));
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            in_block = false;
+            continue;
+        }
+        if in_block {
+            continue;
+        }
+        if ["NOTE", "STYLE", "REGION"].iter().any(|kw| line.split_whitespace().next() == Some(kw)) {
+            in_block = true;
+            continue;
+        }
+        // 时间轴行，以及紧接在时间轴前的序号或 VTT 提示标识
+        if line.contains("-->") || lines.get(i + 1).is_some_and(|next| next.contains("-->")) {
+            continue;
+        }
+
+        let text = clean_line(line);
+        if !text.is_empty() {
+            dialogue.push(DialogueLine { line_number: i + 1, text });
+        }
+    }
+
+    dialogue
+}
+
+/// 去掉格式标签、音效说明和说话人前缀
+fn clean_line(line: &str) -> String {
+    let mut text = String::with_capacity(line.len());
+    let mut closing = None;
+    for c in line.chars() {
+        match closing {
+            Some(end) if c == end => closing = None,
+            Some(_) => {}
+            None => match c {
+                '<' => closing = Some('>'),
+                '{' => closing = Some('}'),
+                '[' => closing = Some(']'),
+                _ => text.push(c),
+            },
+        }
+    }
+
+    let text = text
+        .replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&nbsp;", " ");
+    let text = text.trim().trim_start_matches('-').trim();
+
+    // 全大写的说话人前缀，如 `JOHN:`
+    match text.split_once(':') {
+        Some((speaker, rest))
+            if text::tokenize(speaker).next().is_some()
+                && !speaker.chars().any(|c| c.is_lowercase()) =>
+        {
+            rest.trim().to_string()
+        }
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_srt_and_vtt() {
+        let srt = "\u{feff}1\n00:00:01,000 --> 00:00:02,500\n<i>Hello there,</i> friend!\n\n2\n00:00:03,000 --> 00:00:04,000\n[MUSIC PLAYING]\n- JOHN: Don't move.\n";
+        let lines = dialogue_lines(srt);
+        assert_eq!(
+            lines,
+            [
+                DialogueLine { line_number: 3, text: "Hello there, friend!".to_string() },
+                DialogueLine { line_number: 8, text: "Don't move.".to_string() },
+            ]
+        );
+
+        let vtt = "WEBVTT\nKind: captions\n\nNOTE a comment\nspanning lines\n\nintro\n00:01.000 --> 00:02.000 align:start\n<v Roger>We &amp; {\\an8}they\n";
+        let texts: Vec<String> = dialogue_lines(vtt).into_iter().map(|l| l.text).collect();
+        assert_eq!(texts, ["We & they"]);
+    }
+}
@@ -0,0 +1,230 @@
+//! 文本处理模块
+//!
+//! 纯文本的分词与规范化。只使用 `core` 和 `alloc`，不依赖任何第三方库，
+//! 可以直接复制到 `no_std` 环境（WASM 前端、插件等）中使用
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+
+/// 是否为单词字母（拉丁字母，含带重音的扩展字母）
+pub fn is_letter(c: char) -> bool {
+    c.is_ascii_alphabetic() || ('\u{00C0}'..='\u{024F}').contains(&c) && c != '×' && c != '÷'
+}
+
+/// 是否为可以出现在单词内部的连接符（撇号、连字符）
+fn is_joiner(c: char) -> bool {
+    matches!(c, '\'' | '\u{2019}' | '-')
+}
+
+/// 英文分词器
+///
+/// 按字母序列切分文本，保留单词内部的撇号和连字符（`don't`、`well-known`）
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let (start, _) = self.rest.char_indices().find(|&(_, c)| is_letter(c))?;
+        let rest = &self.rest[start..];
+
+        let mut end = rest.len();
+        let mut chars = rest.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            if is_letter(c) {
+                continue;
+            }
+            if is_joiner(c) && chars.peek().is_some_and(|&(_, next)| is_letter(next)) {
+                continue;
+            }
+            end = i;
+            break;
+        }
+
+        self.rest = &rest[end..];
+        Some(&rest[..end])
+    }
+}
+
+/// 对文本分词
+pub fn tokenize(text: &str) -> Tokens<'_> {
+    Tokens { rest: text }
+}
+
+/// 去掉首尾空白以及首尾的非字母字符
+pub fn trim_word(word: &str) -> &str {
+    word.trim_matches(|c: char| !is_letter(c))
+}
+
+/// 常见组合附加符号与可合成的基本字母、合成后的字母（按位置一一对应）
+const COMPOSITIONS: [(char, &str, &str); 8] = [
+    ('\u{0300}', "AEIOUaeiou", "ÀÈÌÒÙàèìòù"),
+    ('\u{0301}', "AEIOUYaeiouyCcNnSsZz", "ÁÉÍÓÚÝáéíóúýĆćŃńŚśŹź"),
+    ('\u{0302}', "AEIOUaeiou", "ÂÊÎÔÛâêîôû"),
+    ('\u{0303}', "ANOano", "ÃÑÕãñõ"),
+    ('\u{0308}', "AEIOUYaeiouy", "ÄËÏÖÜŸäëïöüÿ"),
+    ('\u{030a}', "Aa", "Åå"),
+    ('\u{0327}', "Cc", "Çç"),
+    ('\u{030c}', "CcSsZz", "ČčŠšŽž"),
+];
+
+/// 零宽字符与软连字符，OCR 和网页复制的文本中常见，直接去掉
+fn is_invisible(c: char) -> bool {
+    matches!(c, '\u{00ad}' | '\u{200b}'..='\u{200f}' | '\u{2060}' | '\u{feff}')
+}
+
+/// 连字（`ﬁ` 等）展开后的字母
+fn expand_ligature(c: char) -> Option<&'static str> {
+    Some(match c {
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        _ => return None,
+    })
+}
+
+/// 弯引号、撇号变体和不间断连字符替换为 ASCII 形式
+fn plain_punctuation(c: char) -> Option<char> {
+    match c {
+        '\u{2018}' | '\u{2019}' | '\u{201b}' | '\u{2032}' | '\u{02bc}' | '\u{ff07}' => Some('\''),
+        '\u{201c}' | '\u{201d}' | '\u{201f}' | '\u{2033}' => Some('"'),
+        '\u{2010}' | '\u{2011}' => Some('-'),
+        _ => None,
+    }
+}
+
+/// 把字母与后面的组合附加符号合成为一个字母（NFC 的常用部分），无法合成时返回 None
+fn compose(base: char, mark: char) -> Option<char> {
+    let (_, bases, composed) = COMPOSITIONS.iter().find(|(m, _, _)| *m == mark)?;
+    let index = bases.chars().position(|b| b == base)?;
+    composed.chars().nth(index)
+}
+
+/// Unicode 规范化：合成字母与组合附加符号（`e` + `◌́` → `é`），展开连字（`ﬁ` → `fi`），
+/// 弯引号替换为直引号，去掉零宽字符。文本不需要修改时不分配内存
+pub fn normalize_unicode(text: &str) -> Cow<'_, str> {
+    let needs_change = |c: char| {
+        !c.is_ascii()
+            && (is_invisible(c) || expand_ligature(c).is_some() || plain_punctuation(c).is_some() || COMPOSITIONS.iter().any(|(m, _, _)| *m == c))
+    };
+    if !text.chars().any(needs_change) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut normalized = String::with_capacity(text.len());
+    for c in text.chars() {
+        if is_invisible(c) {
+            continue;
+        }
+        if let Some(expanded) = expand_ligature(c) {
+            normalized.push_str(expanded);
+        } else if let Some(plain) = plain_punctuation(c) {
+            normalized.push(plain);
+        } else if let Some(composed) = normalized.chars().next_back().and_then(|base| compose(base, c)) {
+            normalized.pop();
+            normalized.push(composed);
+        } else {
+            normalized.push(c);
+        }
+    }
+    Cow::Owned(normalized)
+}
+
+/// 去重、比较用的规范形式（小写）
+pub fn normalize_key(word: &str) -> String {
+    word.trim().to_lowercase()
+}
+
+/// 是否为全部大写的缩写词（至少两个字母），如 `USA`、`DNA`、`U.K.`
+pub fn is_acronym(word: &str) -> bool {
+    let letters = word.chars().filter(|c| c.is_alphabetic());
+    letters.clone().count() >= 2 && letters.into_iter().all(char::is_uppercase)
+}
+
+/// 是否为短语（包含空格或连字符）
+pub fn is_phrase(text: &str) -> bool {
+    text.contains(' ') || text.contains('-')
+}
+
+/// 是否为纯数字序号
+pub fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_numeric())
+}
+
+/// 是否为句末标点
+fn is_sentence_end(c: char) -> bool {
+    matches!(c, '.' | '!' | '?' | '。' | '！' | '？')
+}
+
+/// 文本中第一个包含 `word`（小写形式，整词匹配，也匹配连字符词的一部分）的句子，去掉首尾空白
+pub fn sentence_containing<'a>(text: &'a str, word: &str) -> Option<&'a str> {
+    let matches = |token: &str| token.to_lowercase() == word || token.split('-').any(|part| part.to_lowercase() == word);
+    text.split_inclusive(is_sentence_end)
+        .find(|sentence| tokenize(sentence).any(matches))
+        .map(str::trim)
+}
+
+/// 常见的英语功能词（冠词、代词、介词、连词、助动词等），统计词频时跳过
+pub const STOP_WORDS: [&str; 100] = [
+    "a", "about", "after", "all", "also", "an", "and", "any", "are", "as",
+    "at", "be", "because", "been", "but", "by", "can", "could", "did", "do",
+    "does", "for", "from", "had", "has", "have", "he", "her", "him", "his",
+    "how", "i", "if", "in", "into", "is", "it", "its", "just", "like",
+    "may", "me", "might", "more", "most", "must", "my", "no", "not", "of",
+    "on", "one", "only", "or", "other", "our", "out", "over", "said", "she",
+    "should", "so", "some", "such", "than", "that", "the", "their", "them", "then",
+    "there", "these", "they", "this", "those", "through", "to", "up", "us", "very",
+    "was", "we", "were", "what", "when", "where", "which", "while", "who", "why",
+    "will", "with", "would", "you", "your", "am", "being", "each", "both", "own",
+];
+
+/// 是否为功能词（需传入小写形式）
+pub fn is_stop_word(word: &str) -> bool {
+    STOP_WORDS.contains(&word)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_tokenize() {
+        let tokens: Vec<&str> = tokenize("Don't panic -- it's a well-known café, 42 times!").collect();
+        assert_eq!(tokens, ["Don't", "panic", "it's", "a", "well-known", "café", "times"]);
+    }
+
+    #[test]
+    fn test_tokenize_skips_cjk() {
+        let tokens: Vec<&str> = tokenize("abandon 放弃 v.").collect();
+        assert_eq!(tokens, ["abandon", "v"]);
+    }
+
+    #[test]
+    fn test_sentence_containing() {
+        let text = "It was late. She had to abandon the well-known plan! Nobody knew.";
+        assert_eq!(sentence_containing(text, "abandon"), Some("She had to abandon the well-known plan!"));
+        assert_eq!(sentence_containing(text, "known"), Some("She had to abandon the well-known plan!"));
+        assert_eq!(sentence_containing(text, "lat"), None);
+    }
+
+    #[test]
+    fn test_normalize_unicode() {
+        assert_eq!(normalize_unicode("ﬁnd the ﬂow"), "find the flow");
+        assert_eq!(normalize_unicode("teacher\u{2019}s \u{201c}caf\u{0065}\u{0301}\u{201d}"), "teacher's \"café\"");
+        assert_eq!(normalize_unicode("ab\u{200b}an\u{00ad}don\u{feff}"), "abandon");
+        assert!(matches!(normalize_unicode("plain text"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_trim_word() {
+        assert_eq!(trim_word("  (hello), "), "hello");
+        assert_eq!(trim_word("123"), "");
+    }
+}
@@ -0,0 +1,2565 @@
+//! 单词提取模块
+//! 
+//! 从 Markdown 文件中的 HTML 表格和管道表格（`| NO. | 单词 | 释义 |`）提取单词，
+//! 可选地同时提取列表和“单词 — 释义”形式的纯文本行；也可以从 SRT/VTT 字幕的对白中
+//! 收集单词
+
+use crate::level::Level;
+use crate::pos::PartOfSpeech;
+use crate::sanitize::{self, Cleanup};
+use crate::{kindle, phonetics, subtitle, text, ColumnMapping, Error, InputFormat, Result};
+#[cfg(feature = "html")]
+use scraper::{ElementRef, Html, Selector};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// 单词数据结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Word {
+    pub number: String,
+    pub word: String,
+    pub meaning: String,
+    /// 音标（IPA，形如 `/əˈbændən/`），没有时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phonetic: Option<String>,
+    /// 例句，来自表格的例句列或原文中单词所在的句子
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<String>,
+    /// 词性，来自释义中的缩写或按后缀推测，未标注时为空
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pos: Vec<PartOfSpeech>,
+    /// 在原文中的行号（从 1 开始），从 Markdown、CSV、字幕提取或按词频提取时记录
+    pub line_number: Option<usize>,
+    /// PDF 原文中的页码（从 1 开始），仅 PDF 来源且找到时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<usize>,
+    /// 在原文中出现的次数，仅按词频提取时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency: Option<usize>,
+    /// 难度评分（1 最简单，5 最难），未评分时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub difficulty: Option<u8>,
+    /// CEFR 等级或词频档位，来自分级词表（见 [`level`](crate::level)），未标注时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<Level>,
+    /// 所在章节：Markdown 中单词上方最近的 `#`/`##` 标题，从 Markdown 提取时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chapter: Option<String>,
+    /// 出处，如 Kindle 标注所在的书和位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// 短语数据结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Phrase {
+    pub number: String,
+    pub phrase: String,
+    pub meaning: String,
+    /// 出处，如 Kindle 标注所在的书和位置
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+/// 提取结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ExtractResult {
+    pub words: Vec<Word>,
+    pub phrases: Vec<Phrase>,
+    pub total_words: usize,
+    pub total_phrases: usize,
+    /// 因格式问题被跳过的表格行（表头、空行和停用词不计入）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped: Vec<SkippedRow>,
+    /// 提取时清理过的单词和短语（见 [`sanitize`](crate::sanitize)）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cleaned: Vec<Cleanup>,
+    /// 批量提取的源文件，按提取顺序排列；单词的出处为所在文件的路径
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<String>,
+}
+
+/// 提取时被跳过的表格行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkippedRow {
+    /// 行内容，各列以 ` | ` 连接
+    pub row: String,
+    /// 跳过原因
+    pub reason: String,
+    /// 来源文件，批量提取时记录
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+impl SkippedRow {
+    fn new(cols: &[String], reason: &str) -> Self {
+        Self { row: cols.join(" | "), reason: reason.to_string(), source: None }
+    }
+}
+
+impl Word {
+    /// 单词在原文中的位置，如 `unit1.md:12`；没有出处时使用 `file`，都没有时为 `第 12 行`
+    pub fn location(&self, file: Option<&str>) -> Option<String> {
+        match (self.source.as_deref().or(file), self.line_number) {
+            (Some(file), Some(line)) => Some(format!("{}:{}", file, line)),
+            (None, Some(line)) => Some(format!("第 {} 行", line)),
+            (Some(file), None) if self.source.is_some() => Some(file.to_string()),
+            _ => None,
+        }
+    }
+}
+
+impl ExtractResult {
+    /// 保存完整的提取结果（JSON），之后可以用 [`load`](Self::load) 读回，不必重新解析原文
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+    
+    /// 读取 [`save`](Self::save) 保存的提取结果；也接受
+    /// [`WordExtractor::save_json`] 保存的单词数组（没有短语）。单词数和短语数按列表重新计算
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Saved {
+            Result(ExtractResult),
+            Words(Vec<Word>),
+        }
+        
+        let content = fs::read_to_string(path)?;
+        let mut result = match serde_json::from_str(&content)? {
+            Saved::Result(result) => result,
+            Saved::Words(words) => ExtractResult {
+                total_words: 0,
+                total_phrases: 0,
+                words,
+                phrases: Vec::new(),
+                skipped: Vec::new(),
+                cleaned: Vec::new(),
+                files: Vec::new(),
+            },
+        };
+        result.total_words = result.words.len();
+        result.total_phrases = result.phrases.len();
+        Ok(result)
+    }
+    
+    /// 严格模式的检查：有被跳过的行时返回错误，错误信息列出每一行及原因
+    pub fn ensure_complete(&self) -> Result<()> {
+        if self.skipped.is_empty() {
+            return Ok(());
+        }
+        let details: Vec<String> = self
+            .skipped
+            .iter()
+            .map(|s| match &s.source {
+                Some(source) => format!("  {}: {}（{}）", source, s.row, s.reason),
+                None => format!("  {}（{}）", s.row, s.reason),
+            })
+            .collect();
+        Err(Error::Parse(format!("严格模式：{} 行未能提取\n{}", self.skipped.len(), details.join("\n"))))
+    }
+    
+    /// 批量提取时各源文件的单词数，按提取顺序排列
+    pub fn words_per_file(&self) -> Vec<(&str, usize)> {
+        self.files
+            .iter()
+            .map(|file| (file.as_str(), self.words.iter().filter(|w| w.source.as_deref() == Some(file)).count()))
+            .collect()
+    }
+    
+    /// 批量提取时每个单词及其源文件，用于按文件拆分核对结果（见 [`CheckResult::by_source`](crate::CheckResult::by_source)）
+    pub fn word_files(&self) -> Vec<(&str, &str)> {
+        self.words
+            .iter()
+            .filter_map(|w| {
+                let source = w.source.as_deref().filter(|s| self.files.iter().any(|f| f == s))?;
+                Some((w.word.as_str(), source))
+            })
+            .collect()
+    }
+    
+    /// 按难度从易到难排序单词，未评分的单词排在最后，同难度保持原有顺序
+    pub fn sort_by_difficulty(&mut self) {
+        self.words.sort_by_key(|w| w.difficulty.unwrap_or(u8::MAX));
+    }
+    
+    /// 按难度拆分为简单、困难两册：难度不超过 `max_easy` 的单词归入简单册，
+    /// 其余（含未评分的单词）归入困难册；短语保留在简单册中
+    pub fn split_by_difficulty(&self, max_easy: u8) -> (ExtractResult, ExtractResult) {
+        let (easy, hard): (Vec<Word>, Vec<Word>) = self
+            .words
+            .iter()
+            .cloned()
+            .partition(|w| w.difficulty.is_some_and(|d| d <= max_easy));
+        
+        (
+            ExtractResult {
+                total_words: easy.len(),
+                total_phrases: self.phrases.len(),
+                words: easy,
+                phrases: self.phrases.clone(),
+                skipped: Vec::new(),
+                cleaned: Vec::new(),
+                files: Vec::new(),
+            },
+            ExtractResult {
+                total_words: hard.len(),
+                total_phrases: 0,
+                words: hard,
+                phrases: Vec::new(),
+                skipped: Vec::new(),
+                cleaned: Vec::new(),
+                files: Vec::new(),
+            },
+        )
+    }
+    
+    /// 按章节拆分单词，章节按首次出现的顺序排列，没有章节的单词归入标题为 None 的一组；
+    /// 短语不拆分
+    pub fn split_by_chapter(&self) -> Vec<(Option<&str>, ExtractResult)> {
+        let mut volumes: Vec<(Option<&str>, Vec<Word>)> = Vec::new();
+        for word in &self.words {
+            let chapter = word.chapter.as_deref();
+            match volumes.iter_mut().find(|(c, _)| *c == chapter) {
+                Some((_, words)) => words.push(word.clone()),
+                None => volumes.push((chapter, vec![word.clone()])),
+            }
+        }
+        volumes
+            .into_iter()
+            .map(|(chapter, words)| {
+                let volume = ExtractResult {
+                    total_words: words.len(),
+                    total_phrases: 0,
+                    words,
+                    phrases: Vec::new(),
+                    skipped: Vec::new(),
+                    cleaned: Vec::new(),
+                    files: Vec::new(),
+                };
+                (chapter, volume)
+            })
+            .collect()
+    }
+    
+    /// 抽样：单词和短语各保留最多 `n` 个，用于在小样本上验证配置
+    ///
+    /// `seed` 为 None 时取前 `n` 个，否则按种子随机抽取（同一种子结果相同）；
+    /// 抽中的条目保持原有顺序和序号
+    pub fn sample(&mut self, n: usize, seed: Option<u64>) {
+        fn pick<T>(items: &mut Vec<T>, n: usize, state: Option<&mut u64>) {
+            if items.len() <= n {
+                return;
+            }
+            let Some(state) = state else {
+                items.truncate(n);
+                return;
+            };
+            // 部分 Fisher–Yates 洗牌选出下标，再按原顺序保留
+            let mut indices: Vec<usize> = (0..items.len()).collect();
+            for i in 0..n {
+                let j = i + (splitmix64(state) % (indices.len() - i) as u64) as usize;
+                indices.swap(i, j);
+            }
+            let chosen: HashSet<usize> = indices[..n].iter().copied().collect();
+            let mut index = 0;
+            items.retain(|_| {
+                index += 1;
+                chosen.contains(&(index - 1))
+            });
+        }
+        
+        let mut state = seed;
+        pick(&mut self.words, n, state.as_mut());
+        pick(&mut self.phrases, n, state.as_mut());
+        self.total_words = self.words.len();
+        self.total_phrases = self.phrases.len();
+    }
+}
+
+/// SplitMix64 伪随机数，抽样用
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// 提取模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "clap", value(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum ExtractMode {
+    /// 只输出单词
+    #[default]
+    WordsOnly,
+    /// 输出单词和词义
+    WithMeaning,
+    /// 输出单词、词义和短语
+    Full,
+}
+
+impl ExtractMode {
+    /// 是否提取短语
+    pub fn includes_phrases(self) -> bool {
+        self == ExtractMode::Full
+    }
+    
+    /// 默认输出文件名后缀
+    pub fn output_suffix(self) -> &'static str {
+        match self {
+            ExtractMode::WordsOnly => "_单词.txt",
+            ExtractMode::WithMeaning => "_单词词义.txt",
+            ExtractMode::Full => "_完整.txt",
+        }
+    }
+}
+
+impl std::fmt::Display for ExtractMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ExtractMode::WordsOnly => "words_only",
+            ExtractMode::WithMeaning => "with_meaning",
+            ExtractMode::Full => "full",
+        })
+    }
+}
+
+/// 单词和短语的大小写规则，提取时统一应用
+///
+/// 去重不区分大小写，保留原文大小写时同一本书中可能同时出现 `Abandon` 和 `abandon` 这样不一致的写法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "clap", value(rename_all = "snake_case"))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum Casing {
+    /// 保留原文的大小写
+    #[default]
+    Preserve,
+    /// 全部转为小写
+    LowercaseAll,
+    /// 转为小写，全部大写的缩写词（`USA`、`DNA`）除外；短语逐词判断
+    LowercaseUnlessAcronym,
+}
+
+impl Casing {
+    /// 按规则转换单词或短语
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Casing::Preserve => text.to_string(),
+            Casing::LowercaseAll => text.to_lowercase(),
+            Casing::LowercaseUnlessAcronym => text
+                .split(' ')
+                .map(|token| if text::is_acronym(token) { token.to_string() } else { token.to_lowercase() })
+                .collect::<Vec<_>>()
+                .join(" "),
+        }
+    }
+}
+
+/// 单词与短语的区分规则
+///
+/// 只按空格判断时，`well-known` 这类连字符词会被当作短语，在只输出单词时丢失；
+/// 所有格、斜线分隔的多种写法以及 OCR 产生的连字、弯撇号也无法被不背单词识别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordPolicy {
+    /// 连字符词作为单词，关闭时作为短语
+    pub keep_hyphenated: bool,
+    /// 去掉词尾的所有格（`teacher's` → `teacher`，`students'` → `students`），`it's` 等缩写不受影响
+    pub strip_possessives: bool,
+    /// 拆分斜线分隔的写法（`color/colour` → `color`、`colour`），含空格的短语不拆分
+    pub split_slashes: bool,
+    /// Unicode 规范化（见 [`text::normalize_unicode`]）：`ﬁnd` → `find`、`teacher’s` → `teacher's`、
+    /// 合成附加符号、去掉零宽字符
+    pub normalize_unicode: bool,
+    /// 清理 OCR 残留（见 [`sanitize::sanitize`]）：`abandon,` → `abandon`、`ability1` → `ability`、
+    /// `abroad (adv.)` → `abroad`，清理记录保存在 [`ExtractResult::cleaned`]
+    pub sanitize: bool,
+}
+
+impl Default for WordPolicy {
+    /// 所有规则全部启用
+    fn default() -> Self {
+        Self { keep_hyphenated: true, strip_possessives: true, split_slashes: true, normalize_unicode: true, sanitize: true }
+    }
+}
+
+impl std::str::FromStr for WordPolicy {
+    type Err = Error;
+    
+    /// 解析启用的规则列表：`hyphen`、`possessive`、`slash`、`unicode`、`sanitize` 以逗号分隔，`all` 为全部启用，
+    /// `none` 为全部关闭（只按空格和连字符判断短语）
+    fn from_str(s: &str) -> Result<Self> {
+        let mut policy = Self { keep_hyphenated: false, strip_possessives: false, split_slashes: false, normalize_unicode: false, sanitize: false };
+        for rule in s.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            match rule.to_lowercase().as_str() {
+                "hyphen" => policy.keep_hyphenated = true,
+                "possessive" => policy.strip_possessives = true,
+                "slash" => policy.split_slashes = true,
+                "unicode" => policy.normalize_unicode = true,
+                "sanitize" => policy.sanitize = true,
+                "all" => policy = Self::default(),
+                "none" => {}
+                other => return Err(Error::Parse(format!("未知的单词规则: {}（可用 hyphen、possessive、slash、unicode、sanitize、all、none）", other))),
+            }
+        }
+        Ok(policy)
+    }
+}
+
+impl WordPolicy {
+    /// 不去掉所有格的常见缩写
+    const CONTRACTIONS: [&'static str; 10] = ["it", "he", "she", "that", "what", "let", "there", "here", "who", "where"];
+    
+    /// 规范化表格中的单词/短语，斜线拆分后可能得到多项
+    pub fn normalize(&self, entry: &str) -> Vec<String> {
+        let entry = self.clean(entry.trim());
+        let entry = entry.trim();
+        let parts: Vec<&str> = if self.split_slashes && entry.contains('/') && !entry.contains(char::is_whitespace) {
+            entry.split('/').map(str::trim).filter(|p| !p.is_empty()).collect()
+        } else {
+            vec![entry]
+        };
+        parts
+            .into_iter()
+            .map(|part| if self.strip_possessives { Self::strip_possessive(part) } else { part })
+            .map(str::to_string)
+            .collect()
+    }
+    
+    /// 启用 Unicode 规范化时规范化正文，否则原样返回
+    pub fn clean<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.normalize_unicode {
+            text::normalize_unicode(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+    
+    /// 是否为短语：含空格，或不保留连字符词时含连字符
+    pub fn is_phrase(&self, entry: &str) -> bool {
+        if self.keep_hyphenated {
+            entry.contains(char::is_whitespace)
+        } else {
+            text::is_phrase(entry)
+        }
+    }
+    
+    /// 去掉词尾的 `'s`、`’s` 或复数所有格的撇号
+    fn strip_possessive(word: &str) -> &str {
+        for suffix in ["'s", "\u{2019}s"] {
+            if let Some(stem) = word.strip_suffix(suffix) {
+                let is_contraction = Self::CONTRACTIONS.iter().any(|c| stem.eq_ignore_ascii_case(c));
+                return if is_contraction || stem.chars().count() < 2 { word } else { stem };
+            }
+        }
+        match word.strip_suffix(['\'', '\u{2019}']) {
+            Some(stem) if stem.ends_with(['s', 'S']) => stem,
+            _ => word,
+        }
+    }
+}
+
+/// 单词的长度和字符过滤规则，用于去掉单个字母和 OCR 产生的乱码；短语不受影响
+///
+/// 默认不过滤任何单词
+#[derive(Debug, Clone, Default)]
+pub struct WordFilter {
+    /// 最短字符数，0 和 1 都表示不限
+    pub min_length: usize,
+    /// 最长字符数
+    pub max_length: Option<usize>,
+    /// 只保留 ASCII 字符组成的单词（`café`、`naïve` 会被过滤）
+    pub ascii_only: bool,
+    /// 过滤含有数字的单词（`h2o`、`l0ve`）
+    pub reject_digits: bool,
+}
+
+impl WordFilter {
+    /// 单词是否通过过滤
+    pub fn accepts(&self, word: &str) -> bool {
+        let length = word.chars().count();
+        length >= self.min_length
+            && self.max_length.map_or(true, |max| length <= max)
+            && (!self.ascii_only || word.is_ascii())
+            && (!self.reject_digits || !word.chars().any(|c| c.is_numeric()))
+    }
+}
+
+/// 单词提取器
+pub struct WordExtractor {
+    unique: bool,
+    include_phrases: bool,
+    include_lists: bool,
+    columns: ColumnMapping,
+    fallback: Option<Regex>,
+    stopwords: HashSet<String>,
+    strict: bool,
+    examples: bool,
+    pos_groups: bool,
+    batch_filter: BatchFilter,
+    policy: WordPolicy,
+    header_markers: HashSet<String>,
+    filter: WordFilter,
+    whitelist: HashSet<String>,
+    casing: Casing,
+}
+
+/// 内置的表头标记（小写），见 [`WordExtractor::with_header_markers`]
+pub const DEFAULT_HEADER_MARKERS: [&str; 20] = [
+    "no.", "no", "#", "序号", "编号", "单词", "短语", "词汇", "英文", "释义", "词义", "中文",
+    "word", "words", "phrase", "phrases", "vocabulary", "meaning", "definition", "translation",
+];
+
+/// 默认的兜底正则：`1. abandon 放弃`、`2、take off 起飞` 形式的编号行
+pub const DEFAULT_FALLBACK_PATTERN: &str = r"^\s*(\d+)[.、)]\s*([A-Za-z][A-Za-z'’-]*(?: [A-Za-z'’-]+)*)\s+(.+)$";
+
+impl WordExtractor {
+    /// 创建新的提取器
+    pub fn new(unique: bool, include_phrases: bool) -> Self {
+        Self { unique, include_phrases, include_lists: false, columns: ColumnMapping::default(), fallback: None, stopwords: HashSet::new(), strict: false, examples: false, pos_groups: false, batch_filter: BatchFilter::default(), policy: WordPolicy::default(), header_markers: Self::header_markers(&[]), filter: WordFilter::default(), whitelist: HashSet::new(), casing: Casing::Preserve }
+    }
+    
+    /// 设置兜底正则：Markdown 中找不到表格（以及启用时的列表）时逐行匹配
+    ///
+    /// 优先使用命名分组 `number`、`word`、`meaning`；没有命名分组时，3 个分组依次为
+    /// 序号、单词、词义，2 个分组为单词、词义，1 个分组为单词。没有序号时按行自动编号
+    pub fn with_fallback(mut self, pattern: Option<Regex>) -> Self {
+        self.fallback = pattern;
+        self
+    }
+    
+    /// 编译兜底正则，见 [`with_fallback`](Self::with_fallback)
+    pub fn fallback_pattern(pattern: &str) -> Result<Regex> {
+        let regex = Regex::new(pattern).map_err(|e| Error::Parse(format!("无效的正则表达式: {}", e)))?;
+        let has_word = regex.capture_names().flatten().any(|name| name == "word");
+        if !has_word && regex.captures_len() < 2 {
+            return Err(Error::Parse(format!("正则表达式至少需要一个分组来匹配单词: {}", pattern)));
+        }
+        Ok(regex)
+    }
+    
+    /// 设置严格模式：Markdown 中找不到表格时报错而不使用兜底正则，
+    /// 有行被跳过时报错（见 [`ExtractResult::ensure_complete`]）
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    
+    /// 设置是否记录例句：按词频或从字幕提取时，把单词首次出现的句子作为例句
+    ///
+    /// 表格中的例句列不受此设置影响，见 [`ColumnMapping`]
+    pub fn with_examples(mut self, examples: bool) -> Self {
+        self.examples = examples;
+        self
+    }
+    
+    /// 设置是否在 [`save_with_meaning`](Self::save_with_meaning) 中按词性分组输出单词，
+    /// 词性见 [`pos::fill_pos`](crate::pos::fill_pos)
+    pub fn with_pos_groups(mut self, pos_groups: bool) -> Self {
+        self.pos_groups = pos_groups;
+        self
+    }
+    
+    /// 设置批量提取（[`extract_from_dir`](Self::extract_from_dir)）时的文件过滤规则
+    pub fn with_batch_filter(mut self, filter: BatchFilter) -> Self {
+        self.batch_filter = filter;
+        self
+    }
+    
+    /// 设置表格中单词与短语的区分规则，默认见 [`WordPolicy::default`]
+    pub fn with_policy(mut self, policy: WordPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+    
+    /// 设置单词的长度和字符过滤规则，见 [`WordFilter`]
+    pub fn with_filter(mut self, filter: WordFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+    
+    /// 设置停用词：提取时跳过这些单词（不区分大小写），短语不受影响
+    ///
+    /// 通常由 [`stopwords`](Self::stopwords) 生成，包含内置功能词和用户的排除词表
+    pub fn with_stopwords(mut self, stopwords: HashSet<String>) -> Self {
+        self.stopwords = stopwords;
+        self
+    }
+    
+    /// 内置功能词加上排除词表中的单词，排除词表的格式见 [`word_list`](Self::word_list)
+    pub fn stopwords(exclusions: Option<&str>) -> HashSet<String> {
+        let mut stopwords: HashSet<String> = text::STOP_WORDS.iter().map(|w| w.to_string()).collect();
+        stopwords.extend(Self::word_list(exclusions.unwrap_or_default()));
+        stopwords
+    }
+    
+    /// 读取词表中的单词（小写）：每行可写多个单词（空格或逗号分隔），`#` 开头的行为注释
+    pub fn word_list(content: &str) -> HashSet<String> {
+        let mut words = HashSet::new();
+        for line in content.lines().map(str::trim) {
+            if line.starts_with('#') {
+                continue;
+            }
+            words.extend(line.split([',', ' ', '\t']).map(text::normalize_key).filter(|w| !w.is_empty()));
+        }
+        words
+    }
+    
+    /// 设置单词和短语的大小写规则，默认保留原文的大小写
+    pub fn with_casing(mut self, casing: Casing) -> Self {
+        self.casing = casing;
+        self
+    }
+    
+    /// 设置白名单：其中的单词（不区分大小写）不会被停用词、排除词表、长度和字符过滤
+    /// 以及按词频提取时的功能词规则去掉，如 `ox` 这类短词
+    pub fn with_whitelist(mut self, whitelist: HashSet<String>) -> Self {
+        self.whitelist = whitelist;
+        self
+    }
+    
+    /// 内置表头标记加上额外的标记（忽略大小写和首尾空白）
+    pub fn header_markers(extra: &[String]) -> HashSet<String> {
+        DEFAULT_HEADER_MARKERS
+            .iter()
+            .map(|m| m.to_string())
+            .chain(extra.iter().map(|m| text::normalize_key(m)))
+            .filter(|m| !m.is_empty())
+            .collect()
+    }
+    
+    /// 设置表头标记：序号列不是数字、且前三列中有一列等于某个标记的行视为表头，
+    /// 通常由 [`header_markers`](Self::header_markers) 生成
+    pub fn with_header_markers(mut self, markers: HashSet<String>) -> Self {
+        self.header_markers = markers;
+        self
+    }
+    
+    /// 是否为表头行或「补充区」等分区标题
+    fn is_header(&self, cols: &[String]) -> bool {
+        let is_marker = |cell: &String| self.header_markers.contains(&text::normalize_key(cell));
+        cols.iter().any(|c| c.contains("补充区"))
+            || cols.get(1).is_some_and(|c| c == "单词" || c == "短语")
+            || cols.first().is_some_and(|c| !text::is_number(c.trim())) && cols.iter().take(3).any(is_marker)
+    }
+    
+    /// 是否为停用词
+    fn is_stopword(&self, word: &str) -> bool {
+        !self.stopwords.is_empty() && self.stopwords.contains(&text::normalize_key(word))
+    }
+    
+    /// 是否在白名单中
+    fn is_whitelisted(&self, word: &str) -> bool {
+        !self.whitelist.is_empty() && self.whitelist.contains(&text::normalize_key(word))
+    }
+    
+    /// 是否被停用词或过滤规则排除，白名单中的单词不排除
+    fn is_excluded(&self, word: &str) -> bool {
+        !self.is_whitelisted(word) && (self.is_stopword(word) || !self.filter.accepts(word))
+    }
+    
+    /// 启用例句时，单词在这一行中所在的句子
+    fn example(&self, line: &str, word: &str) -> Option<String> {
+        if !self.examples {
+            return None;
+        }
+        text::sentence_containing(line, word).map(str::to_string)
+    }
+    
+    /// 设置表格的列映射，默认依次为序号、单词/短语、词义
+    ///
+    /// 只作用于表格（HTML、管道表格、Excel 和 Word 表格），不影响列表和纯文本行
+    pub fn with_columns(mut self, columns: ColumnMapping) -> Self {
+        self.columns = columns;
+        self
+    }
+    
+    /// 设置是否同时提取表格以外的内容
+    ///
+    /// 启用后会识别无序列表、有序列表和 `word — 释义` 形式的行，
+    /// 适用于以纯 Markdown 列表记录的单词笔记
+    pub fn with_lists(mut self, include_lists: bool) -> Self {
+        self.include_lists = include_lists;
+        self
+    }
+    
+    /// 从文件提取单词，格式由 [`InputFormat::detect`] 按扩展名和内容识别
+    ///
+    /// 启用 `xlsx` 特性时，Excel/ODS 文件读取第一个工作表；启用 `apkg` 特性时按默认字段
+    /// 读取 Anki 牌组；Word 文档、EPUB 需要对应的特性
+    pub fn extract_from_file<P: AsRef<Path>>(&self, file_path: P) -> Result<ExtractResult> {
+        self.extract_as(file_path.as_ref(), InputFormat::detect(file_path.as_ref())?)
+    }
+    
+    /// 按指定格式从文件提取单词，PDF 需要先转换为 Markdown
+    pub fn extract_as<P: AsRef<Path>>(&self, file_path: P, format: InputFormat) -> Result<ExtractResult> {
+        match format {
+            InputFormat::Pdf => Err(Error::Other("PDF 需要先用 Mineru 转换为 Markdown".to_string())),
+            #[cfg(feature = "docx")]
+            InputFormat::Docx => self.extract_from_docx(file_path),
+            #[cfg(feature = "xlsx")]
+            InputFormat::Xlsx => self.extract_from_xlsx(file_path, None),
+            #[cfg(feature = "apkg")]
+            InputFormat::Apkg => self.extract_from_apkg(file_path, &crate::apkg::AnkiFields::default()),
+            #[cfg(feature = "epub")]
+            InputFormat::Epub => self.extract_from_epub(file_path),
+            // 对应的特性未启用（特性名与格式名相同）
+            #[allow(unreachable_patterns)]
+            InputFormat::Docx | InputFormat::Xlsx | InputFormat::Apkg | InputFormat::Epub => {
+                Err(Error::Other(format!("未启用 {} 特性，无法读取该文件", format)))
+            }
+            _ => self.extract_from_content(&fs::read_to_string(file_path)?, format),
+        }
+    }
+    
+    /// 按指定的文本格式从内容提取单词
+    pub fn extract_from_content(&self, content: &str, format: InputFormat) -> Result<ExtractResult> {
+        match format {
+            InputFormat::Clippings => Ok(self.extract_from_clippings(content)),
+            InputFormat::Subtitle => Ok(self.extract_from_subtitles(content)),
+            InputFormat::Csv => Ok(self.extract_from_csv(content)),
+            InputFormat::Markdown | InputFormat::Html | InputFormat::Text => self.extract_from_markdown(content),
+            _ => Err(Error::Other(format!("{} 格式不是文本，需要从文件读取", format))),
+        }
+    }
+    
+    /// 从任意输入流（如标准输入）读取 UTF-8 文本并提取单词，格式按内容识别
+    pub fn extract_from_reader<R: Read>(&self, mut reader: R) -> Result<ExtractResult> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content)?;
+        self.extract_from_content(&content, InputFormat::sniff(content.as_bytes()))
+    }
+    
+    /// 从 CSV/TSV 表格提取单词，列与 Markdown 表格相同，可用 [`with_columns`](Self::with_columns) 修改
+    pub fn extract_from_csv(&self, content: &str) -> ExtractResult {
+        let mut result = self.extract_from_rows(self.columns.apply(csv_rows(content)));
+        locate_lines(&mut result.words, content);
+        result
+    }
+    
+    /// 从 EPUB 电子书章节中的 HTML 表格提取单词
+    #[cfg(feature = "epub")]
+    pub fn extract_from_epub<P: AsRef<Path>>(&self, file_path: P) -> Result<ExtractResult> {
+        let chapters = crate::epub::read_chapters(&fs::read(file_path)?)?;
+        log::info!("读取 EPUB 章节: {} 个", chapters.len());
+        self.extract_from_markdown(&chapters.join("\n"))
+    }
+    
+    /// 下载网页并从其中的 HTML 表格和 `<li>` 列表项提取单词
+    ///
+    /// 表格列与 Markdown 表格相同（可用 [`with_columns`](Self::with_columns) 修改），列表项按
+    /// “单词 — 释义”形式读取；网页不使用兜底正则。非 2xx 响应按状态码返回 [`Error::Api`]、
+    /// [`Error::RateLimited`] 或 [`Error::Forbidden`]，附带截断后的响应正文
+    #[cfg(feature = "http")]
+    pub fn extract_from_url(&self, url: &str) -> Result<ExtractResult> {
+        let client = reqwest::blocking::Client::builder()
+            .timeout(URL_TIMEOUT)
+            .user_agent(concat!("bbdc_word_tool/", env!("CARGO_PKG_VERSION")))
+            .build()?;
+        let response = client.get(url).send()?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().unwrap_or_default();
+            return Err(crate::ApiError::new(status, format!("无法下载网页 {}", url), Vec::new(), &body).into());
+        }
+        let content = response.text()?;
+        log::info!("已下载网页: {}（{} 字节）", url, content.len());
+        
+        let mut rows = self.columns.apply(html_table_rows(&content)?);
+        rows.extend(list_rows(&html_list_items(&content).join("\n")));
+        let result = self.extract_from_rows(rows);
+        if self.strict {
+            result.ensure_complete()?;
+        }
+        Ok(result)
+    }
+    
+    /// 从目录或通配符匹配的多个文件提取单词并合并
+    ///
+    /// `path` 为目录时递归读取其中的 Markdown 文件（`.md`/`.markdown`）；包含 `*`、`?` 时
+    /// 按通配符匹配文件（`**` 匹配任意层目录），如 `notes/**/*.md`；可以用
+    /// [`with_batch_filter`](Self::with_batch_filter) 进一步筛选。文件按路径顺序逐个
+    /// 提取，合并后重新编号；出处记录为源文件路径（已有出处的保留原值），启用去重时跨文件去重
+    pub fn extract_from_dir<P: AsRef<Path>>(&self, path: P) -> Result<ExtractResult> {
+        let files = batch_files(path.as_ref(), &self.batch_filter)?;
+        if files.is_empty() {
+            return Err(Error::Other(format!("没有找到要提取的文件: {:?}", path.as_ref())));
+        }
+        
+        let mut words: Vec<Word> = Vec::new();
+        let mut phrases: Vec<Phrase> = Vec::new();
+        let mut seen_words: HashSet<String> = HashSet::new();
+        let mut skipped: Vec<SkippedRow> = Vec::new();
+        let mut cleaned: Vec<Cleanup> = Vec::new();
+        for file in &files {
+            let result = self.extract_from_file(file)?;
+            log::info!("{:?}: {} 个单词", file, result.total_words);
+            let source = file.display().to_string();
+            for mut word in result.words {
+                if self.unique && !seen_words.insert(text::normalize_key(&word.word)) {
+                    continue;
+                }
+                word.number = (words.len() + 1).to_string();
+                word.source.get_or_insert_with(|| source.clone());
+                words.push(word);
+            }
+            for mut phrase in result.phrases {
+                phrase.number = (phrases.len() + 1).to_string();
+                phrase.source.get_or_insert_with(|| source.clone());
+                phrases.push(phrase);
+            }
+            skipped.extend(result.skipped.into_iter().map(|row| SkippedRow { source: Some(source.clone()), ..row }));
+            cleaned.extend(result.cleaned);
+        }
+        
+        log::info!("从 {} 个文件合并得到 {} 个单词", files.len(), words.len());
+        
+        Ok(ExtractResult {
+            total_words: words.len(),
+            total_phrases: phrases.len(),
+            words,
+            phrases,
+            skipped,
+            cleaned,
+            files: files.iter().map(|f| f.display().to_string()).collect(),
+        })
+    }
+    
+    /// 从 Kindle 标注文件（`My Clippings.txt`）提取单词和短语
+    ///
+    /// 只取不超过 [`MAX_CLIPPING_WORDS`] 个词的标注：单个词作为单词（转为小写），
+    /// 多个词作为短语；出处记录为 `书名 · 位置`
+    pub fn extract_from_clippings(&self, content: &str) -> ExtractResult {
+        let mut words = Vec::new();
+        let mut phrases = Vec::new();
+        let mut seen_words: HashSet<String> = HashSet::new();
+        
+        for clipping in kindle::parse_clippings(content) {
+            let clipping_text = self.policy.clean(&clipping.text);
+            let tokens: Vec<&str> = clipping_text.split_whitespace().map(text::trim_word).filter(|t| !t.is_empty()).collect();
+            let source = Some(format!("{} · {}", clipping.book, clipping.location));
+            
+            match tokens.len() {
+                1 => {
+                    let word = text::normalize_key(tokens[0]);
+                    if self.is_excluded(&word) || self.unique && !seen_words.insert(word.clone()) {
+                        continue;
+                    }
+                    words.push(Word {
+                        number: (words.len() + 1).to_string(),
+                        word,
+                        meaning: String::new(),
+                        phonetic: None,
+                        example: None,
+                        pos: Vec::new(),
+                        line_number: None,
+                        page: None,
+                        frequency: None,
+                        difficulty: None,
+                        level: None,
+                        chapter: None,
+                        source,
+                    });
+                }
+                n if n > 1 && n <= MAX_CLIPPING_WORDS && self.include_phrases => {
+                    phrases.push(Phrase {
+                        number: (phrases.len() + 1).to_string(),
+                        phrase: self.casing.apply(&tokens.join(" ")),
+                        meaning: String::new(),
+                        source,
+                    });
+                }
+                _ => {}
+            }
+        }
+        
+        log::info!("从 Kindle 标注提取到 {} 个单词", words.len());
+        if self.include_phrases {
+            log::info!("从 Kindle 标注提取到 {} 个短语", phrases.len());
+        }
+        
+        ExtractResult {
+            total_words: words.len(),
+            total_phrases: phrases.len(),
+            words,
+            phrases,
+            skipped: Vec::new(),
+            cleaned: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+    
+    /// 按词频从任意正文中提取单词
+    ///
+    /// 去掉 HTML 标签后分词，单词统一转为小写，跳过功能词、缩写和单个字母；
+    /// 出现次数不少于 `min_count` 的单词按次数从高到低排列（次数相同时按首次出现的顺序），
+    /// 序号为名次，行号为首次出现的行
+    pub fn extract_by_frequency(&self, content: &str, min_count: usize) -> ExtractResult {
+        let tags = Regex::new(r"<[^>]*>").expect("有效的正则表达式");
+        let mut counts: Vec<(String, usize, usize, Option<String>)> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        
+        for (i, line) in content.lines().enumerate() {
+            let line = tags.replace_all(line, " ");
+            let line = self.policy.clean(&line);
+            for token in text::tokenize(&line) {
+                if token.contains(['\'', '\u{2019}']) {
+                    continue;
+                }
+                let word = text::normalize_key(token);
+                if !self.is_whitelisted(&word) && (word.chars().count() < 2 || text::is_stop_word(&word) || self.is_excluded(&word)) {
+                    continue;
+                }
+                match index.get(&word) {
+                    Some(&position) => counts[position].1 += 1,
+                    None => {
+                        index.insert(word.clone(), counts.len());
+                        let example = self.example(&line, &word);
+                        counts.push((word, 1, i + 1, example));
+                    }
+                }
+            }
+        }
+        
+        counts.retain(|(_, count, _, _)| *count >= min_count.max(1));
+        counts.sort_by_key(|(_, count, _, _)| std::cmp::Reverse(*count));
+        
+        let words: Vec<Word> = counts
+            .into_iter()
+            .enumerate()
+            .map(|(rank, (word, count, line_number, example))| Word {
+                number: (rank + 1).to_string(),
+                word,
+                meaning: String::new(),
+                phonetic: None,
+                example,
+                pos: Vec::new(),
+                line_number: Some(line_number),
+                page: None,
+                frequency: Some(count),
+                difficulty: None,
+                level: None,
+                chapter: None,
+                source: None,
+            })
+            .collect();
+        
+        log::info!("按词频提取到 {} 个单词", words.len());
+        
+        ExtractResult {
+            total_words: words.len(),
+            total_phrases: 0,
+            words,
+            phrases: Vec::new(),
+            skipped: Vec::new(),
+            cleaned: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+    
+    /// 从 SRT/VTT 字幕的对白中提取单词
+    ///
+    /// 单词统一转为小写，词义为空，行号为首次出现的字幕行；连字符词拆成单独的单词，
+    /// 缩写（`don't`）和单个字母会被跳过
+    pub fn extract_from_subtitles(&self, content: &str) -> ExtractResult {
+        let mut words = Vec::new();
+        let mut seen_words: HashSet<String> = HashSet::new();
+        
+        for line in subtitle::dialogue_lines(content) {
+            let dialogue = self.policy.clean(&line.text);
+            for token in text::tokenize(&dialogue).flat_map(|t| t.split('-')) {
+                if token.contains(['\'', '\u{2019}']) {
+                    continue;
+                }
+                
+                let word = text::normalize_key(token);
+                if (!self.is_whitelisted(&word) && word.chars().count() < 2) || self.is_excluded(&word) || self.unique && !seen_words.insert(word.clone()) {
+                    continue;
+                }
+                
+                let example = self.example(&dialogue, &word);
+                words.push(Word {
+                    number: (words.len() + 1).to_string(),
+                    word,
+                    meaning: String::new(),
+                    phonetic: None,
+                    example,
+                    pos: Vec::new(),
+                    line_number: Some(line.line_number),
+                    page: None,
+                    frequency: None,
+                    difficulty: None,
+                    level: None,
+                    chapter: None,
+                    source: None,
+                });
+            }
+        }
+        
+        log::info!("从字幕提取到 {} 个单词", words.len());
+        
+        ExtractResult {
+            total_words: words.len(),
+            total_phrases: 0,
+            words,
+            phrases: Vec::new(),
+            skipped: Vec::new(),
+            cleaned: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+    
+    /// 从 Word 文档提取单词
+    ///
+    /// 表格按 Markdown 表格处理；表格外的段落按列表和 `word — 释义` 行处理，
+    /// 不受 [`with_lists`](Self::with_lists) 影响
+    #[cfg(feature = "docx")]
+    pub fn extract_from_docx<P: AsRef<Path>>(&self, file_path: P) -> Result<ExtractResult> {
+        let content = crate::docx::read_docx(&fs::read(file_path)?)?;
+        
+        let mut rows = self.columns.apply(content.table_rows);
+        rows.extend(list_rows(&content.paragraphs.join("\n")));
+        Ok(self.extract_from_rows(rows))
+    }
+    
+    /// 从 Anki 牌组提取单词，按 `fields` 指定的字段取单词和释义，序号为笔记顺序
+    #[cfg(feature = "apkg")]
+    pub fn extract_from_apkg<P: AsRef<Path>>(&self, file_path: P, fields: &crate::apkg::AnkiFields) -> Result<ExtractResult> {
+        let notes = crate::apkg::read_notes(&fs::read(file_path)?)?;
+        
+        let rows = notes.iter().enumerate().filter_map(|(i, note)| {
+            let word = note.field(&fields.word)?;
+            let meaning = note.field(&fields.meaning).unwrap_or_default();
+            let mut row = vec![(i + 1).to_string(), word.to_string(), meaning.to_string()];
+            row.extend(fields.phonetic.as_deref().and_then(|f| note.field(f)).and_then(phonetics::normalize));
+            Some(row)
+        });
+        Ok(self.extract_from_rows(rows))
+    }
+    
+    /// 从 Excel 工作簿提取单词
+    ///
+    /// `sheet` 为工作表名称或从 1 开始的序号，缺省时使用第一个工作表。
+    /// 表格列与 Markdown 表格相同，默认为序号、单词/短语、词义，可用 [`with_columns`](Self::with_columns) 修改
+    #[cfg(feature = "xlsx")]
+    pub fn extract_from_xlsx<P: AsRef<Path>>(&self, file_path: P, sheet: Option<&str>) -> Result<ExtractResult> {
+        use calamine::{open_workbook_auto, Reader};
+        
+        let mut workbook = open_workbook_auto(file_path.as_ref())
+            .map_err(|e| Error::Parse(format!("无法打开工作簿: {}", e)))?;
+        
+        let names = workbook.sheet_names().to_vec();
+        let name = match sheet {
+            None => names.first(),
+            Some(sheet) => names.iter().find(|name| name.as_str() == sheet).or_else(|| {
+                sheet.parse::<usize>().ok().and_then(|i| i.checked_sub(1)).and_then(|i| names.get(i))
+            }),
+        }
+        .ok_or_else(|| Error::Parse(format!("工作表不存在: {}（可用: {}）", sheet.unwrap_or("1"), names.join(", "))))?
+        .clone();
+        
+        let range = workbook
+            .worksheet_range(&name)
+            .map_err(|e| Error::Parse(format!("读取工作表 {} 失败: {}", name, e)))?;
+        log::info!("读取工作表: {}", name);
+        
+        let rows = range
+            .rows()
+            .map(|row| row.iter().map(|cell| cell.to_string().trim().to_string()).collect())
+            .collect();
+        Ok(self.extract_from_rows(self.columns.apply(rows)))
+    }
+    
+    /// 从 Markdown 内容提取单词
+    pub fn extract_from_markdown(&self, content: &str) -> Result<ExtractResult> {
+        let mut rows = self.columns.apply(html_table_rows(content)?);
+        rows.extend(self.columns.apply(pipe_table_rows(content)));
+        // 围栏代码块中未转义的表格在整体解析时已经读取，转义过的（`&lt;table&gt;`）只是文本，反转义后再读取
+        for block in fenced_blocks(content).iter().filter(|block| block.contains("&lt;table")) {
+            rows.extend(self.columns.apply(html_table_rows(&unescape_html(block))?));
+        }
+        if self.include_lists {
+            rows.extend(list_rows(content));
+        }
+        if rows.is_empty() {
+            if self.strict {
+                return Err(Error::Parse("严格模式：没有找到单词表格，不使用兜底正则".to_string()));
+            }
+            if let Some(pattern) = &self.fallback {
+                rows = regex_rows(content, pattern);
+                log::info!("未找到表格，兜底正则匹配到 {} 行", rows.len());
+            }
+        }
+        
+        let mut result = self.extract_from_rows(rows);
+        if self.strict {
+            result.ensure_complete()?;
+        }
+        locate_lines(&mut result.words, content);
+        locate_chapters(&mut result.words, content);
+        Ok(result)
+    }
+    
+    /// 从表格行提取单词，每行依次为序号、单词/短语、词义
+    ///
+    /// 第 4 列为音标时读取音标，第 5 列不为空时作为例句（见 [`ColumnMapping`]），其余列忽略
+    pub fn extract_from_rows<I>(&self, rows: I) -> ExtractResult
+    where
+        I: IntoIterator<Item = Vec<String>>,
+    {
+        let mut words = Vec::new();
+        let mut phrases = Vec::new();
+        let mut seen_words: HashSet<String> = HashSet::new();
+        let mut stopped = 0;
+        let mut filtered = 0;
+        let mut skipped = Vec::new();
+        let mut cleaned = Vec::new();
+        
+        for cols in rows {
+            // 单元格中的换行（HTML 的 `<br>`、Excel 的多行文本）合并为空格
+            let cols: Vec<String> = cols
+                .into_iter()
+                .map(|c| if c.contains('\n') { c.split_whitespace().collect::<Vec<_>>().join(" ") } else { c })
+                .collect();
+            // 跳过表头行和分区标题
+            if self.is_header(&cols) {
+                continue;
+            }
+            
+            // 至少需要3列：序号、单词/短语、词义
+            if cols.len() < 3 {
+                if cols.iter().any(|c| !c.is_empty()) {
+                    skipped.push(SkippedRow::new(&cols, "列数不足"));
+                }
+            } else {
+                let row: Vec<String> = cols.iter().take(3).cloned().collect();
+                let mut cols = cols.into_iter();
+                let col1_text = cols.next().unwrap_or_default();
+                let col2_text = cols.next().unwrap_or_default();
+                let col3_text = cols.next().unwrap_or_default();
+                // 第 4 列为 `/…/` 或 `[…]` 形式的音标时一并读取
+                let phonetic = cols.next().filter(|c| phonetics::is_phonetic(c)).and_then(|c| phonetics::normalize(&c));
+                let example = cols.next().map(|c| c.trim().to_string()).filter(|c| !c.is_empty());
+                
+                // 跳过空行和无效数据；只有词义等其他列的行通常是合并单元格或列错位造成的
+                if col1_text.is_empty() && col2_text.is_empty() {
+                    if !col3_text.is_empty() {
+                        skipped.push(SkippedRow::new(&row, "缺少序号和单词"));
+                    }
+                    continue;
+                }
+                if col2_text.is_empty() || !text::is_number(&col1_text) {
+                    let reason = if col2_text.is_empty() {
+                        "缺少单词"
+                    } else if col1_text.is_empty() {
+                        "缺少序号"
+                    } else {
+                        "序号不是数字"
+                    };
+                    skipped.push(SkippedRow::new(&row, reason));
+                    continue;
+                }
+                if !col2_text.chars().any(is_latin_letter) {
+                    skipped.push(SkippedRow::new(&row, "单词列没有英文字母，可能是合并单元格导致列错位"));
+                    continue;
+                }
+                
+                // 清理 OCR 残留后按规则规范化，再区分单词和短语，斜线分隔的多种写法各自成为一项
+                let col2_text = match self.policy.sanitize.then(|| sanitize::sanitize(&self.policy.clean(&col2_text))).flatten() {
+                    Some(cleanup) => {
+                        let text = cleanup.cleaned.clone();
+                        cleaned.push(cleanup);
+                        text
+                    }
+                    None => col2_text,
+                };
+                for entry in self.policy.normalize(&col2_text) {
+                    if self.policy.is_phrase(&entry) {
+                        if self.include_phrases {
+                            phrases.push(Phrase {
+                                number: col1_text.clone(),
+                                phrase: self.casing.apply(&entry),
+                                meaning: col3_text.clone(),
+                                source: None,
+                            });
+                        }
+                        continue;
+                    }
+                    
+                    if !self.is_whitelisted(&entry) {
+                        if self.is_stopword(&entry) {
+                            stopped += 1;
+                            continue;
+                        }
+                        if !self.filter.accepts(&entry) {
+                            filtered += 1;
+                            continue;
+                        }
+                    }
+                    
+                    // 去重检查
+                    if self.unique && !seen_words.insert(text::normalize_key(&entry)) {
+                        continue;
+                    }
+                    
+                    words.push(Word {
+                        number: col1_text.clone(),
+                        word: self.casing.apply(&entry),
+                        meaning: col3_text.clone(),
+                        phonetic: phonetic.clone(),
+                        example: example.clone(),
+                        pos: Vec::new(),
+                        line_number: None,
+                        page: None,
+                        frequency: None,
+                        difficulty: None,
+                        level: None,
+                        chapter: None,
+                        source: None,
+                    });
+                }
+            }
+        }
+        
+        log::info!("提取到 {} 个单词", words.len());
+        if stopped > 0 {
+            log::info!("跳过 {} 个停用词", stopped);
+        }
+        if filtered > 0 {
+            log::info!("跳过 {} 个不符合长度或字符要求的单词", filtered);
+        }
+        if !cleaned.is_empty() {
+            log::info!("清理了 {} 个带有标点、脚注或注释的单词", cleaned.len());
+        }
+        if !skipped.is_empty() {
+            log::warn!("⚠️  跳过 {} 个格式不正确的行", skipped.len());
+        }
+        if self.include_phrases {
+            log::info!("提取到 {} 个短语", phrases.len());
+        }
+        
+        ExtractResult {
+            total_words: words.len(),
+            total_phrases: phrases.len(),
+            words,
+            phrases,
+            skipped,
+            cleaned,
+            files: Vec::new(),
+        }
+    }
+    
+    /// 保存单词列表到文件（仅单词，每行一个）
+    pub fn save_words_only<P: AsRef<Path>>(
+        &self,
+        words: &[Word],
+        output_path: P,
+    ) -> Result<()> {
+        let content = words
+            .iter()
+            .map(|w| w.word.clone())
+            .collect::<Vec<_>>()
+            .join("\n");
+        
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+    
+    /// 按不背单词网页导入的格式保存单词，返回写入的单词数
+    ///
+    /// 导入页面只读取单词（不读取词义）：UTF-8 无 BOM、每行一个单词、换行为 `\n`。
+    /// 写入前规范化 Unicode（去掉 BOM 和零宽字符、替换弯撇号）、合并单词内的连续空白，
+    /// 跳过不含字母的行，并按不区分大小写去重（保留第一次出现的写法）
+    pub fn save_bbdc_import<P: AsRef<Path>>(
+        &self,
+        words: &[Word],
+        output_path: P,
+    ) -> Result<usize> {
+        let mut seen = HashSet::new();
+        let mut content = String::new();
+        for word in words {
+            let word = text::normalize_unicode(&word.word).split_whitespace().collect::<Vec<_>>().join(" ");
+            if word.chars().any(text::is_letter) && seen.insert(text::normalize_key(&word)) {
+                content.push_str(&word);
+                content.push('\n');
+            }
+        }
+        
+        fs::write(output_path, content)?;
+        Ok(seen.len())
+    }
+    
+    /// 保存单词的全部字段为 JSON 数组，供其他工具读取；短语不包含在内
+    pub fn save_json<P: AsRef<Path>>(
+        &self,
+        words: &[Word],
+        output_path: P,
+    ) -> Result<()> {
+        fs::write(output_path, serde_json::to_string_pretty(words)?)?;
+        Ok(())
+    }
+    
+    /// 保存单词的全部字段为 JSON Lines（每行一个单词），便于逐行处理；短语不包含在内
+    pub fn save_jsonl<P: AsRef<Path>>(
+        &self,
+        words: &[Word],
+        output_path: P,
+    ) -> Result<()> {
+        let mut content = String::new();
+        for word in words {
+            content.push_str(&serde_json::to_string(word)?);
+            content.push('\n');
+        }
+        
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+    
+    /// 保存为 Anki 牌组，章节作为子牌组和标签，见 [`apkg::write_deck`](crate::apkg::write_deck)；短语不包含在内
+    #[cfg(feature = "apkg")]
+    pub fn save_apkg<P: AsRef<Path>>(
+        &self,
+        words: &[Word],
+        metadata: &crate::BookMetadata,
+        output_path: P,
+    ) -> Result<()> {
+        fs::write(output_path, crate::apkg::write_deck(words, metadata)?)?;
+        Ok(())
+    }
+    
+    /// 保存为 Excel 工作簿，每章一个工作表，见 [`xlsx::write_workbook`](crate::xlsx::write_workbook)；短语不包含在内
+    #[cfg(feature = "xlsx")]
+    pub fn save_xlsx<P: AsRef<Path>>(
+        &self,
+        words: &[Word],
+        output_path: P,
+    ) -> Result<()> {
+        fs::write(output_path, crate::xlsx::write_workbook(words)?)?;
+        Ok(())
+    }
+    
+    /// 保存完整信息（单词+词义）
+    pub fn save_with_meaning<P: AsRef<Path>>(
+        &self,
+        result: &ExtractResult,
+        output_path: P,
+    ) -> Result<()> {
+        let mut content = String::new();
+        
+        content.push_str(&"=".repeat(50));
+        content.push_str("\n单词列表\n");
+        content.push_str(&"=".repeat(50));
+        content.push_str("\n\n");
+        content.push_str(&self.word_lines(&result.words));
+        
+        if self.include_phrases && !result.phrases.is_empty() {
+            content.push_str(&phrase_header());
+            content.push_str(&phrase_lines(&result.phrases));
+        }
+        
+        fs::write(output_path, content)?;
+        Ok(())
+    }
+    
+    /// with_meaning 格式的单词行，启用词性分组时按词性分组
+    fn word_lines(&self, words: &[Word]) -> String {
+        let mut content = String::new();
+        let groups = if self.pos_groups {
+            crate::pos::group_by_pos(words)
+        } else {
+            vec![(None, words.iter().collect())]
+        };
+        for (i, (pos, words)) in groups.iter().enumerate() {
+            if self.pos_groups {
+                if i > 0 {
+                    content.push('\n');
+                }
+                let label = pos.map_or("未标注词性".to_string(), |p| format!("{} {}", p, p.abbreviation()));
+                content.push_str(&format!("【{}】（{}）\n", label, words.len()));
+            }
+            for word in words {
+                let phonetic = word.phonetic.as_deref().map(|p| format!(" {}", p)).unwrap_or_default();
+                let level = word.level.map(|l| format!(" [{}]", l)).unwrap_or_default();
+                content.push_str(&format!(
+                    "{}. {}{}{}\t{}{}\n",
+                    word.number, word.word, phonetic, level, word.meaning, source_suffix(&word.source)
+                ));
+                if let Some(example) = &word.example {
+                    content.push_str(&format!("    例：{}\n", example));
+                }
+            }
+        }
+        content
+    }
+    
+    /// 把单词追加到 [`save_words_only`](Self::save_words_only) 保存的文件，文件不存在时直接保存
+    pub fn append_words_only<P: AsRef<Path>>(&self, words: &[Word], output_path: P) -> Result<()> {
+        let path = output_path.as_ref();
+        if !path.exists() {
+            return self.save_words_only(words, path);
+        }
+        let existing = fs::read_to_string(path)?;
+        let separator = if existing.is_empty() || existing.ends_with('\n') { "" } else { "\n" };
+        let lines: Vec<&str> = words.iter().map(|w| w.word.as_str()).collect();
+        append(path, &format!("{}{}", separator, lines.join("\n")))
+    }
+    
+    /// 把单词追加到 [`save_json`](Self::save_json) 保存的文件，文件不存在时直接保存
+    pub fn append_json<P: AsRef<Path>>(&self, words: &[Word], output_path: P) -> Result<()> {
+        let path = output_path.as_ref();
+        if !path.exists() {
+            return self.save_json(words, path);
+        }
+        let mut existing = ExtractResult::load(path)?.words;
+        existing.extend(words.iter().cloned());
+        self.save_json(&existing, path)
+    }
+    
+    /// 把单词追加到 [`save_jsonl`](Self::save_jsonl) 保存的文件，文件不存在时直接保存
+    pub fn append_jsonl<P: AsRef<Path>>(&self, words: &[Word], output_path: P) -> Result<()> {
+        let path = output_path.as_ref();
+        if !path.exists() {
+            return self.save_jsonl(words, path);
+        }
+        let mut content = String::new();
+        for word in words {
+            content.push_str(&serde_json::to_string(word)?);
+            content.push('\n');
+        }
+        append(path, &content)
+    }
+    
+    /// 把单词和短语追加到 [`save_with_meaning`](Self::save_with_meaning) 保存的文件，文件不存在时直接保存
+    ///
+    /// 单词插入到单词列表末尾，短语插入到短语列表末尾（没有时新建），
+    /// 两者都在文件末尾的报告段落（易混词等 `## ` 段落）之前
+    pub fn append_with_meaning<P: AsRef<Path>>(&self, result: &ExtractResult, output_path: P) -> Result<()> {
+        let path = output_path.as_ref();
+        if !path.exists() {
+            return self.save_with_meaning(result, path);
+        }
+        let mut content = fs::read_to_string(path)?;
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        let header = phrase_header();
+        // 报告段落的开头（`from` 之后第一个 `## ` 行及其前面的空行），没有时为文件末尾
+        let sections_at = |content: &str, from: usize| match content[from..].find("\n## ") {
+            Some(i) if content[..from + i + 1].ends_with("\n\n") => from + i,
+            Some(i) => from + i + 1,
+            None => content.len(),
+        };
+        
+        let words_end = content.find(&header).unwrap_or_else(|| sections_at(&content, 0));
+        content.insert_str(words_end, &self.word_lines(&result.words));
+        if self.include_phrases && !result.phrases.is_empty() {
+            match content.find(&header) {
+                Some(at) => {
+                    let end = sections_at(&content, at + header.len());
+                    content.insert_str(end, &phrase_lines(&result.phrases));
+                }
+                None => {
+                    let end = sections_at(&content, 0);
+                    content.insert_str(end, &format!("{}{}", header, phrase_lines(&result.phrases)));
+                }
+            }
+        }
+        
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// with_meaning 格式中短语列表的标题
+fn phrase_header() -> String {
+    format!("\n{}\n短语列表\n{}\n\n", "=".repeat(50), "=".repeat(50))
+}
+
+/// with_meaning 格式的短语行
+fn phrase_lines(phrases: &[Phrase]) -> String {
+    phrases
+        .iter()
+        .map(|phrase| format!("{}. {}\t{}{}\n", phrase.number, phrase.phrase, phrase.meaning, source_suffix(&phrase.source)))
+        .collect()
+}
+
+/// 在文件末尾追加内容
+fn append(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+    let mut file = fs::OpenOptions::new().append(true).open(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// 读取 Markdown 管道表格的所有行（含表头），每行为去除首尾空白的单元格文本
+///
+/// 表格以表头行和紧随其后的分隔行（`|---|:---:|`）开始，到第一个不含 `|` 的行结束
+fn pipe_table_rows(content: &str) -> Vec<Vec<String>> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut rows = Vec::new();
+    let mut i = 0;
+    
+    while i < lines.len() {
+        let is_table_start = lines[i].contains('|')
+            && lines.get(i + 1).is_some_and(|next| is_delimiter_row(next));
+        if !is_table_start {
+            i += 1;
+            continue;
+        }
+        
+        rows.push(split_pipe_row(lines[i]));
+        i += 2;
+        while i < lines.len() && lines[i].contains('|') {
+            rows.push(split_pipe_row(lines[i]));
+            i += 1;
+        }
+    }
+    
+    rows
+}
+
+/// 拆分管道表格的一行，支持 `\|` 转义
+fn split_pipe_row(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(rest) if !rest.ends_with('\\') => rest,
+        _ => line,
+    };
+    
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                cell.push('|');
+                chars.next();
+            }
+            '|' => cells.push(core::mem::take(&mut cell).trim().to_string()),
+            _ => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+    
+    cells
+}
+
+/// 是否为管道表格的分隔行，如 `| --- | :---: |`
+fn is_delimiter_row(line: &str) -> bool {
+    let cells = split_pipe_row(line);
+    line.contains('-')
+        && cells.iter().all(|cell| {
+            let cell = cell.trim_start_matches(':').trim_end_matches(':');
+            !cell.is_empty() && cell.chars().all(|c| c == '-')
+        })
+}
+
+/// 保存时附在释义后的出处
+fn source_suffix(source: &Option<String>) -> String {
+    source.as_ref().map(|s| format!("\t（{}）", s)).unwrap_or_default()
+}
+
+/// Kindle 标注中作为短语提取的最大词数，更长的标注视为句子并跳过
+pub const MAX_CLIPPING_WORDS: usize = 4;
+
+/// 是否为 SRT/VTT 字幕文件
+pub fn is_subtitle(path: &Path) -> bool {
+    has_extension(path, &["srt", "vtt"])
+}
+
+/// 是否为 Excel/ODS 工作簿
+#[cfg(feature = "xlsx")]
+pub fn is_spreadsheet(path: &Path) -> bool {
+    has_extension(path, &["xlsx", "xlsm", "xls", "ods"])
+}
+
+/// 下载网页的超时时间，见 [`WordExtractor::extract_from_url`]
+#[cfg(feature = "http")]
+const URL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// 输入是否为 `http://` 或 `https://` 网址
+pub fn is_url(input: &str) -> bool {
+    let input = input.trim_start();
+    ["http://", "https://"].iter().any(|scheme| input.get(..scheme.len()).is_some_and(|s| s.eq_ignore_ascii_case(scheme)))
+}
+
+/// 路径是否包含通配符
+pub fn is_glob(path: &Path) -> bool {
+    path.to_str().is_some_and(|p| p.contains(['*', '?']))
+}
+
+/// 批量提取时的文件过滤规则，通配符相对于遍历起点（输入目录或通配符前的目录）匹配
+#[derive(Debug, Clone, Default)]
+pub struct BatchFilter {
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl BatchFilter {
+    /// 编译包含和排除的通配符，如 `**/*.md`、`**/answers/**`
+    ///
+    /// 指定了包含规则时只提取匹配任一规则的文件（不限于 Markdown），匹配任一排除规则的文件被跳过
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        let compile = |patterns: &[String]| patterns.iter().map(|p| glob_regex(&p.replace('\\', "/"))).collect::<Result<Vec<_>>>();
+        Ok(Self { include: compile(include)?, exclude: compile(exclude)? })
+    }
+    
+    /// 相对路径（以 `/` 分隔）是否通过过滤
+    fn matches(&self, relative: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|r| r.is_match(relative)))
+            && !self.exclude.iter().any(|r| r.is_match(relative))
+    }
+}
+
+/// 批量提取的文件列表，见 [`WordExtractor::extract_from_dir`]
+///
+/// 目录中默认只取 Markdown 文件，`filter` 指定了包含规则时按规则选取
+pub fn batch_files(path: &Path, filter: &BatchFilter) -> Result<Vec<PathBuf>> {
+    let (base, matcher) = if is_glob(path) {
+        let pattern = path.to_str().unwrap_or_default().replace('\\', "/");
+        // 通配符之前的目录作为遍历起点
+        let literal = &pattern[..pattern.find(['*', '?']).unwrap_or(pattern.len())];
+        let base = literal.rfind('/').map_or("", |i| &literal[..i + 1]);
+        (PathBuf::from(if base.is_empty() { "." } else { base }), Some(glob_regex(&pattern[base.len()..])?))
+    } else if path.is_dir() {
+        (path.to_path_buf(), None)
+    } else {
+        return Err(Error::Other(format!("不是目录或通配符: {:?}", path)));
+    };
+    
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(&base)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|file| {
+            let Some(relative) = file.strip_prefix(&base).ok().and_then(|rel| rel.to_str()) else { return false };
+            let relative = relative.replace('\\', "/");
+            let selected = match &matcher {
+                Some(regex) => regex.is_match(&relative),
+                None => !filter.include.is_empty() || has_extension(file, &["md", "markdown"]),
+            };
+            selected && filter.matches(&relative)
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// 把通配符转换为正则：`**/` 匹配任意层目录，`*` 和 `?` 不跨越目录
+fn glob_regex(pattern: &str) -> Result<Regex> {
+    let mut regex = String::from("^");
+    let mut rest = pattern;
+    while let Some(c) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("**/") {
+            regex.push_str("(?:.*/)?");
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix("**") {
+            regex.push_str(".*");
+            rest = after;
+            continue;
+        }
+        match c {
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    regex.push('$');
+    Regex::new(&regex).map_err(|e| Error::Parse(format!("无效的通配符: {}", e)))
+}
+
+/// 扩展名（忽略大小写）是否在列表中
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|ext| e.eq_ignore_ascii_case(ext)))
+}
+
+/// 为没有行号的单词查找所在的行（从 1 开始）
+///
+/// 从上一个单词所在的行开始向后查找整词匹配的行，找不到时从头查找，
+/// 因此重复出现的单词按表格顺序对应到各自的行
+fn locate_lines(words: &mut [Word], content: &str) {
+    let lines: Vec<Vec<String>> = content
+        .lines()
+        .map(|line| {
+            let line = text::normalize_unicode(line);
+            text::tokenize(&line)
+                // 所有格在提取时可能被去掉，按撇号前的部分也能匹配
+                .flat_map(|t| [t, t.split(['\'', '\u{2019}']).next().unwrap_or(t)])
+                .map(text::normalize_key)
+                .collect()
+        })
+        .collect();
+    let mut cursor = 0;
+    for word in words.iter_mut().filter(|w| w.line_number.is_none()) {
+        let key = text::normalize_key(&word.word);
+        if let Some(i) = (cursor..lines.len()).chain(0..cursor).find(|&i| lines[i].contains(&key)) {
+            word.line_number = Some(i + 1);
+            cursor = i;
+        }
+    }
+}
+
+/// 围栏代码块（```` ``` ```` 或 `~~~`）中的内容，按出现顺序排列；没有结束标记的代码块到文末为止
+fn fenced_blocks(content: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(&str, String)> = None;
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        let fence = ["```", "~~~"].into_iter().find(|f| trimmed.starts_with(f));
+        match (&mut current, fence) {
+            (Some((open, _)), Some(fence)) if *open == fence => {
+                blocks.extend(current.take().map(|(_, block)| block));
+            }
+            (Some((_, block)), _) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (None, Some(fence)) => current = Some((fence, String::new())),
+            (None, None) => {}
+        }
+    }
+    blocks.extend(current.map(|(_, block)| block));
+    blocks
+}
+
+/// 反转义 HTML 实体 `&lt;`、`&gt;`、`&quot;`、`&#39;` 和 `&amp;`
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&#39;", "'").replace("&amp;", "&")
+}
+
+/// 是否为拉丁字母等西文字母（不含中日韩文字）
+fn is_latin_letter(c: char) -> bool {
+    c.is_alphabetic() && c < '\u{2e80}'
+}
+
+/// 按行号为单词标注所在章节：行号之前最近的一级或二级标题，代码块中的 `#` 不算标题
+fn locate_chapters(words: &mut [Word], content: &str) {
+    let mut headings: Vec<(usize, String)> = Vec::new();
+    let mut fenced = false;
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.starts_with("```") || line.starts_with("~~~") {
+            fenced = !fenced;
+            continue;
+        }
+        let Some(rest) = line.strip_prefix("## ").or_else(|| line.strip_prefix("# ")) else { continue };
+        let title = rest.trim().trim_end_matches('#').trim();
+        if !fenced && !title.is_empty() {
+            headings.push((i + 1, title.to_string()));
+        }
+    }
+    if headings.is_empty() {
+        return;
+    }
+    for word in words.iter_mut().filter(|w| w.chapter.is_none()) {
+        let Some(line) = word.line_number else { continue };
+        word.chapter = headings.iter().rev().find(|(start, _)| *start < line).map(|(_, title)| title.clone());
+    }
+}
+
+/// 解析 CSV/TSV：首行含制表符时按制表符分隔，否则按逗号；支持双引号包裹的单元格
+fn csv_rows(content: &str) -> Vec<Vec<String>> {
+    let content = content.trim_start_matches('\u{feff}');
+    let delimiter = if content.lines().next().is_some_and(|line| line.contains('\t')) { '\t' } else { ',' };
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.trim().is_empty() => {
+                cell.clear();
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => row.push(std::mem::take(&mut cell).trim().to_string()),
+            '\n' if !quoted => {
+                row.push(std::mem::take(&mut cell).trim().to_string());
+                rows.push(std::mem::take(&mut row));
+            }
+            '\r' if !quoted => {}
+            c => cell.push(c),
+        }
+    }
+    if !cell.is_empty() || !row.is_empty() {
+        row.push(cell.trim().to_string());
+        rows.push(row);
+    }
+    rows
+}
+
+/// 纯文本行中单词与释义之间的分隔符，按优先级排列
+const LIST_SEPARATORS: [&str; 7] = [" — ", "—", " – ", " - ", "：", ":", "\t"];
+
+/// 读取列表项和 `word — 释义` 形式的行，转换为 `[序号, 单词, 释义]`
+///
+/// 有序列表沿用原序号，其余行按出现顺序编号。
+/// 不是列表项的行必须带分隔符，避免把正文句子当作单词
+fn list_rows(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(['#', '|', '<', '>']) {
+            continue;
+        }
+        
+        let (number, item, is_list_item) = match split_list_marker(line) {
+            Some((number, item)) => (number, item, true),
+            None => (None, line, false),
+        };
+        
+        let (term, meaning) = match LIST_SEPARATORS.iter().find_map(|sep| item.split_once(sep)) {
+            Some((term, meaning)) => (term, meaning),
+            None if is_list_item => split_leading_term(item),
+            None => continue,
+        };
+        
+        let term = term.trim().trim_matches(['*', '_', '`']).trim();
+        if !is_list_term(term) {
+            continue;
+        }
+        
+        let number = number.unwrap_or_else(|| (rows.len() + 1).to_string());
+        rows.push(vec![number, term.to_string(), meaning.trim().to_string()]);
+    }
+    
+    rows
+}
+
+/// 用兜底正则逐行匹配，转换为 `[序号, 单词, 释义]`
+fn regex_rows(content: &str, pattern: &Regex) -> Vec<Vec<String>> {
+    let named = pattern.capture_names().flatten().any(|name| name == "word");
+    let (number, word, meaning) = match pattern.captures_len() - 1 {
+        _ if named => (None, 0, None),
+        n if n >= 3 => (Some(1), 2, Some(3)),
+        2 => (None, 1, Some(2)),
+        _ => (None, 1, None),
+    };
+    
+    let mut rows = Vec::new();
+    for line in content.lines() {
+        let Some(caps) = pattern.captures(line) else { continue };
+        let group = |index: Option<usize>, name: &str| {
+            let m = if named { caps.name(name) } else { index.and_then(|i| caps.get(i)) };
+            m.map(|m| m.as_str().trim().to_string())
+        };
+        let Some(term) = group(Some(word), "word").filter(|w| !w.is_empty()) else { continue };
+        let number = group(number, "number").unwrap_or_else(|| (rows.len() + 1).to_string());
+        rows.push(vec![number, term, group(meaning, "meaning").unwrap_or_default()]);
+    }
+    rows
+}
+
+/// 去掉列表标记（`-`、`*`、`+`、`1.`、`1)`），返回有序列表的序号和剩余内容
+fn split_list_marker(line: &str) -> Option<(Option<String>, &str)> {
+    if let Some(rest) = line.strip_prefix(['-', '*', '+']) {
+        return rest.starts_with(' ').then(|| (None, rest.trim_start()));
+    }
+    
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let rest = line[digits..].strip_prefix(['.', ')'])?;
+    (digits > 0 && rest.starts_with(' ')).then(|| (Some(line[..digits].to_string()), rest.trim_start()))
+}
+
+/// 没有分隔符的列表项：开头的英文部分为单词，其余为释义（如 `abandon 放弃`）
+fn split_leading_term(item: &str) -> (&str, &str) {
+    let end = item
+        .char_indices()
+        .find(|&(_, c)| !(text::is_letter(c) || matches!(c, ' ' | '\'' | '\u{2019}' | '-' | '.')))
+        .map_or(item.len(), |(i, _)| i);
+    item.split_at(end)
+}
+
+/// 是否像一个英文单词或短语
+fn is_list_term(term: &str) -> bool {
+    term.chars().next().is_some_and(text::is_letter)
+        && term.chars().all(|c| text::is_letter(c) || matches!(c, ' ' | '\'' | '\u{2019}' | '-' | '.'))
+}
+
+/// 合并单元格的最大跨度，防止异常的 rowspan/colspan 生成过多单元格
+#[cfg(feature = "html")]
+const MAX_SPAN: usize = 64;
+
+/// 单元格的 `rowspan` 或 `colspan`，缺失或无效时为 1
+#[cfg(feature = "html")]
+fn cell_span(cell: &ElementRef, attr: &str) -> usize {
+    cell.value().attr(attr).and_then(|v| v.trim().parse().ok()).unwrap_or(1).clamp(1, MAX_SPAN)
+}
+
+/// 单元格的文本，`<br>` 转换为换行，供 [`ColumnMapping::with_split`] 拆分
+#[cfg(feature = "html")]
+fn cell_text(cell: &ElementRef) -> String {
+    let mut text = String::new();
+    for node in cell.descendants() {
+        match node.value() {
+            scraper::Node::Text(t) => text.push_str(t),
+            scraper::Node::Element(e) if e.name() == "br" => text.push('\n'),
+            _ => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// 读取 HTML 表格的所有行，每行为去除首尾空白的单元格文本
+///
+/// 合并单元格按所占位置展开，保证各行的列对齐：`rowspan` 的文本复制到下面的行，
+/// `colspan` 多占的列为空
+#[cfg(feature = "html")]
+fn html_table_rows(content: &str) -> Result<Vec<Vec<String>>> {
+    /// 补上从上面的行延伸到当前列的单元格
+    fn fill_spans(cells: &mut Vec<String>, pending: &mut [Option<(usize, String)>]) {
+        loop {
+            let col = cells.len();
+            let Some(Some((remaining, text))) = pending.get_mut(col) else { break };
+            cells.push(text.clone());
+            *remaining -= 1;
+            if *remaining == 0 {
+                pending[col] = None;
+            }
+        }
+    }
+    
+    let document = Html::parse_document(content);
+    
+    // 嵌套表格的行也只出现一次，按文档顺序读取
+    let row_selector = Selector::parse("table tr")
+        .map_err(|e| Error::Parse(format!("行选择器错误: {:?}", e)))?;
+    
+    let mut rows = Vec::new();
+    // 各表格中向下延伸的单元格：列 → (剩余行数, 文本)
+    let mut spans: HashMap<_, Vec<Option<(usize, String)>>> = HashMap::new();
+    for row in document.select(&row_selector) {
+        let table = row.ancestors().filter_map(ElementRef::wrap).find(|e| e.value().name() == "table").map(|t| t.id());
+        let pending = spans.entry(table).or_default();
+        let mut cells = Vec::new();
+        // 只取本行的直接单元格，嵌套表格的单元格由其自身的行处理
+        for cell in row.children().filter_map(ElementRef::wrap).filter(|cell| cell.value().name() == "td") {
+            fill_spans(&mut cells, pending);
+            let text = cell_text(&cell);
+            let rowspan = cell_span(&cell, "rowspan");
+            for i in 0..cell_span(&cell, "colspan") {
+                let text = if i == 0 { text.clone() } else { String::new() };
+                if rowspan > 1 {
+                    if pending.len() <= cells.len() {
+                        pending.resize(cells.len() + 1, None);
+                    }
+                    pending[cells.len()] = Some((rowspan - 1, text.clone()));
+                }
+                cells.push(text);
+            }
+        }
+        // 行尾延伸下来的单元格，中间空缺的列为空
+        while pending.iter().skip(cells.len()).any(Option::is_some) {
+            fill_spans(&mut cells, pending);
+            if pending.get(cells.len()).is_some_and(Option::is_none) {
+                cells.push(String::new());
+            }
+        }
+        rows.push(cells);
+    }
+    
+    Ok(rows)
+}
+
+/// 未启用 `html` 特性时不解析 HTML 表格
+#[cfg(not(feature = "html"))]
+fn html_table_rows(_content: &str) -> Result<Vec<Vec<String>>> {
+    log::warn!("未启用 html 特性，跳过 HTML 表格");
+    Ok(Vec::new())
+}
+
+/// 读取 HTML 中所有 `<li>` 列表项的文本，多个空白合并为一个空格；
+/// 嵌套列表的文本也包含在外层列表项中，由 [`list_rows`] 按格式筛选
+#[cfg(all(feature = "http", feature = "html"))]
+fn html_list_items(content: &str) -> Vec<String> {
+    let document = Html::parse_document(content);
+    let selector = Selector::parse("li").expect("有效的选择器");
+    document
+        .select(&selector)
+        .map(|item| item.text().collect::<String>().split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+/// 未启用 `html` 特性时不解析 HTML 列表
+#[cfg(all(feature = "http", not(feature = "html")))]
+fn html_list_items(_content: &str) -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(all(test, feature = "html"))]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_extract_from_markdown() {
+        let markdown = r#"
+<table>
+<tr><td>NO.</td><td>单词</td><td>释义</td></tr>
+<tr><td>1</td><td>hello</td><td>你好</td></tr>
+<tr><td>2</td><td>world</td><td>世界</td></tr>
+</table>
+"#;
+        
+        let extractor = WordExtractor::new(false, false);
+        let result = extractor.extract_from_markdown(markdown).unwrap();
+        
+        assert_eq!(result.words.len(), 2);
+        assert_eq!(result.words[0].word, "hello");
+        assert_eq!(result.words[1].word, "world");
+    }
+    
+    #[test]
+    fn test_merged_cells() {
+        let markdown = r#"
+<table>
+<tr><td>NO.</td><td>单词</td><td>释义</td></tr>
+<tr><td rowspan="2">1</td><td>abandon</td><td>放弃</td></tr>
+<tr><td>abandonment</td><td>放弃</td></tr>
+<tr><td>2</td><td>abroad</td><td rowspan="2">在国外</td></tr>
+<tr><td>3</td><td>overseas</td></tr>
+<tr><td colspan="2">4 ability</td><td>能力</td></tr>
+<tr><td>5</td><td>能力</td><td>ability</td></tr>
+<tr><td></td><td></td><td>补充释义</td></tr>
+</table>
+"#;
+        let rows = html_table_rows(markdown).unwrap();
+        assert_eq!(rows[2], ["1", "abandonment", "放弃"]);
+        assert_eq!(rows[4], ["3", "overseas", "在国外"]);
+        assert_eq!(rows[5], ["4 ability", "", "能力"]);
+        
+        let result = WordExtractor::new(false, false).extract_from_markdown(markdown).unwrap();
+        let words: Vec<_> = result.words.iter().map(|w| (w.number.as_str(), w.word.as_str(), w.meaning.as_str())).collect();
+        assert_eq!(words, [("1", "abandon", "放弃"), ("1", "abandonment", "放弃"), ("2", "abroad", "在国外"), ("3", "overseas", "在国外")]);
+        let reasons: Vec<_> = result.skipped.iter().map(|s| s.reason.as_str()).collect();
+        assert_eq!(reasons, ["缺少单词", "单词列没有英文字母，可能是合并单元格导致列错位", "缺少序号和单词"]);
+        
+        let report = crate::Report::new(&crate::BookMetadata::default()).with_extract(&result).to_markdown();
+        assert!(report.contains("## 可疑的表格行（3）"));
+    }
+    
+    #[test]
+    fn test_fenced_tables() {
+        let markdown = "```html\n<table><tr><td>1</td><td>abandon</td><td>放弃</td></tr></table>\n```\n\n\
+            ~~~\n| NO. | 单词 | 词义 |\n|---|---|---|\n| 2 | ability | 能力 |\n~~~\n\n\
+            ```\n&lt;table&gt;&lt;tr&gt;&lt;td&gt;3&lt;/td&gt;&lt;td&gt;abroad&lt;/td&gt;&lt;td&gt;在国外 &amp; 海外&lt;/td&gt;&lt;/tr&gt;&lt;/table&gt;\n";
+        let result = WordExtractor::new(true, false).extract_from_markdown(markdown).unwrap();
+        let words: Vec<_> = result.words.iter().map(|w| (w.word.as_str(), w.meaning.as_str())).collect();
+        assert_eq!(words, [("abandon", "放弃"), ("ability", "能力"), ("abroad", "在国外 & 海外")]);
+        assert_eq!(fenced_blocks(markdown).len(), 3);
+    }
+    
+    #[test]
+    fn test_line_numbers() {
+        let markdown = "# Unit 1\n\n| NO. | 单词 | 词义 |\n|---|---|---|\n| 1 | abandon | 放弃 |\n| 2 | teacher's | 老师的 |\n\n| NO. | 单词 | 词义 |\n|---|---|---|\n| 1 | abandon | 放弃 |\n";
+        let result = WordExtractor::new(false, false).extract_from_markdown(markdown).unwrap();
+        let lines: Vec<_> = result.words.iter().map(|w| (w.word.as_str(), w.line_number)).collect();
+        assert_eq!(lines, [("abandon", Some(5)), ("teacher", Some(6)), ("abandon", Some(10))]);
+        assert_eq!(result.words[1].location(Some("unit1.md")).as_deref(), Some("unit1.md:6"));
+    }
+    
+    #[test]
+    fn test_chapters() {
+        let table = |word: &str| format!("| NO. | 单词 | 词义 |\n|---|---|---|\n| 1 | {} | 释义 |\n", word);
+        let markdown = format!(
+            "{}\n# Book 1\n## Unit 1 ##\n\n{}\n```\n# 代码\n```\n{}\n## Unit 2\n{}",
+            table("preface"), table("abandon"), table("ability"), table("abroad")
+        );
+        let result = WordExtractor::new(true, false).extract_from_markdown(&markdown).unwrap();
+        let chapters: Vec<_> = result.words.iter().map(|w| w.chapter.as_deref()).collect();
+        assert_eq!(chapters, [None, Some("Unit 1"), Some("Unit 1"), Some("Unit 2")]);
+        
+        let volumes = result.split_by_chapter();
+        let sizes: Vec<_> = volumes.iter().map(|(chapter, volume)| (*chapter, volume.total_words)).collect();
+        assert_eq!(sizes, [(None, 1), (Some("Unit 1"), 2), (Some("Unit 2"), 1)]);
+        
+        let book = crate::Book::from_extract("book", crate::BookMetadata::default(), &result).unwrap();
+        let titles: Vec<_> = book.chapters().iter().map(|(title, entries)| (*title, entries.len())).collect();
+        assert_eq!(titles, [("Unit 1", 2), ("Unit 2", 1)]);
+    }
+    
+    #[test]
+    fn test_extract_mode_serde() {
+        let mode: ExtractMode = serde_json::from_str("\"with_meaning\"").unwrap();
+        assert_eq!(mode, ExtractMode::WithMeaning);
+        assert_eq!(serde_json::to_string(&ExtractMode::Full).unwrap(), "\"full\"");
+        assert!(serde_json::from_str::<ExtractMode>("\"wordsonly\"").is_err());
+    }
+    
+    #[test]
+    fn test_nested_table_rows_not_duplicated() {
+        let markdown = "<table><tr><td>1</td><td>hello</td><td>\
+            <table><tr><td>2</td><td>world</td><td>世界</td></tr></table>\
+            </td></tr></table>";
+        
+        let extractor = WordExtractor::new(false, false);
+        let result = extractor.extract_from_markdown(markdown).unwrap();
+        
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["hello", "world"]);
+    }
+    
+    #[test]
+    fn test_extract_from_pipe_table() {
+        let markdown = "\
+| NO. | 单词 | 释义 |
+| :-- | ---- | ---: |
+| 1 | hello | 你好 |
+| 2 | take off | 起飞 |
+| 3 | either\\|or | 或者 |
+
+<table><tr><td>4</td><td>world</td><td>世界</td></tr></table>
+";
+        
+        let extractor = WordExtractor::new(true, true);
+        let result = extractor.extract_from_markdown(markdown).unwrap();
+        
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["world", "hello", "either|or"]);
+        assert_eq!(result.phrases[0].phrase, "take off");
+        assert_eq!(result.words[1].meaning, "你好");
+    }
+    
+    #[test]
+    fn test_sample() {
+        // 单词末尾不带数字，以免被当作脚注标记清理
+        let rows = (1..=20u8).map(|i| vec![i.to_string(), format!("word{}", char::from(b'a' + i)), String::new()]);
+        let result = WordExtractor::new(true, false).extract_from_rows(rows);
+        
+        let mut first = result.clone();
+        first.sample(3, None);
+        let numbers: Vec<_> = first.words.iter().map(|w| w.number.as_str()).collect();
+        assert_eq!(numbers, ["1", "2", "3"]);
+        
+        let (mut a, mut b) = (result.clone(), result.clone());
+        a.sample(5, Some(42));
+        b.sample(5, Some(42));
+        let numbers = |r: &ExtractResult| r.words.iter().map(|w| w.number.parse::<usize>().unwrap()).collect::<Vec<_>>();
+        assert_eq!(numbers(&a), numbers(&b));
+        assert_eq!(a.total_words, 5);
+        assert!(numbers(&a).windows(2).all(|w| w[0] < w[1]));
+    }
+    
+    #[test]
+    fn test_extract_from_reader() {
+        let markdown = "| NO. | 单词 | 释义 |\n|---|---|---|\n| 1 | hello | 你好 |\n";
+        let result = WordExtractor::new(true, false).extract_from_reader(markdown.as_bytes()).unwrap();
+        assert_eq!(result.words[0].word, "hello");
+    }
+    
+    #[test]
+    fn test_strict_mode() {
+        let markdown = "| NO. | 单词 | 释义 |\n|---|---|---|\n| 1 | hello | 你好 |\n| 2a | world | 世界 |\n| 3 |  | 空 |\n";
+        
+        let result = WordExtractor::new(true, false).extract_from_markdown(markdown).unwrap();
+        assert_eq!(result.total_words, 1);
+        let reasons: Vec<_> = result.skipped.iter().map(|s| (s.row.as_str(), s.reason.as_str())).collect();
+        assert_eq!(reasons, [("2a | world | 世界", "序号不是数字"), ("3 |  | 空", "缺少单词")]);
+        
+        let strict = WordExtractor::new(true, false).with_strict(true);
+        let error = strict.extract_from_markdown(markdown).unwrap_err().to_string();
+        assert!(error.contains("2 行未能提取") && error.contains("2a | world | 世界（序号不是数字）"));
+        let fallback = WordExtractor::fallback_pattern(DEFAULT_FALLBACK_PATTERN).unwrap();
+        assert!(strict.with_fallback(Some(fallback)).extract_from_markdown("1. abandon 放弃").is_err());
+    }
+    
+    #[test]
+    fn test_fuzz_seeds_do_not_panic() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../fuzz/seeds/extract_markdown");
+        let extractor = WordExtractor::new(true, true);
+        for entry in fs::read_dir(dir).unwrap() {
+            let content = fs::read(entry.unwrap().path()).unwrap();
+            let _ = extractor.extract_from_markdown(&String::from_utf8_lossy(&content));
+        }
+    }
+    
+    #[test]
+    fn test_extract_from_lists() {
+        let markdown = "\
+# Unit 1
+
+- abandon — 放弃
+* ability: 能力
+3. abroad 在国外
+4) take off - 起飞
+absorb：吸收
+This sentence is not a word.
+";
+        
+        let result = WordExtractor::new(true, true).extract_from_markdown(markdown).unwrap();
+        assert!(result.words.is_empty());
+        
+        let result = WordExtractor::new(true, true).with_lists(true).extract_from_markdown(markdown).unwrap();
+        let words: Vec<_> = result.words.iter().map(|w| (w.number.as_str(), w.word.as_str(), w.meaning.as_str())).collect();
+        assert_eq!(words, [("1", "abandon", "放弃"), ("2", "ability", "能力"), ("3", "abroad", "在国外"), ("5", "absorb", "吸收")]);
+        assert_eq!(result.phrases[0].phrase, "take off");
+    }
+    
+    #[test]
+    fn test_regex_fallback() {
+        let markdown = "# Unit 1\n\n1. abandon 放弃\n2、take off 起飞\n正文不会被匹配\n";
+        
+        let extractor = WordExtractor::new(true, true)
+            .with_fallback(Some(WordExtractor::fallback_pattern(DEFAULT_FALLBACK_PATTERN).unwrap()));
+        let result = extractor.extract_from_markdown(markdown).unwrap();
+        assert_eq!((result.words[0].number.as_str(), result.words[0].meaning.as_str()), ("1", "放弃"));
+        assert_eq!(result.phrases[0].phrase, "take off");
+        
+        let custom = WordExtractor::fallback_pattern(r"^(?P<word>[a-z]+) = (?P<meaning>.+)$").unwrap();
+        let result = WordExtractor::new(true, false).with_fallback(Some(custom)).extract_from_markdown("abroad = 在国外").unwrap();
+        assert_eq!((result.words[0].number.as_str(), result.words[0].word.as_str()), ("1", "abroad"));
+        assert!(WordExtractor::fallback_pattern(r"^\d+$").is_err());
+    }
+    
+    #[test]
+    fn test_extract_by_frequency() {
+        let content = "<p>The <b>climate</b> is changing.</p>\nClimate change affects the ocean; the OCEAN warms.\nclimate it's";
+        
+        let result = WordExtractor::new(true, false).extract_by_frequency(content, 2);
+        let words: Vec<_> = result.words.iter().map(|w| (w.number.as_str(), w.word.as_str(), w.frequency, w.line_number)).collect();
+        assert_eq!(words, [("1", "climate", Some(3), Some(1)), ("2", "ocean", Some(2), Some(2))]);
+    }
+
+    #[test]
+    fn test_header_markers() {
+        let rows = [["No.", "Word", "Meaning"], ["1", "word", "单词"], ["Index", "Term", "Gloss"], ["2", "abandon", "放弃"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let result = WordExtractor::new(true, false).extract_from_rows(rows.clone());
+        assert_eq!(result.total_words, 2);
+        assert_eq!(result.skipped.len(), 1);
+        
+        let markers = WordExtractor::header_markers(&["Term".to_string()]);
+        let result = WordExtractor::new(true, false).with_header_markers(markers).extract_from_rows(rows);
+        assert_eq!(result.words[0].word, "word");
+        assert!(result.skipped.is_empty());
+    }
+    
+    #[test]
+    fn test_word_policy() {
+        let rows = [["1", "well-known", "著名的"], ["2", "teacher's", "老师的"], ["3", "color/colour", "颜色"], ["4", "it's", "它是"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let result = WordExtractor::new(true, false).extract_from_rows(rows.clone());
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["well-known", "teacher", "color", "colour", "it's"]);
+        
+        let legacy: WordPolicy = "none".parse().unwrap();
+        let result = WordExtractor::new(true, true).with_policy(legacy).extract_from_rows(rows);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["teacher's", "color/colour", "it's"]);
+        assert_eq!(result.phrases[0].phrase, "well-known");
+        assert!("hyphen,typo".parse::<WordPolicy>().is_err());
+        
+        // OCR 产生的连字、弯撇号和零宽字符
+        let rows = [["1", "ﬁnd\u{200b}", "发现"], ["2", "students\u{2019}", "学生的"]].map(|row| row.map(str::to_string).to_vec());
+        let result = WordExtractor::new(true, false).extract_from_rows(rows);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["find", "students"]);
+        
+        // OCR 残留：末尾标点、脚注标记、括号注释
+        let rows = [["1", "abandon,", "放弃"], ["2", "ability1", "能力"], ["3", "abroad (adv.)", "在国外"]].map(|row| row.map(str::to_string).to_vec());
+        let result = WordExtractor::new(true, false).extract_from_rows(rows);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["abandon", "ability", "abroad"]);
+        assert_eq!(result.cleaned.len(), 3);
+    }
+    
+    #[test]
+    fn test_stopwords() {
+        let stopwords = WordExtractor::stopwords(Some("# 已掌握\nApple, banana\n"));
+        assert!(stopwords.contains("the") && stopwords.contains("apple") && stopwords.contains("banana"));
+
+        let rows = [["1", "The", "这"], ["2", "apple", "苹果"], ["3", "abandon", "放弃"], ["4", "of course", "当然"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let result = WordExtractor::new(true, true).with_stopwords(stopwords).extract_from_rows(rows);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["abandon"]);
+        assert_eq!(result.phrases.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn test_extract_from_url() {
+        use std::io::{BufRead, BufReader, Write};
+        
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/unit1.html", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            while reader.read_line(&mut request).unwrap() > 2 {
+                request.clear();
+            }
+            let body = "<table><tr><th>No.</th><th>Word</th><th>Meaning</th></tr><tr><td>1</td><td>abandon</td><td>放弃</td></tr></table>\
+                        <ul><li><b>ability</b> — 能力</li><li>Contact us</li></ul>";
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}", body.len(), body).unwrap();
+        });
+        
+        let result = WordExtractor::new(true, false).extract_from_url(&url).unwrap();
+        server.join().unwrap();
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["abandon", "ability"]);
+        assert!(is_url("HTTPS://example.com") && !is_url("notes/unit1.md"));
+    }
+    
+    #[test]
+    fn test_save_json_and_jsonl() {
+        let rows = [["1", "abandon", "放弃"], ["2", "ability", "能力"]].map(|row| row.map(str::to_string).to_vec());
+        let extractor = WordExtractor::new(true, false);
+        let result = extractor.extract_from_rows(rows);
+        let path = std::env::temp_dir().join(format!("bbdc_json_test_{}", std::process::id()));
+        
+        extractor.save_json(&result.words, &path).unwrap();
+        let words: Vec<Word> = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(words[1].meaning, "能力");
+        
+        extractor.save_jsonl(&result.words, &path).unwrap();
+        let content = fs::read_to_string(&path).unwrap();
+        let words: Vec<Word> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].word, "abandon");
+        fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    fn test_append_with_meaning() {
+        let dir = std::env::temp_dir().join(format!("bbdc_append_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("words.txt");
+        let extractor = WordExtractor::new(true, true);
+        let rows = |rows: &[[&str; 3]]| rows.iter().map(|row| row.map(str::to_string).to_vec()).collect::<Vec<_>>();
+        
+        extractor.append_with_meaning(&extractor.extract_from_rows(rows(&[["1", "abandon", "放弃"]])), &path).unwrap();
+        fs::write(&path, fs::read_to_string(&path).unwrap() + "\n## 易混词\n\n- a / b\n").unwrap();
+        extractor.append_with_meaning(&extractor.extract_from_rows(rows(&[["2", "ability", "能力"], ["3", "give up", "放弃"]])), &path).unwrap();
+        extractor.append_with_meaning(&extractor.extract_from_rows(rows(&[["4", "take off", "起飞"]])), &path).unwrap();
+        
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("1. abandon\t放弃\n2. ability\t能力\n\n====="));
+        assert!(content.ends_with("短语列表\n==================================================\n\n3. give up\t放弃\n4. take off\t起飞\n\n## 易混词\n\n- a / b\n"));
+        
+        let jsonl = dir.join("words.jsonl");
+        let words = extractor.extract_from_rows(rows(&[["1", "abandon", "放弃"], ["2", "ability", "能力"]])).words;
+        extractor.append_jsonl(&words[..1], &jsonl).unwrap();
+        extractor.append_jsonl(&words[1..], &jsonl).unwrap();
+        assert_eq!(fs::read_to_string(&jsonl).unwrap().lines().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_extract_result_round_trip() {
+        let rows = [["1", "abandon", "放弃"], ["2", "give up", "放弃"]].map(|row| row.map(str::to_string).to_vec());
+        let extractor = WordExtractor::new(true, true);
+        let mut result = extractor.extract_from_rows(rows);
+        result.words[0].phonetic = Some("/əˈbændən/".to_string());
+        let path = std::env::temp_dir().join(format!("bbdc_result_test_{}.json", std::process::id()));
+        
+        result.save(&path).unwrap();
+        let loaded = ExtractResult::load(&path).unwrap();
+        assert_eq!(loaded.words[0].phonetic, result.words[0].phonetic);
+        assert_eq!(loaded.total_phrases, 1);
+        
+        extractor.save_json(&result.words, &path).unwrap();
+        let loaded = ExtractResult::load(&path).unwrap();
+        assert_eq!((loaded.total_words, loaded.total_phrases), (1, 0));
+        
+        fs::write(&path, "{\"words\": 1}").unwrap();
+        assert!(ExtractResult::load(&path).is_err());
+        fs::remove_file(&path).unwrap();
+    }
+    
+    #[test]
+    fn test_word_filter() {
+        let rows = [["1", "a", "一个"], ["2", "abandon", "放弃"], ["3", "h2o", "水"], ["4", "café", "咖啡馆"], ["5", "incomprehensibilities", "费解"], ["6", "a lot", "许多"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let filter = WordFilter { min_length: 2, max_length: Some(20), ascii_only: true, reject_digits: true };
+        let result = WordExtractor::new(true, true).with_filter(filter).extract_from_rows(rows);
+        let words: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(words, ["abandon"]);
+        assert_eq!(result.phrases.len(), 1);
+        assert!(WordFilter::default().accepts("a"));
+        
+        let rows = [["1", "ox", "公牛"], ["2", "the", "这"], ["3", "abandon", "放弃"]].map(|row| row.map(str::to_string).to_vec());
+        let whitelist = WordExtractor::word_list("# 短词\nOX, the\n");
+        let filter = WordFilter { min_length: 3, ..WordFilter::default() };
+        let extractor = || WordExtractor::new(true, false).with_filter(filter.clone()).with_stopwords(WordExtractor::stopwords(Some("abandon the")));
+        assert!(extractor().extract_from_rows(rows.clone()).words.is_empty());
+        let words: Vec<_> = extractor().with_whitelist(whitelist).extract_from_rows(rows).words.into_iter().map(|w| w.word).collect();
+        assert_eq!(words, ["ox", "the"]);
+    }
+
+    #[test]
+    fn test_casing() {
+        let rows = [["1", "Abandon", "放弃"], ["2", "USA", "美国"], ["3", "DNA Test", "基因检测"]].map(|row| row.map(str::to_string).to_vec());
+        let extract = |casing| {
+            let result = WordExtractor::new(true, true).with_casing(casing).extract_from_rows(rows.clone());
+            let entries: Vec<String> = result.words.into_iter().map(|w| w.word).chain(result.phrases.into_iter().map(|p| p.phrase)).collect();
+            entries
+        };
+        assert_eq!(extract(Casing::Preserve), ["Abandon", "USA", "DNA Test"]);
+        assert_eq!(extract(Casing::LowercaseAll), ["abandon", "usa", "dna test"]);
+        assert_eq!(extract(Casing::LowercaseUnlessAcronym), ["abandon", "USA", "DNA test"]);
+    }
+
+    #[test]
+    fn test_save_bbdc_import() {
+        let rows = [["1", "\u{feff}abandon", "放弃"], ["2", "teacher\u{2019}s", "老师的"], ["3", "Abandon", "抛弃"], ["4", "—", "破折号"], ["5", "give  up", "放弃"]]
+            .map(|row| row.map(str::to_string).to_vec());
+        let extractor = WordExtractor::new(false, false).with_policy("none".parse().unwrap());
+        let result = extractor.extract_from_rows(rows);
+        
+        let path = std::env::temp_dir().join(format!("bbdc_import_test_{}.txt", std::process::id()));
+        let written = extractor.save_bbdc_import(&result.words, &path).unwrap();
+        let content = fs::read(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        assert_eq!(written, 2);
+        assert_eq!(String::from_utf8(content).unwrap(), "abandon\nteacher's\n");
+    }
+    
+    #[test]
+    fn test_extract_from_dir() {
+        let dir = std::env::temp_dir().join(format!("bbdc_batch_test_{}", std::process::id()));
+        fs::create_dir_all(dir.join("unit2")).unwrap();
+        fs::write(dir.join("unit1.md"), "| NO. | 单词 | 词义 |\n|---|---|---|\n| 1 | abandon | 放弃 |\n| 2 | ability | 能力 |\n").unwrap();
+        fs::write(dir.join("unit2/words.md"), "| NO. | 单词 | 词义 |\n|---|---|---|\n| 1 | Ability | 能力 |\n| 2 | abroad | 在国外 |\n").unwrap();
+        fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let result = WordExtractor::new(true, false).extract_from_dir(&dir).unwrap();
+        let words: Vec<_> = result.words.iter().map(|w| (w.number.as_str(), w.word.as_str())).collect();
+        assert_eq!(words, [("1", "abandon"), ("2", "ability"), ("3", "abroad")]);
+        assert!(result.words[2].source.as_deref().unwrap().ends_with("words.md"));
+
+        let result = WordExtractor::new(true, false).extract_from_dir(dir.join("**/words.md")).unwrap();
+        assert_eq!(result.total_words, 2);
+        
+        let filter = BatchFilter::new(&["**/*.md".to_string()], &["unit2/**".to_string()]).unwrap();
+        let result = WordExtractor::new(true, false).with_batch_filter(filter).extract_from_dir(&dir).unwrap();
+        assert_eq!(result.total_words, 2);
+        assert!(result.words.iter().all(|w| w.source.as_deref().unwrap().ends_with("unit1.md")));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extract_from_csv() {
+        let csv = "NO.,单词,词义\r\n1,abandon,\"v. 放弃, 抛弃\"\r\n2,\"say \"\"hi\"\"\",打招呼\r\n3,ability,能力";
+        let result = WordExtractor::new(true, false).extract_from_content(csv, InputFormat::Csv).unwrap();
+        let words: Vec<_> = result.words.iter().map(|w| (w.word.as_str(), w.meaning.as_str())).collect();
+        assert_eq!(words, [("abandon", "v. 放弃, 抛弃"), ("ability", "能力")]);
+        
+        let tsv = "abandon\t放弃\nability\t能力\n";
+        let result = WordExtractor::new(true, false)
+            .with_columns("word=1,meaning=2".parse().unwrap())
+            .extract_from_content(tsv, InputFormat::Csv)
+            .unwrap();
+        assert_eq!(result.words[1].number, "2");
+    }
+    
+    #[test]
+    fn test_extract_from_subtitles() {
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\nI don't know, Well-known WELL.\n\n2\n00:00:03,000 --> 00:00:04,000\nknow it\n";
+        
+        let result = WordExtractor::new(true, false).extract_from_subtitles(srt);
+        let words: Vec<_> = result.words.iter().map(|w| (w.word.as_str(), w.line_number)).collect();
+        assert_eq!(words, [("know", Some(3)), ("well", Some(3)), ("known", Some(3)), ("it", Some(7))]);
+        assert!(result.words[0].example.is_none());
+        
+        let result = WordExtractor::new(true, false).with_examples(true).extract_from_subtitles(srt);
+        assert_eq!(result.words[2].example.as_deref(), Some("I don't know, Well-known WELL."));
+    }
+    
+    #[test]
+    fn test_sort_and_split_by_difficulty() {
+        let mut result = WordExtractor::new(true, false).extract_from_subtitles("alpha beta gamma delta");
+        for (word, difficulty) in result.words.iter_mut().zip([Some(4), None, Some(1), Some(2)]) {
+            word.difficulty = difficulty;
+        }
+        
+        result.sort_by_difficulty();
+        let order: Vec<_> = result.words.iter().map(|w| w.word.as_str()).collect();
+        assert_eq!(order, ["gamma", "delta", "alpha", "beta"]);
+        
+        let (easy, hard) = result.split_by_difficulty(2);
+        assert_eq!((easy.total_words, hard.total_words), (2, 2));
+        assert_eq!(hard.words[1].word, "beta");
+    }
+    
+    #[cfg(feature = "xlsx")]
+    #[test]
+    fn test_extract_from_xlsx() {
+        use std::io::Write;
+        
+        fn sheet(rows: &[[&str; 3]]) -> String {
+            let rows: String = rows
+                .iter()
+                .enumerate()
+                .map(|(r, row)| {
+                    let cells: String = row
+                        .iter()
+                        .zip(["A", "B", "C"])
+                        .map(|(v, c)| format!(r#"<c r="{c}{}" t="inlineStr"><is><t>{v}</t></is></c>"#, r + 1))
+                        .collect();
+                    format!(r#"<row r="{}">{cells}</row>"#, r + 1)
+                })
+                .collect();
+            format!(r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>{rows}</sheetData></worksheet>"#)
+        }
+        
+        let files = [
+            ("[Content_Types].xml", r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/><Override PartName="/xl/worksheets/sheet1.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/><Override PartName="/xl/worksheets/sheet2.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/></Types>"#.to_string()),
+            ("_rels/.rels", r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#.to_string()),
+            ("xl/workbook.xml", r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets><sheet name="Unit1" sheetId="1" r:id="rId1"/><sheet name="Unit2" sheetId="2" r:id="rId2"/></sheets></workbook>"#.to_string()),
+            ("xl/_rels/workbook.xml.rels", r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet1.xml"/><Relationship Id="rId2" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet2.xml"/></Relationships>"#.to_string()),
+            ("xl/worksheets/sheet1.xml", sheet(&[["NO.", "单词", "释义"], ["1", "hello", "你好"]])),
+            ("xl/worksheets/sheet2.xml", sheet(&[["1", "world", "世界"], ["2", "take off", "起飞"]])),
+        ];
+        
+        let path = std::env::temp_dir().join(format!("bbdc_extract_test_{}.xlsx", std::process::id()));
+        let mut writer = zip::ZipWriter::new(fs::File::create(&path).unwrap());
+        for (name, content) in files {
+            writer.start_file(name, zip::write::FileOptions::default()).unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+        
+        let extractor = WordExtractor::new(true, true);
+        assert_eq!(extractor.extract_from_file(&path).unwrap().words[0].word, "hello");
+        
+        let result = extractor.extract_from_xlsx(&path, Some("Unit2")).unwrap();
+        assert_eq!(result.words[0].word, "world");
+        assert_eq!(result.phrases[0].phrase, "take off");
+        assert_eq!(extractor.extract_from_xlsx(&path, Some("2")).unwrap().total_words, 1);
+        assert!(extractor.extract_from_xlsx(&path, Some("Unit3")).is_err());
+        
+        fs::remove_file(path).ok();
+    }
+}
@@ -0,0 +1,182 @@
+//! Excel 导出模块
+//!
+//! 把单词写成 `.xlsx` 工作簿，每章一个工作表（以章节标题命名），列依次为序号、单词、释义、音标、例句；
+//! 前三列与读取 Excel 时的默认列映射一致，导出的工作簿可以直接再次提取
+
+use crate::{Error, Result, Word};
+use std::io::{Cursor, Write};
+
+/// 没有章节的单词所在的工作表
+const DEFAULT_SHEET: &str = "单词";
+
+/// Excel 工作表名称的最大字符数
+const MAX_SHEET_NAME: usize = 31;
+
+/// 表头
+const HEADER: [&str; 5] = ["序号", "单词", "释义", "音标", "例句"];
+
+/// 按章节分工作表导出单词，工作表按章节首次出现的顺序排列
+pub fn write_workbook(words: &[Word]) -> Result<Vec<u8>> {
+    let mut sheets: Vec<(Option<&str>, Vec<&Word>)> = Vec::new();
+    for word in words {
+        let chapter = word.chapter.as_deref();
+        match sheets.iter_mut().find(|(c, _)| *c == chapter) {
+            Some((_, words)) => words.push(word),
+            None => sheets.push((chapter, vec![word])),
+        }
+    }
+    if sheets.is_empty() {
+        sheets.push((None, Vec::new()));
+    }
+
+    let mut names: Vec<String> = Vec::new();
+    for (chapter, _) in &sheets {
+        let name = sheet_name(chapter.unwrap_or(DEFAULT_SHEET), &names);
+        names.push(name);
+    }
+
+    let mut files: Vec<(String, String)> = vec![
+        ("[Content_Types].xml".to_string(), content_types(sheets.len())),
+        (
+            "_rels/.rels".to_string(),
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="xl/workbook.xml"/></Relationships>"#.to_string(),
+        ),
+        ("xl/workbook.xml".to_string(), workbook(&names)),
+        ("xl/_rels/workbook.xml.rels".to_string(), workbook_rels(sheets.len())),
+    ];
+    for (i, (_, words)) in sheets.iter().enumerate() {
+        files.push((format!("xl/worksheets/sheet{}.xml", i + 1), worksheet(words)));
+    }
+
+    let zip_error = |e: zip::result::ZipError| Error::Parse(format!("无法写入工作簿: {}", e));
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, content) in files {
+        writer.start_file(name, zip::write::FileOptions::default()).map_err(zip_error)?;
+        writer.write_all(content.as_bytes())?;
+    }
+    Ok(writer.finish().map_err(zip_error)?.into_inner())
+}
+
+/// 合法且不与 `taken` 重复（不区分大小写）的工作表名称：去掉 Excel 不允许的字符，
+/// 截断到 31 个字符，重复时加上 `_2`、`_3` 等后缀
+pub fn sheet_name(title: &str, taken: &[String]) -> String {
+    let cleaned: String = title.chars().filter(|c| !matches!(c, '[' | ']' | ':' | '*' | '?' | '/' | '\\') && !c.is_control()).collect();
+    let cleaned = cleaned.trim().trim_matches('\'').trim();
+    let base = if cleaned.is_empty() { DEFAULT_SHEET } else { cleaned };
+    let is_taken = |name: &str| taken.iter().any(|t| t.to_lowercase() == name.to_lowercase());
+
+    let mut n = 1;
+    loop {
+        let suffix = if n == 1 { String::new() } else { format!("_{}", n) };
+        let name: String = base.chars().take(MAX_SHEET_NAME - suffix.chars().count()).chain(suffix.chars()).collect();
+        if !is_taken(&name) {
+            return name;
+        }
+        n += 1;
+    }
+}
+
+fn content_types(sheets: usize) -> String {
+    let overrides: String = (1..=sheets)
+        .map(|i| format!(r#"<Override PartName="/xl/worksheets/sheet{}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#, i))
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types"><Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/><Default Extension="xml" ContentType="application/xml"/><Override PartName="/xl/workbook.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>{}</Types>"#,
+        overrides
+    )
+}
+
+fn workbook(names: &[String]) -> String {
+    let sheets: String = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| format!(r#"<sheet name="{}" sheetId="{}" r:id="rId{}"/>"#, escape_xml(name), i + 1, i + 1))
+        .collect();
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships"><sheets>{}</sheets></workbook>"#,
+        sheets
+    )
+}
+
+fn workbook_rels(sheets: usize) -> String {
+    let rels: String = (1..=sheets)
+        .map(|i| format!(r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" Target="worksheets/sheet{}.xml"/>"#, i, i))
+        .collect();
+    format!(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{}</Relationships>"#, rels)
+}
+
+/// 工作表：第一行为表头，之后每行一个单词，单元格均为内联字符串
+fn worksheet(words: &[&Word]) -> String {
+    let row = |r: usize, cells: [&str; 5]| {
+        let cells: String = cells
+            .iter()
+            .zip(["A", "B", "C", "D", "E"])
+            .filter(|(value, _)| !value.is_empty())
+            .map(|(value, col)| format!(r#"<c r="{}{}" t="inlineStr"><is><t xml:space="preserve">{}</t></is></c>"#, col, r, escape_xml(value)))
+            .collect();
+        format!(r#"<row r="{}">{}</row>"#, r, cells)
+    };
+    let mut rows = row(1, HEADER);
+    for (i, word) in words.iter().enumerate() {
+        rows.push_str(&row(
+            i + 2,
+            [
+                &word.number,
+                &word.word,
+                &word.meaning,
+                word.phonetic.as_deref().unwrap_or_default(),
+                word.example.as_deref().unwrap_or_default(),
+            ],
+        ));
+    }
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>{}</sheetData></worksheet>"#,
+        rows
+    )
+}
+
+/// 转义 XML 特殊字符，去掉 XML 不允许的控制字符
+fn escape_xml(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WordExtractor;
+
+    #[test]
+    fn test_sheet_name() {
+        assert_eq!(sheet_name("Unit 1: Greetings?", &[]), "Unit 1 Greetings");
+        assert_eq!(sheet_name("unit 1", &["Unit 1".to_string()]), "unit 1_2");
+        assert_eq!(sheet_name(&"a".repeat(40), &[]).chars().count(), MAX_SHEET_NAME);
+        assert_eq!(sheet_name("[]", &[]), DEFAULT_SHEET);
+    }
+
+    #[test]
+    fn test_write_workbook_by_chapter() {
+        let extractor = WordExtractor::new(true, false);
+        let mut result = extractor
+            .extract_from_markdown("# Unit 1\n\n| NO. | 单词 | 释义 |\n|---|---|---|\n| 1 | hello | 你好 & 问候 |\n\n# Unit 2\n\n| NO. | 单词 | 释义 |\n|---|---|---|\n| 2 | world | 世界 |\n")
+            .unwrap();
+        result.words[1].phonetic = Some("/wɜːld/".to_string());
+
+        let path = std::env::temp_dir().join(format!("bbdc_xlsx_export_test_{}.xlsx", std::process::id()));
+        std::fs::write(&path, write_workbook(&result.words).unwrap()).unwrap();
+        let first = extractor.extract_from_xlsx(&path, Some("Unit 1"));
+        let second = extractor.extract_from_xlsx(&path, Some("Unit 2"));
+        std::fs::remove_file(&path).ok();
+
+        let first = first.unwrap();
+        assert_eq!(first.words[0].word, "hello");
+        assert_eq!(first.words[0].meaning, "你好 & 问候");
+        assert_eq!(second.unwrap().words[0].word, "world");
+    }
+}
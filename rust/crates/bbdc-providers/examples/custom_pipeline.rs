@@ -0,0 +1,68 @@
+//! 自定义流水线示例
+//!
+//! 在库 API 之上组合自己的处理流程：提取单词和词义、过滤、核对，
+//! 最后输出带元数据的报告：
+//!
+//! ```bash
+//! cargo run -p bbdc-providers --example custom_pipeline
+//! ```
+
+use bbdc_core::{BookMetadata, Report, Result, WordExtractor};
+use bbdc_providers::{BBDCChecker, MockTransport};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fixtures");
+
+fn run() -> Result<String> {
+    let source = format!("{}/unit1.md", FIXTURES);
+
+    // 提取单词和短语
+    let extractor = WordExtractor::new(true, true);
+    let mut result = extractor.extract_from_file(&source)?;
+
+    // 自定义过滤：只保留动词
+    result.words.retain(|w| w.meaning.starts_with("v."));
+    result.total_words = result.words.len();
+
+    // 离线核对
+    let checker = BBDCChecker::with_transport(MockTransport::from_file(format!(
+        "{}/known_words.txt",
+        FIXTURES
+    ))?);
+    let check = checker.check_word_structs(&result.words)?;
+
+    // 带元数据的报告
+    let metadata = BookMetadata {
+        title: "{name} 动词".to_string(),
+        tags: vec!["示例".to_string()],
+        ..Default::default()
+    }
+    .render(&source);
+
+    let report = Report::new(&metadata)
+        .with_extract(&result)
+        .with_check(&check)
+        .to_markdown();
+
+    Ok(report)
+}
+
+fn main() {
+    match run() {
+        Ok(report) => println!("{}", report),
+        Err(e) => {
+            eprintln!("❌ 错误: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_runs() {
+        let report = super::run().unwrap();
+        assert!(report.starts_with("# unit1 动词"));
+        assert!(report.contains("- 单词数: 3"));
+        assert!(report.contains("- 短语数: 1"));
+    }
+}
@@ -0,0 +1,47 @@
+//! 提取并核对示例
+//!
+//! 从示例 Markdown 中提取单词，再用离线的模拟传输层核对：
+//!
+//! ```bash
+//! cargo run -p bbdc-providers --example extract_and_check
+//! ```
+
+use bbdc_core::{Result, WordExtractor};
+use bbdc_providers::{BBDCChecker, MockTransport};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/examples/fixtures");
+
+fn run() -> Result<()> {
+    // 1. 提取：去重，不含短语
+    let extractor = WordExtractor::new(true, false);
+    let result = extractor.extract_from_file(format!("{}/unit1.md", FIXTURES))?;
+    println!("提取到 {} 个单词", result.total_words);
+
+    // 2. 核对：模拟传输层以本地单词表代替不背单词接口
+    let transport = MockTransport::from_file(format!("{}/known_words.txt", FIXTURES))?;
+    let checker = BBDCChecker::with_transport(transport);
+    let check = checker.check_word_structs(&result.words)?;
+
+    println!("识别成功 {}/{}", check.recognized_count, check.total_count);
+    for word in &check.unrecognized_words {
+        println!("  未识别: {}", word);
+    }
+
+    assert_eq!(check.unrecognized_words, vec!["abundnat", "acess"]);
+    Ok(())
+}
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("❌ 错误: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn example_runs() {
+        super::run().unwrap();
+    }
+}
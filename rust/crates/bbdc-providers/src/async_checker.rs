@@ -0,0 +1,331 @@
+//! 异步不背单词核对模块
+//!
+//! [`AsyncBBDCChecker`] 使用非阻塞 reqwest，可以嵌入 tokio 应用，分块并发上传时不占用线程。
+//! 去重、分块、截断重传、响应解析、验证码和最小上传间隔与 [`BBDCChecker`](crate::BBDCChecker)
+//! 共用同一套实现；被限流（429、5xx）的分块在其余分块完成后逐块重传，无法连接时可改用离线词表。
+//!
+//! 同步核对器的以下功能没有提供：识别缓存、进度事件、用其他写法重新核对、核对进度
+//! （断点续传、跳过未变化的单词列表）、保存识别结果和无头浏览器回退
+
+use crate::bbdc_checker::{
+    captcha_image_path, check_concurrency, chunk_file_name, chunk_size_from_env, concurrency_from_env, dedup_words,
+    parse_response, politeness_delay_from_env, split_truncated, CaptchaChallenge, DEFAULT_CONCURRENCY,
+    MAX_CAPTCHA_ATTEMPTS,
+};
+use crate::transport::{AsyncHttpTransport, AsyncTransport, SubmitRequest};
+use crate::OfflineDictionary;
+use bbdc_core::{CheckResult, Error, Result, Word};
+use futures::future::BoxFuture;
+use futures::stream::{self, StreamExt};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// 验证码求解回调，在阻塞线程池中调用，可以等待用户输入
+type SharedCaptchaSolver = Arc<dyn Fn(&CaptchaChallenge) -> Option<String> + Send + Sync>;
+
+/// 异步不背单词核对器
+pub struct AsyncBBDCChecker {
+    transport: Box<dyn AsyncTransport>,
+    captcha_solver: Option<SharedCaptchaSolver>,
+    chunk_size: usize,
+    concurrency: usize,
+    politeness_delay: Duration,
+    /// 上一次上传的时间，所有并发的上传共用
+    last_submit: Mutex<Option<Instant>>,
+    offline: Option<OfflineDictionary>,
+}
+
+impl AsyncBBDCChecker {
+    /// 创建新的异步核对器
+    pub fn new() -> Result<Self> {
+        Self::with_transport(AsyncHttpTransport::new()?).with_concurrency(concurrency_from_env()?)
+    }
+
+    /// 使用指定传输层创建核对器
+    ///
+    /// [`MockTransport`](crate::transport::MockTransport) 同样实现了 [`AsyncTransport`]，可离线运行
+    pub fn with_transport<T: AsyncTransport + 'static>(transport: T) -> Self {
+        Self {
+            transport: Box::new(transport),
+            captcha_solver: None,
+            chunk_size: chunk_size_from_env(),
+            concurrency: DEFAULT_CONCURRENCY,
+            politeness_delay: politeness_delay_from_env(),
+            last_submit: Mutex::new(None),
+            offline: None,
+        }
+    }
+
+    /// 设置验证码求解回调，见 [`BBDCChecker::with_captcha_solver`](crate::BBDCChecker::with_captcha_solver)
+    ///
+    /// 回调在 [`tokio::task::spawn_blocking`] 的线程中调用，等待输入时不会阻塞异步任务
+    pub fn with_captcha_solver<F>(mut self, solver: F) -> Self
+    where
+        F: Fn(&CaptchaChallenge) -> Option<String> + Send + Sync + 'static,
+    {
+        self.captcha_solver = Some(Arc::new(solver));
+        self
+    }
+
+    /// 每次上传的单词数上限，0 表示不分块
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// 分块上传时同时上传的块数（1~3），超出范围时报错
+    pub fn with_concurrency(mut self, concurrency: usize) -> Result<Self> {
+        self.concurrency = check_concurrency(concurrency)?;
+        Ok(self)
+    }
+
+    /// 两次上传之间的最小间隔，默认 1 秒
+    pub fn with_politeness_delay(mut self, delay: Duration) -> Self {
+        self.politeness_delay = delay;
+        self
+    }
+
+    /// 无法连接不背单词（连接失败、超时）时改用离线词表核对
+    pub fn with_offline_fallback(mut self, dictionary: OfflineDictionary) -> Self {
+        self.offline = Some(dictionary);
+        self
+    }
+
+    /// 读取单词文件（每行一个单词）进行核对
+    pub async fn check_words_file<P: AsRef<Path>>(&self, file_path: P) -> Result<CheckResult> {
+        let file_path = file_path.as_ref();
+        let content = tokio::fs::read(file_path)
+            .await
+            .map_err(|e| Error::FileRead(std::io::Error::new(e.kind(), format!("读取 {:?} 失败: {}", file_path, e))))?;
+        let text = String::from_utf8_lossy(&content);
+        if text.trim().is_empty() {
+            return Err(Error::Empty(format!("{:?} 是空文件，无需核对", file_path)));
+        }
+
+        let stem = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("words");
+        self.check(stem, text.lines()).await
+    }
+
+    /// 核对单词列表
+    pub async fn check_words(&self, words: &[String]) -> Result<CheckResult> {
+        self.check("words", words.iter().map(String::as_str)).await
+    }
+
+    /// 核对 Word 结构体列表
+    pub async fn check_word_structs(&self, words: &[Word]) -> Result<CheckResult> {
+        self.check("words", words.iter().map(|w| w.word.as_str())).await
+    }
+
+    /// 去掉空行和重复的单词后分块并发上传，按原顺序合并；被限流（429、5xx）的分块在其余分块完成后逐块重传
+    async fn check<'a, I>(&self, stem: &str, lines: I) -> Result<CheckResult>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let (words, removed) = dedup_words(lines);
+        if words.is_empty() {
+            return Err(Error::Empty(format!("{} 中没有需要核对的单词", stem)));
+        }
+        let words = words.as_slice();
+        let chunk_size = if self.chunk_size == 0 { words.len().max(1) } else { self.chunk_size };
+        let chunks: Vec<&[&str]> = words.chunks(chunk_size).collect();
+        log::info!("共 {} 个单词，分 {} 块上传（并发 {}）", words.len(), chunks.len(), self.concurrency);
+
+        let chunk_name = |i: usize| chunk_file_name(stem, i);
+        let attempts: Vec<Result<CheckResult>> = stream::iter(chunks.iter().enumerate())
+            .map(|(i, chunk)| self.submit_chunk(chunk_name(i), chunk))
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+
+        let mut results = Vec::with_capacity(chunks.len());
+        for (i, attempt) in attempts.into_iter().enumerate() {
+            let result = match attempt {
+                Err(e) if e.is_throttled() => {
+                    log::warn!("上传第 {} 块时被限流（{}），稍后重新上传", i + 1, e);
+                    self.submit_chunk(chunk_name(i), chunks[i]).await?
+                }
+                attempt => attempt?,
+            };
+            results.push(result);
+        }
+        let mut result = CheckResult::merge(&results);
+        result.removed_count = removed;
+        Ok(result)
+    }
+
+    /// 上传一块单词，响应缺少单词且块足够大时拆成两半重新上传
+    fn submit_chunk<'a>(&'a self, chunk_name: String, words: &'a [&'a str]) -> BoxFuture<'a, Result<CheckResult>> {
+        Box::pin(async move {
+            let mut result = self.submit_content(&chunk_name, words.join("\n").as_bytes()).await?;
+            let Some([(first_name, first), (second_name, second)]) = split_truncated(&chunk_name, words, &mut result) else {
+                return Ok(result);
+            };
+            let first = self.submit_chunk(first_name, first).await?;
+            let second = self.submit_chunk(second_name, second).await?;
+            Ok(CheckResult::merge(&[first, second]))
+        })
+    }
+
+    /// 上传一份单词内容并处理验证码
+    async fn submit_content(&self, file_name: &str, file_content: &[u8]) -> Result<CheckResult> {
+        let mut captcha: Option<(String, Option<String>)> = None;
+        let mut attempts = 0;
+        loop {
+            self.wait_turn().await;
+            let request = SubmitRequest {
+                file_name,
+                content: file_content,
+                captcha: captcha.as_ref().map(|(token, _)| token.as_str()),
+                captcha_key: captcha.as_ref().and_then(|(_, key)| key.as_deref()),
+            };
+            let body = match self.transport.submit(request).await {
+                Ok(body) => body,
+                Err(e) => match &self.offline {
+                    Some(dictionary) if e.is_unreachable() => {
+                        log::warn!("无法连接不背单词（{}），{} 改用离线词表核对", e, file_name);
+                        return Ok(dictionary.check_content(file_content));
+                    }
+                    _ => return Err(e),
+                },
+            };
+            let data_body = parse_response(&body)?;
+
+            let Some(image_url) = data_body.captcha_image_url(&self.transport.endpoint()) else {
+                return Ok(data_body.into_check_result());
+            };
+            attempts += 1;
+            if attempts > MAX_CAPTCHA_ATTEMPTS {
+                return Err(Error::CaptchaFailed { attempts: MAX_CAPTCHA_ATTEMPTS });
+            }
+            let challenge = CaptchaChallenge {
+                image_path: self.save_captcha_image(&image_url).await,
+                image_url,
+                key: data_body.captcha_key(),
+            };
+            log::warn!("不背单词要求输入验证码: {}", challenge.image_url);
+
+            let token = match &self.captcha_solver {
+                Some(solve) => {
+                    let (solve, challenge) = (solve.clone(), challenge.clone());
+                    tokio::task::spawn_blocking(move || solve(&challenge))
+                        .await
+                        .map_err(|e| Error::Other(format!("验证码回调异常退出: {}", e)))?
+                }
+                None => None,
+            }
+            .ok_or_else(|| Error::CaptchaRequired { image_url: challenge.image_url.clone() })?;
+            captcha = Some((token, challenge.key));
+        }
+    }
+
+    /// 等到距上一次上传满最小间隔后再返回，并记录本次上传的时间
+    async fn wait_turn(&self) {
+        let mut last_submit = self.last_submit.lock().await;
+        if let Some(wait) = last_submit.and_then(|last| self.politeness_delay.checked_sub(last.elapsed())) {
+            tokio::time::sleep(wait).await;
+        }
+        *last_submit = Some(Instant::now());
+    }
+
+    /// 下载验证码图片到临时目录，失败时返回 None
+    async fn save_captcha_image(&self, image_url: &str) -> Option<std::path::PathBuf> {
+        let bytes = self
+            .transport
+            .fetch(image_url)
+            .await
+            .map_err(|e| log::warn!("下载验证码图片失败: {}", e))
+            .ok()?;
+        let path = captcha_image_path();
+        match tokio::fs::write(&path, &bytes).await {
+            Ok(()) => Some(path),
+            Err(e) => {
+                log::warn!("保存验证码图片失败: {}", e);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_async_chunked_check() {
+        let words: Vec<String> = (0..10).map(|i| format!("word{}", i)).collect();
+        let checker = AsyncBBDCChecker::with_transport(MockTransport::new(&words[..7]))
+            .with_chunk_size(3)
+            .with_concurrency(3)
+            .unwrap()
+            .with_politeness_delay(Duration::ZERO);
+
+        let result = checker.check_words(&words).await.unwrap();
+        assert_eq!(result.total_count, 10);
+        assert_eq!(result.recognized_words, &words[..7]);
+        assert_eq!(result.unrecognized_words, &words[7..]);
+    }
+
+    #[tokio::test]
+    async fn test_async_captcha_solver() {
+        /// 第一次提交要求验证码，带上验证码后正常核对
+        struct CaptchaOnce(MockTransport);
+
+        impl AsyncTransport for CaptchaOnce {
+            fn submit<'a>(&'a self, request: SubmitRequest<'a>) -> BoxFuture<'a, Result<String>> {
+                if request.captcha.is_none() {
+                    let body = r#"{"data_body":{"captchaUrl":"/captcha.jpg","captchaKey":"k1"}}"#.to_string();
+                    return Box::pin(futures::future::ready(Ok(body)));
+                }
+                AsyncTransport::submit(&self.0, request)
+            }
+
+            fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<Vec<u8>>> {
+                AsyncTransport::fetch(&self.0, url)
+            }
+        }
+
+        let words = ["apple".to_string(), "banan".to_string()];
+        let checker = || AsyncBBDCChecker::with_transport(CaptchaOnce(MockTransport::new(["apple"])));
+        assert!(matches!(checker().check_words(&words).await, Err(Error::CaptchaRequired { .. })));
+
+        let checker = checker()
+            .with_politeness_delay(Duration::ZERO)
+            .with_captcha_solver(|challenge| challenge.key.as_ref().map(|_| "1234".to_string()));
+        let result = checker.check_words(&words).await.unwrap();
+        assert_eq!(result.recognized_words, ["apple"]);
+        assert_eq!(result.unrecognized_words, ["banan"]);
+    }
+
+    #[tokio::test]
+    async fn test_async_captcha_attempts() {
+        /// 每次提交都要求验证码
+        struct AlwaysCaptcha(MockTransport);
+
+        impl AsyncTransport for AlwaysCaptcha {
+            fn submit<'a>(&'a self, _request: SubmitRequest<'a>) -> BoxFuture<'a, Result<String>> {
+                let body = r#"{"data_body":{"captchaUrl":"/captcha.jpg"}}"#.to_string();
+                Box::pin(futures::future::ready(Ok(body)))
+            }
+
+            fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<Vec<u8>>> {
+                AsyncTransport::fetch(&self.0, url)
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let checker = AsyncBBDCChecker::with_transport(AlwaysCaptcha(MockTransport::new(["apple"])))
+            .with_politeness_delay(Duration::ZERO)
+            .with_captcha_solver(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Some("1234".to_string())
+            });
+        let result = checker.check_words(&["apple".to_string()]).await;
+        assert!(matches!(result, Err(Error::CaptchaFailed { attempts: MAX_CAPTCHA_ATTEMPTS })));
+        assert_eq!(calls.load(Ordering::SeqCst), MAX_CAPTCHA_ATTEMPTS);
+    }
+}
@@ -0,0 +1,306 @@
+//! 登录态管理模块
+//!
+//! 保存不背单词的会话 Cookie，并支持从本地浏览器导入（需启用 `browser-cookies` 特性）
+//!
+//! 目前只支持 Firefox：Chrome/Edge 的 Cookie 由系统密钥（Windows DPAPI、macOS 钥匙串、
+//! Linux 密钥环）加密，本模块不做解密
+
+use bbdc_core::{EnvLoader, Error, Result};
+#[cfg(feature = "browser-cookies")]
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// 不背单词站点域名
+#[cfg(feature = "browser-cookies")]
+const BBDC_DOMAIN: &str = "bbdc.cn";
+
+/// 支持导入 Cookie 的浏览器
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum Browser {
+    Firefox,
+}
+
+/// 单个 Cookie
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    pub name: String,
+    pub value: String,
+}
+
+/// Cookie 存储
+pub struct CookieStore;
+
+impl CookieStore {
+    /// Cookie 文件路径
+    ///
+    /// 优先使用 `BBDC_COOKIE_FILE`，否则为用户目录下的 `.bbdc_word_tool/cookies.txt`
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = EnvLoader::get_optional("BBDC_COOKIE_FILE") {
+            return Some(PathBuf::from(path));
+        }
+
+        EnvLoader::data_dir().map(|dir| dir.join("cookies.txt"))
+    }
+
+    /// 保存 Cookie，返回写入的文件路径；Unix 上文件权限为 0600
+    pub fn save(cookies: &[Cookie]) -> Result<PathBuf> {
+        let path = Self::path()
+            .ok_or_else(|| Error::Other("无法确定 Cookie 文件位置，请设置 BBDC_COOKIE_FILE".to_string()))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        write_private(&path, to_header(cookies).as_bytes())?;
+        Ok(path)
+    }
+
+    /// 读取请求用的 Cookie 头
+    ///
+    /// 环境变量 `BBDC_COOKIE` 优先于 Cookie 文件
+    pub fn load_header() -> Option<String> {
+        if let Some(cookie) = EnvLoader::get_optional("BBDC_COOKIE") {
+            return Some(cookie);
+        }
+
+        let content = fs::read_to_string(Self::path()?).ok()?;
+        let header = content.trim();
+
+        if header.is_empty() {
+            None
+        } else {
+            Some(header.to_string())
+        }
+    }
+}
+
+/// 拼接为 `name=value; name2=value2` 形式
+fn to_header(cookies: &[Cookie]) -> String {
+    cookies
+        .iter()
+        .map(|c| format!("{}={}", c.name, c.value))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// 解析 `name=value; name2=value2` 形式的 Cookie 头，忽略没有 `=` 的片段
+#[cfg(feature = "headless")]
+pub(crate) fn parse_header(header: &str) -> Vec<Cookie> {
+    header
+        .split(';')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(name, value)| Cookie { name: name.trim().to_string(), value: value.trim().to_string() })
+        .filter(|cookie| !cookie.name.is_empty())
+        .collect()
+}
+
+/// 写入只有当前用户可读写的文件，已存在的文件同样收紧权限
+fn write_private(path: &Path, content: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    file.write_all(content)?;
+    Ok(())
+}
+
+/// 用户主目录
+#[cfg(feature = "browser-cookies")]
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// 从本地浏览器读取不背单词的 Cookie
+#[cfg(feature = "browser-cookies")]
+pub fn import_browser_cookies(browser: Browser) -> Result<Vec<Cookie>> {
+    let db_path = browser_store::cookie_db(browser).ok_or_else(|| {
+        Error::Other(format!("未找到 {:?} 的 Cookie 数据库", browser))
+    })?;
+
+    log::info!("读取 Cookie 数据库: {:?}", db_path);
+
+    // 浏览器运行时会锁定数据库，先复制一份再读取；最近写入的 Cookie 可能还在 -wal 文件中，
+    // 一并复制。复制到新建的私有目录，避免与其他进程冲突或被预先放置的符号链接劫持
+    let temp_dir = private_temp_dir()?;
+    let result = copy_cookie_db(&db_path, &temp_dir).and_then(|copy| browser_store::read_cookies(&copy));
+    let _ = fs::remove_dir_all(&temp_dir);
+
+    let cookies = result?;
+    if cookies.is_empty() {
+        return Err(Error::Other(format!(
+            "{:?} 中没有找到 {} 的 Cookie，请先在浏览器中登录不背单词",
+            browser, BBDC_DOMAIN
+        )));
+    }
+
+    Ok(cookies)
+}
+
+/// 在系统临时目录下新建只有当前用户可访问的目录，目录已存在时报错而不是复用
+#[cfg(feature = "browser-cookies")]
+fn private_temp_dir() -> Result<PathBuf> {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let dir = env::temp_dir().join(format!("bbdc_cookies_{}_{:08x}", std::process::id(), nanos));
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(&dir)?;
+    Ok(dir)
+}
+
+/// 把 Cookie 数据库及其 -wal 文件复制到 `dir`，返回副本路径
+#[cfg(feature = "browser-cookies")]
+fn copy_cookie_db(db_path: &Path, dir: &Path) -> Result<PathBuf> {
+    let copy = dir.join("cookies.sqlite");
+    fs::copy(db_path, &copy)?;
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    let wal = PathBuf::from(wal);
+    if wal.exists() {
+        fs::copy(&wal, dir.join("cookies.sqlite-wal"))?;
+    }
+    Ok(copy)
+}
+
+/// 未启用 `browser-cookies` 特性时的占位实现
+#[cfg(not(feature = "browser-cookies"))]
+pub fn import_browser_cookies(_browser: Browser) -> Result<Vec<Cookie>> {
+    Err(Error::Other(
+        "当前版本未启用浏览器 Cookie 导入，请使用 `cargo build --features browser-cookies` 重新编译".to_string(),
+    ))
+}
+
+#[cfg(feature = "browser-cookies")]
+mod browser_store {
+    use super::{home_dir, Browser, Cookie, BBDC_DOMAIN};
+    use bbdc_core::{Error, Result};
+    use rusqlite::{Connection, OpenFlags};
+    use std::env;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// 查找浏览器的 Cookie 数据库
+    pub fn cookie_db(browser: Browser) -> Option<PathBuf> {
+        match browser {
+            Browser::Firefox => firefox_db(),
+        }
+    }
+
+    /// Firefox 最近使用的配置文件中的 cookies.sqlite
+    fn firefox_db() -> Option<PathBuf> {
+        let mut roots = Vec::new();
+        if let Some(appdata) = env::var_os("APPDATA") {
+            roots.push(PathBuf::from(appdata).join("Mozilla/Firefox/Profiles"));
+        }
+        if let Some(home) = home_dir() {
+            roots.push(home.join(".mozilla/firefox"));
+            roots.push(home.join("Library/Application Support/Firefox/Profiles"));
+        }
+
+        roots
+            .iter()
+            .filter_map(|root| fs::read_dir(root).ok())
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path().join("cookies.sqlite"))
+            .filter(|path| path.exists())
+            .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+    }
+
+    /// 读取 Firefox 数据库中属于不背单词（`bbdc.cn` 及其子域名）的 Cookie
+    ///
+    /// `db_path` 应为复制出的副本：以读写方式打开，才能读取 -wal 文件中的内容
+    pub fn read_cookies(db_path: &Path) -> Result<Vec<Cookie>> {
+        let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_WRITE)
+            .map_err(sql_error)?;
+        let subdomains = format!("%.{}", BBDC_DOMAIN);
+        let params = [BBDC_DOMAIN, subdomains.as_str()];
+
+        let mut stmt = conn
+            .prepare("SELECT name, value FROM moz_cookies WHERE host = ?1 OR host LIKE ?2")
+            .map_err(sql_error)?;
+        let rows = stmt
+            .query_map(params, |row| {
+                Ok(Cookie {
+                    name: row.get(0)?,
+                    value: row.get(1)?,
+                })
+            })
+            .map_err(sql_error)?;
+        rows.collect::<rusqlite::Result<Vec<_>>>().map_err(sql_error)
+    }
+
+    fn sql_error(e: rusqlite::Error) -> Error {
+        Error::Other(format!("读取 Cookie 数据库失败: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cookie_header() {
+        let cookies = vec![
+            Cookie { name: "JSESSIONID".to_string(), value: "abc".to_string() },
+            Cookie { name: "acw_tc".to_string(), value: "123".to_string() },
+        ];
+        assert_eq!(to_header(&cookies), "JSESSIONID=abc; acw_tc=123");
+    }
+
+    #[cfg(feature = "headless")]
+    #[test]
+    fn test_parse_cookie_header() {
+        let cookies = parse_header(" JSESSIONID=abc; token=a=b ;; broken");
+        assert_eq!(to_header(&cookies), "JSESSIONID=abc; token=a=b");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cookie_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("bbdc_cookies_test_{}.txt", std::process::id()));
+        fs::write(&path, "").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        write_private(&path, b"JSESSIONID=abc").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let _ = fs::remove_file(&path);
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[cfg(feature = "browser-cookies")]
+    #[test]
+    fn test_firefox_cookies_match_bbdc_hosts_only() {
+        let dir = private_temp_dir().unwrap();
+        let db = dir.join("source.sqlite");
+        let conn = rusqlite::Connection::open(&db).unwrap();
+        conn.execute_batch(
+            "PRAGMA journal_mode = WAL;
+             CREATE TABLE moz_cookies (name TEXT, value TEXT, host TEXT);
+             INSERT INTO moz_cookies VALUES ('a', '1', 'bbdc.cn'), ('b', '2', '.bbdc.cn'),
+                 ('c', '3', 'www.bbdc.cn'), ('d', '4', 'evilbbdc.cn');",
+        )
+        .unwrap();
+
+        // 连接未关闭，写入的内容仍只在 -wal 文件中
+        fs::create_dir(dir.join("copy")).unwrap();
+        let copy = copy_cookie_db(&db, &dir.join("copy")).unwrap();
+        let cookies = browser_store::read_cookies(&copy).unwrap();
+        drop(conn);
+        let _ = fs::remove_dir_all(&dir);
+        let names: Vec<_> = cookies.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,1222 @@
+//! 不背单词词书核对模块
+//! 
+//! 调用不背单词 API 检查单词是否被识别
+
+use bbdc_core::{text, CheckResult, Error, Lemmatizer, Result, Word};
+#[cfg(feature = "http")]
+use crate::transport::HttpTransport;
+use crate::transport::{BbdcEndpoint, SubmitRequest, Transport};
+use crate::{CheckCache, CheckState, OfflineDictionary};
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 验证码最多尝试次数
+pub(crate) const MAX_CAPTCHA_ATTEMPTS: usize = 3;
+
+/// 每次上传的单词数上限，超过时分块上传
+pub(crate) const DEFAULT_CHUNK_SIZE: usize = 2000;
+
+/// 响应缺少单词时拆分重传的最小块大小，更小的块缺少单词时只记录警告
+pub(crate) const MIN_SPLIT_SIZE: usize = 50;
+
+/// 分块上传的默认并发数
+pub(crate) const DEFAULT_CONCURRENCY: usize = 1;
+
+/// 并发数上限，避免给不背单词服务器造成压力
+pub(crate) const MAX_CONCURRENCY: usize = 3;
+
+/// 检查并发数是否在 1~3 之间
+pub(crate) fn check_concurrency(concurrency: usize) -> Result<usize> {
+    if (1..=MAX_CONCURRENCY).contains(&concurrency) {
+        Ok(concurrency)
+    } else {
+        Err(Error::Parse(format!("并发数应为 1~{}，不能为 {}", MAX_CONCURRENCY, concurrency)))
+    }
+}
+
+/// 环境变量 `BBDC_CHECK_CONCURRENCY` 设置的并发数，未设置时为默认值，不是 1~3 的整数时报错
+#[cfg(feature = "http")]
+pub(crate) fn concurrency_from_env() -> Result<usize> {
+    match bbdc_core::EnvLoader::get_optional("BBDC_CHECK_CONCURRENCY") {
+        None => Ok(DEFAULT_CONCURRENCY),
+        Some(value) => value
+            .trim()
+            .parse()
+            .ok()
+            .filter(|n| (1..=MAX_CONCURRENCY).contains(n))
+            .ok_or_else(|| Error::EnvVar(format!(
+                "BBDC_CHECK_CONCURRENCY 应为 1~{} 的整数，当前为 {}", MAX_CONCURRENCY, value
+            ))),
+    }
+}
+
+/// 两次上传之间的默认最小间隔
+pub(crate) const DEFAULT_POLITENESS_DELAY: Duration = Duration::from_millis(1000);
+
+/// 验证码求解回调：返回 `None` 表示放弃
+pub type CaptchaSolver = Box<dyn Fn(&CaptchaChallenge) -> Option<String> + Send + Sync>;
+
+/// 核对进度事件，供前端显示进度
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckEvent {
+    /// 开始上传：需要上传的单词数（不含缓存命中的）和分块数
+    Started { words: usize, chunks: usize },
+    /// 从上次中断的核对中恢复了 `chunks` 块已完成的结果，这些块不再上传
+    Resumed { chunks: usize },
+    /// 开始上传第 `index` 块（从 1 开始），共 `total` 块
+    ChunkStarted { index: usize, total: usize, words: usize },
+    /// 第 `index` 块的响应已解析
+    ChunkFinished { index: usize, total: usize, recognized: usize },
+    /// 单词列表与上次成功核对时相同，直接使用上次的结果，没有上传
+    Unchanged { recognized: usize, total: usize },
+    /// 全部完成（含缓存命中的单词）
+    Finished { recognized: usize, total: usize },
+    /// 用 `variants` 种其他写法重新核对 `words` 个识别失败的单词，在 `Finished` 之后发出；
+    /// 重新核对的上传不再发出其他事件
+    Variants { words: usize, variants: usize },
+}
+
+/// 进度回调
+pub type ProgressCallback = Box<dyn Fn(&CheckEvent) + Send + Sync>;
+
+/// 不背单词核对器
+pub struct BBDCChecker {
+    transport: Box<dyn Transport>,
+    captcha_solver: Option<CaptchaSolver>,
+    progress: Option<ProgressCallback>,
+    chunk_size: usize,
+    concurrency: usize,
+    politeness_delay: Duration,
+    /// 上一次上传的时间，所有线程共用
+    last_submit: Mutex<Option<Instant>>,
+    offline: Option<OfflineDictionary>,
+    /// 改用离线词表核对的次数，离线结果不写入缓存
+    offline_checks: AtomicUsize,
+    cache: Option<CheckCache>,
+    /// 分块核对的进度，中断后重新运行时跳过已完成的分块
+    state: Option<CheckState>,
+    /// 单词列表与上次成功核对时相同时直接使用上次的结果
+    skip_unchanged: bool,
+    /// 核对单词文件后在旁边保存识别成功、识别失败的单词
+    save_word_lists: bool,
+    /// 用其他写法重新核对识别失败的单词
+    variant_recheck: bool,
+    #[cfg(feature = "headless")]
+    headless_fallback: bool,
+}
+
+/// 验证码挑战
+#[derive(Debug, Clone)]
+pub struct CaptchaChallenge {
+    /// 验证码图片地址
+    pub image_url: String,
+    /// 已下载到本地的验证码图片（下载失败时为 None）
+    pub image_path: Option<PathBuf>,
+    /// 服务端下发的验证码标识，提交时原样带回
+    pub key: Option<String>,
+}
+
+/// 响应中各字段可能使用的名称，按顺序查找；比较时忽略大小写和下划线，
+/// 字段改名为 `know_list`、`KnowList` 等写法时仍能识别
+const DATA_BODY_KEYS: &[&str] = &["data_body", "data"];
+const KNOW_LIST_KEYS: &[&str] = &["knowList", "knownList"];
+const UNKNOW_LIST_KEYS: &[&str] = &["unknowList", "unknownList"];
+const CAPTCHA_URL_KEYS: &[&str] = &["captchaUrl", "captchaImage", "captchaImg"];
+const CAPTCHA_KEY_KEYS: &[&str] = &["captchaKey", "captchaId"];
+
+/// 解析上传接口的响应正文
+///
+/// 先解析为 [`Value`] 再宽松地查找字段，单词列表可以是逗号分隔的字符串或字符串数组；
+/// 不是 JSON 或字段类型无法识别时返回 [`Error::BadResponse`]，缺少 data_body 或单词列表时返回
+/// [`Error::EmptyDataBody`]，其中列出响应实际包含的字段
+pub(crate) fn parse_response(body: &str) -> Result<DataBody> {
+    let value: Value = serde_json::from_str(body)
+        .map_err(|e| Error::bad_response(format!("不背单词的响应不是 JSON: {}", e), body))?;
+    let root = value.as_object().ok_or_else(|| Error::bad_response("不背单词的响应不是 JSON 对象", body))?;
+    let data = find_field(root, DATA_BODY_KEYS).and_then(Value::as_object).ok_or_else(|| {
+        Error::empty_data_body("data_body", field_names(root), body)
+    })?;
+    
+    let text = |keys: &[&str]| {
+        find_field(data, keys).and_then(Value::as_str).map(str::trim).filter(|s| !s.is_empty()).map(str::to_string)
+    };
+    let captcha_url = text(CAPTCHA_URL_KEYS);
+    let captcha_key = text(CAPTCHA_KEY_KEYS);
+    let know = find_field(data, KNOW_LIST_KEYS);
+    let unknow = find_field(data, UNKNOW_LIST_KEYS);
+    if know.is_none() && unknow.is_none() && captcha_url.is_none() {
+        return Err(Error::empty_data_body("knowList/unknowList", field_names(data), body));
+    }
+    
+    let list = |value: Option<&Value>, name: &str| {
+        word_list(value)
+            .ok_or_else(|| Error::bad_response(format!("无法识别 {} 的类型（应为字符串或字符串数组）", name), body))
+    };
+    Ok(DataBody {
+        know_list: list(know, "knowList")?,
+        unknow_list: list(unknow, "unknowList")?,
+        captcha_url,
+        captcha_key,
+    })
+}
+
+/// 按候选名称查找字段，忽略大小写和下划线
+fn find_field<'a>(object: &'a Map<String, Value>, keys: &[&str]) -> Option<&'a Value> {
+    let normalize = |key: &str| key.replace('_', "").to_lowercase();
+    keys.iter().find_map(|key| {
+        let key = normalize(key);
+        object.iter().find(|(name, _)| normalize(name) == key).map(|(_, value)| value)
+    })
+}
+
+/// 对象的字段名，用于错误说明
+fn field_names(object: &Map<String, Value>) -> Vec<String> {
+    object.keys().cloned().collect()
+}
+
+/// 单词列表：逗号分隔的字符串或字符串数组，缺失或为 null 时为空；其他类型返回 None
+fn word_list(value: Option<&Value>) -> Option<Vec<String>> {
+    let words: Vec<String> = match value {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::String(list)) => list.split(',').map(str::to_string).collect(),
+        Some(Value::Array(items)) => {
+            items.iter().map(|item| item.as_str().map(str::to_string)).collect::<Option<Vec<_>>>()?
+        }
+        Some(_) => return None,
+    };
+    Some(words.into_iter().map(|w| w.trim().to_string()).filter(|w| !w.is_empty()).collect())
+}
+
+/// 上传接口响应中的核对结果
+#[derive(Debug)]
+pub(crate) struct DataBody {
+    know_list: Vec<String>,
+    unknow_list: Vec<String>,
+    captcha_url: Option<String>,
+    captcha_key: Option<String>,
+}
+
+impl DataBody {
+    /// 验证码图片的完整地址（相对地址按传输层的 `endpoint` 补全），不要求验证码时为 None
+    pub(crate) fn captcha_image_url(&self, endpoint: &BbdcEndpoint) -> Option<String> {
+        let image_url = self.captcha_url.as_deref()?;
+        Some(endpoint.resolve(image_url))
+    }
+    
+    pub(crate) fn captcha_key(&self) -> Option<String> {
+        self.captcha_key.clone()
+    }
+    
+    /// 转换为核对结果
+    pub(crate) fn into_check_result(self) -> CheckResult {
+        CheckResult::from_lists(self.know_list, self.unknow_list)
+    }
+}
+
+/// 整理待上传的单词：去掉两端空白、空行和重复的单词（不区分大小写，保留第一次出现的写法），
+/// 返回整理后的单词和去掉的行数
+pub(crate) fn dedup_words<'a, I>(lines: I) -> (Vec<&'a str>, usize)
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut seen = HashSet::new();
+    let mut removed = 0;
+    let mut words = Vec::new();
+    for word in lines.into_iter().map(str::trim) {
+        if !word.is_empty() && seen.insert(word.to_lowercase()) {
+            words.push(word);
+        } else {
+            removed += 1;
+        }
+    }
+    (words, removed)
+}
+
+/// 上传的单词数（不区分大小写去重），用于判断响应是否被截断
+fn expected_count(words: &[&str]) -> usize {
+    words.iter().map(|w| w.to_lowercase()).collect::<HashSet<_>>().len()
+}
+
+/// 第 `i` 块（从 0 开始）上传时使用的文件名
+pub(crate) fn chunk_file_name(stem: &str, i: usize) -> String {
+    format!("{}_{}.txt", stem, i + 1)
+}
+
+/// 检查响应是否包含上传的全部单词（不区分大小写去重后计数）
+///
+/// 缺少单词且块足够大时返回拆分后的两半及其文件名，由调用方分别重新上传；
+/// 块太小时只记录警告，返回 None 表示使用已有结果
+pub(crate) fn split_truncated<'w, 'a>(
+    chunk_name: &str,
+    words: &'w [&'a str],
+    result: &mut CheckResult,
+) -> Option<[(String, &'w [&'a str]); 2]> {
+    result.detect_normalized(words);
+    let expected = expected_count(words);
+    if result.total_count >= expected {
+        return None;
+    }
+    if words.len() < MIN_SPLIT_SIZE * 2 {
+        log::warn!("{} 上传了 {} 个单词，响应只包含 {} 个", chunk_name, expected, result.total_count);
+        return None;
+    }
+    
+    log::warn!(
+        "{} 上传了 {} 个单词，响应只包含 {} 个，拆成两半重新上传",
+        chunk_name,
+        expected,
+        result.total_count
+    );
+    let (first, second) = words.split_at(words.len() / 2);
+    let stem = chunk_name.trim_end_matches(".txt");
+    Some([(format!("{}a.txt", stem), first), (format!("{}b.txt", stem), second)])
+}
+
+/// 读取数值型环境变量，未设置或无法解析时为 None
+fn env_usize(key: &str) -> Option<usize> {
+    bbdc_core::EnvLoader::get_optional(key).and_then(|v| v.trim().parse().ok())
+}
+
+/// 每次上传的单词数上限：`BBDC_CHUNK_SIZE`，未设置时为 2000
+pub(crate) fn chunk_size_from_env() -> usize {
+    env_usize("BBDC_CHUNK_SIZE").unwrap_or(DEFAULT_CHUNK_SIZE)
+}
+
+/// 两次上传之间的最小间隔：`BBDC_REQUEST_INTERVAL_MS`（毫秒），未设置时为 1 秒
+pub(crate) fn politeness_delay_from_env() -> Duration {
+    env_usize("BBDC_REQUEST_INTERVAL_MS").map_or(DEFAULT_POLITENESS_DELAY, |ms| Duration::from_millis(ms as u64))
+}
+
+/// 下载的验证码图片保存位置
+pub(crate) fn captcha_image_path() -> PathBuf {
+    std::env::temp_dir().join("bbdc_captcha.png")
+}
+
+impl BBDCChecker {
+    /// 创建新的核对器
+    #[cfg(feature = "http")]
+    pub fn new() -> Result<Self> {
+        Self::with_transport(HttpTransport::new()?).with_concurrency(concurrency_from_env()?)
+    }
+    
+    /// 按配置创建核对器：设置了 `BBDC_MOCK_CHECK` 时使用 [`MockTransport`] 离线核对
+    /// （试运行、测试），否则同 [`new`](Self::new)
+    #[cfg(feature = "http")]
+    pub fn from_env() -> Result<Self> {
+        match crate::transport::MockTransport::from_env() {
+            Some(transport) => {
+                log::info!("使用模拟核对，不访问不背单词");
+                Self::with_transport(transport?).with_concurrency(concurrency_from_env()?)
+            }
+            None => Self::new(),
+        }
+    }
+    
+    /// 使用指定传输层创建核对器
+    /// 
+    /// 传入 [`MockTransport`](crate::transport::MockTransport) 即可离线运行；
+    /// 不读取 `BBDC_CHECK_CONCURRENCY`，需要时用 [`with_concurrency`](Self::with_concurrency) 设置
+    pub fn with_transport<T: Transport + 'static>(transport: T) -> Self {
+        Self {
+            transport: Box::new(transport),
+            captcha_solver: None,
+            progress: None,
+            chunk_size: chunk_size_from_env(),
+            concurrency: DEFAULT_CONCURRENCY,
+            politeness_delay: politeness_delay_from_env(),
+            last_submit: Mutex::new(None),
+            offline: None,
+            offline_checks: AtomicUsize::new(0),
+            cache: None,
+            state: None,
+            skip_unchanged: true,
+            save_word_lists: false,
+            variant_recheck: false,
+            #[cfg(feature = "headless")]
+            headless_fallback: bbdc_core::EnvLoader::exists("BBDC_HEADLESS_FALLBACK"),
+        }
+    }
+    
+    /// 设置验证码求解回调
+    /// 
+    /// 不设置时遇到验证码直接返回错误
+    pub fn with_captcha_solver<F>(mut self, solver: F) -> Self
+    where
+        F: Fn(&CaptchaChallenge) -> Option<String> + Send + Sync + 'static,
+    {
+        self.captcha_solver = Some(Box::new(solver));
+        self
+    }
+    
+    /// 设置进度回调，分块并发上传时会在多个线程中调用
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&CheckEvent) + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+    
+    fn emit(&self, event: CheckEvent) {
+        if let Some(callback) = &self.progress {
+            callback(&event);
+        }
+    }
+    
+    fn emit_if(&self, report: bool, event: CheckEvent) {
+        if report {
+            self.emit(event);
+        }
+    }
+    
+    /// 每次上传的单词数上限，0 表示不分块
+    /// 
+    /// 默认 2000，也可以用环境变量 `BBDC_CHUNK_SIZE` 设置
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+    
+    /// 分块上传时同时上传的块数（1~3），超出范围时报错
+    /// 
+    /// 默认 1（逐块上传），也可以用环境变量 `BBDC_CHECK_CONCURRENCY` 设置；
+    /// 服务端返回 429 或 5xx 时自动改为逐块上传
+    pub fn with_concurrency(mut self, concurrency: usize) -> Result<Self> {
+        self.concurrency = check_concurrency(concurrency)?;
+        Ok(self)
+    }
+    
+    /// 两次上传之间的最小间隔，默认 1 秒
+    /// 
+    /// 分块上传的各线程和先后核对的多个文件共用同一个间隔，验证码重试同样计入；
+    /// 也可以用环境变量 `BBDC_REQUEST_INTERVAL_MS`（毫秒）设置
+    pub fn with_politeness_delay(mut self, delay: Duration) -> Self {
+        self.politeness_delay = delay;
+        self
+    }
+    
+    /// 无法连接不背单词（连接失败、超时）时改用离线词表核对
+    pub fn with_offline_fallback(mut self, dictionary: OfflineDictionary) -> Self {
+        self.offline = Some(dictionary);
+        self
+    }
+    
+    /// 使用核对结果缓存：缓存中已识别的单词不再上传，识别成功的单词写入缓存
+    pub fn with_cache(mut self, cache: CheckCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+    
+    /// 分块核对时把每个已完成分块的结果写入状态文件，核对中断后重新运行时
+    /// 内容相同的分块直接使用保存的结果；全部完成后删除这些记录
+    /// 
+    /// 状态文件还记录每个成功核对的单词列表（去重后）的结果，再次核对相同的列表时
+    /// 直接返回上次的结果，不调用接口，见 [`with_skip_unchanged`](Self::with_skip_unchanged)
+    pub fn with_state(mut self, state: CheckState) -> Self {
+        self.state = Some(state);
+        self
+    }
+    
+    /// 单词列表与上次成功核对时相同时是否直接使用上次的结果，默认开启，需要 [`with_state`](Self::with_state)；
+    /// 关闭后总是重新核对（仍会记录本次的结果）
+    pub fn with_skip_unchanged(mut self, enabled: bool) -> Self {
+        self.skip_unchanged = enabled;
+        self
+    }
+    
+    /// 核对单词文件后，在文件旁保存 `<文件名>_recognized.txt` 和 `<文件名>_unrecognized.txt`，
+    /// 见 [`CheckResult::save_word_lists`]
+    pub fn with_word_lists(mut self, enabled: bool) -> Self {
+        self.save_word_lists = enabled;
+        self
+    }
+    
+    /// 核对后用其他写法（小写、原形、去掉派生后缀）重新核对识别失败的单词，
+    /// 识别成功的写法记录在结果的 `variants` 中，默认关闭
+    pub fn with_variant_recheck(mut self, enabled: bool) -> Self {
+        self.variant_recheck = enabled;
+        self
+    }
+    
+    /// 核对结果缓存，用于输出命中统计
+    pub fn cache(&self) -> Option<&CheckCache> {
+        self.cache.as_ref()
+    }
+    
+    /// 直接上传失败时是否改用无头浏览器提交
+    /// 
+    /// 默认由环境变量 `BBDC_HEADLESS_FALLBACK` 决定
+    #[cfg(feature = "headless")]
+    pub fn with_headless_fallback(mut self, enabled: bool) -> Self {
+        self.headless_fallback = enabled;
+        self
+    }
+    
+    /// 上传单词文件进行核对
+    /// 
+    /// 单词数超过分块大小时分块上传并按原顺序合并结果；某块的响应缺少单词（被截断）时
+    /// 把该块拆成两半重新上传
+    pub fn check_words_file<P: AsRef<Path>>(&self, file_path: P) -> Result<CheckResult> {
+        let file_path = file_path.as_ref();
+        
+        if !file_path.exists() {
+            return Err(Error::FileRead(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("文件不存在: {:?}", file_path),
+            )));
+        }
+        
+        let file_name = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .ok_or_else(|| Error::Other("无效的文件名".to_string()))?;
+        
+        let file_content = fs::read(file_path)?;
+        if file_content.iter().all(u8::is_ascii_whitespace) {
+            return Err(Error::Empty(format!("{:?} 是空文件，无需核对", file_path)));
+        }
+        
+        let text = String::from_utf8_lossy(&file_content);
+        let result = self.check(file_name, text.lines(), Some(file_path))?;
+        if self.save_word_lists {
+            let (recognized, unrecognized) = result.save_word_lists(file_path)?;
+            log::info!("已保存识别结果: {:?}、{:?}", recognized, unrecognized);
+        }
+        Ok(result)
+    }
+    
+    /// 核对单词列表，再按需用其他写法重新核对识别失败的单词
+    /// 
+    /// 使用核对进度时，单词列表（去重后）和核对设置与上次成功核对时相同则直接返回上次的结果
+    fn check<'a, I>(&self, file_name: &str, lines: I, file_path: Option<&Path>) -> Result<CheckResult>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let lines: Vec<&str> = lines.into_iter().collect();
+        let (words, removed) = dedup_words(lines.iter().copied());
+        if let (Some(state), true) = (&self.state, self.skip_unchanged) {
+            if let Some(mut result) = state.get_list(&words, self.list_settings()) {
+                log::info!("{} 的单词列表与上次成功核对时相同，使用上次的结果", file_name);
+                result.removed_count = removed;
+                self.emit(CheckEvent::Unchanged { recognized: result.recognized_count, total: result.total_count });
+                return Ok(result);
+            }
+        }
+        
+        let offline_checks = self.offline_checks.load(Ordering::Relaxed);
+        let mut result = self.check_lines(file_name, lines, file_path, true)?;
+        let mut complete = true;
+        if self.variant_recheck && !result.unrecognized_words.is_empty() {
+            if let Err(e) = self.recheck_variants(&mut result) {
+                log::warn!("用其他写法重新核对失败: {}", e);
+                complete = false;
+            }
+        }
+        // 离线词表的结果和重新核对失败的结果不保存，下次运行时重新核对
+        let online = self.offline_checks.load(Ordering::Relaxed) == offline_checks;
+        if let (Some(state), true) = (&self.state, online && complete) {
+            if let Err(e) = state.record_list(&words, self.list_settings(), &result) {
+                log::warn!("保存核对结果失败: {}", e);
+            }
+        }
+        Ok(result)
+    }
+    
+    /// 影响核对结果、需要计入单词列表记录的设置
+    fn list_settings(&self) -> &'static str {
+        if self.variant_recheck {
+            "variants"
+        } else {
+            "exact"
+        }
+    }
+    
+    /// 用 [`Lemmatizer::variants`] 生成识别失败单词的其他写法，一次性上传核对，
+    /// 每个单词取第一个识别成功的写法
+    fn recheck_variants(&self, result: &mut CheckResult) -> Result<()> {
+        let lemmatizer = Lemmatizer::new();
+        let candidates: Vec<(String, Vec<String>)> = result
+            .unrecognized_words
+            .iter()
+            .map(|word| (word.clone(), lemmatizer.variants(word)))
+            .filter(|(_, variants)| !variants.is_empty())
+            .collect();
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        
+        let lines: Vec<&str> = candidates.iter().flat_map(|(_, variants)| variants.iter().map(String::as_str)).collect();
+        log::info!("用 {} 种其他写法重新核对 {} 个识别失败的单词", lines.len(), candidates.len());
+        self.emit(CheckEvent::Variants { words: candidates.len(), variants: lines.len() });
+        let checked = self.check_lines("variants.txt", lines, None, false)?;
+        let recognized: HashSet<String> = checked.recognized_words.iter().map(|w| text::normalize_key(w)).collect();
+        let is_recognized = |variant: &String| {
+            let form = checked.normalized.get(variant).unwrap_or(variant);
+            recognized.contains(&text::normalize_key(form))
+        };
+        
+        for (word, variants) in candidates {
+            if let Some(variant) = variants.into_iter().find(is_recognized) {
+                result.variants.insert(word, variant);
+            }
+        }
+        if !result.variants.is_empty() {
+            log::info!("{} 个识别失败的单词换一种写法即可识别", result.variants.len());
+        }
+        Ok(())
+    }
+    
+    /// 核对单词列表，`file_name` 为上传时使用的文件名，`file_path` 为无头浏览器回退时上传的文件
+    ///
+    /// 上传前去掉空行和重复的单词，去掉的行数记录在结果的 `removed_count` 中；
+    /// `report` 为 false 时不发出进度事件，用于内部的重新核对
+    fn check_lines<'a, I>(&self, file_name: &str, lines: I, file_path: Option<&Path>, report: bool) -> Result<CheckResult>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let (words, removed) = dedup_words(lines);
+        if words.is_empty() {
+            return Err(Error::Empty(format!("{} 中没有需要核对的单词", file_name)));
+        }
+        if removed > 0 {
+            log::info!("去掉 {} 个空行和重复的单词，剩余 {} 个", removed, words.len());
+        }
+        let (cached, words) = match &self.cache {
+            Some(cache) => cache.partition(&words),
+            None => (Vec::new(), words),
+        };
+        if !cached.is_empty() {
+            log::info!("{} 个单词已在缓存中识别，跳过上传", cached.len());
+        }
+        let offline_checks = self.offline_checks.load(Ordering::Relaxed);
+        
+        let single = self.chunk_size == 0 || words.len() <= self.chunk_size;
+        let chunk_count = if single { usize::from(!words.is_empty()) } else { words.chunks(self.chunk_size).len() };
+        self.emit_if(report, CheckEvent::Started { words: words.len(), chunks: chunk_count });
+        
+        let result = if words.is_empty() {
+            CheckResult::merge(&[])
+        } else if single {
+            log::info!("正在上传 {} 个单词到不背单词API: {}", words.len(), file_name);
+            // 无头浏览器只能上传原文件，有单词命中缓存或被去重时不再回退
+            let file_path = file_path.filter(|_| cached.is_empty() && removed == 0);
+            self.emit_if(report, CheckEvent::ChunkStarted { index: 1, total: 1, words: words.len() });
+            let result = self.submit_content(file_name, words.join("\n").as_bytes(), file_path)?;
+            let result = self.complete_chunk(file_name, &words, result)?;
+            self.emit_if(report, CheckEvent::ChunkFinished { index: 1, total: 1, recognized: result.recognized_count });
+            result
+        } else {
+            let chunks: Vec<&[&str]> = words.chunks(self.chunk_size).collect();
+            log::info!(
+                "{} 共 {} 个单词，分 {} 块上传（并发 {}）",
+                file_name,
+                words.len(),
+                chunks.len(),
+                self.concurrency
+            );
+            let stem = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+            self.submit_chunks(stem, &chunks, report)?
+        };
+        
+        let mut result = match &self.cache {
+            Some(cache) => {
+                if self.offline_checks.load(Ordering::Relaxed) == offline_checks {
+                    cache.record(&result)?;
+                }
+                let cached = CheckResult::from_lists(cached.iter().copied(), Vec::<String>::new());
+                CheckResult::merge(&[cached, result])
+            }
+            None => result,
+        };
+        result.removed_count = removed;
+        
+        self.emit_if(report, CheckEvent::Finished { recognized: result.recognized_count, total: result.total_count });
+        log::info!(
+            "核对完成: 识别 {}/{} ({:.1}%)",
+            result.recognized_count,
+            result.total_count,
+            if result.total_count > 0 {
+                result.recognized_count as f64 / result.total_count as f64 * 100.0
+            } else {
+                0.0
+            }
+        );
+        
+        Ok(result)
+    }
+    
+    /// 分块上传：多个线程依次领取分块，上传之间的间隔由 [`wait_turn`](Self::wait_turn) 控制；
+    /// 遇到限流（429、5xx）时所有线程停止领取，剩余分块改为逐块上传。
+    /// 使用核对进度时，上次已完成的分块不再上传
+    fn submit_chunks(&self, stem: &str, chunks: &[&[&str]], report: bool) -> Result<CheckResult> {
+        let chunk_name = |i: usize| chunk_file_name(stem, i);
+        
+        let mut results: Vec<Option<CheckResult>> = match &self.state {
+            Some(state) => chunks.iter().map(|words| state.get(words)).collect(),
+            None => vec![None; chunks.len()],
+        };
+        let resumed = results.iter().flatten().count();
+        if resumed > 0 {
+            log::info!("从上次中断的核对中恢复 {}/{} 块，不再上传", resumed, chunks.len());
+            self.emit_if(report, CheckEvent::Resumed { chunks: resumed });
+        }
+        let pending: Vec<usize> = (0..chunks.len()).filter(|&i| results[i].is_none()).collect();
+        
+        let next = AtomicUsize::new(0);
+        let throttled = AtomicBool::new(false);
+        let workers = self.concurrency.min(pending.len());
+        
+        let finished = thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    scope.spawn(|| -> Result<Vec<(usize, CheckResult)>> {
+                        let mut done = Vec::new();
+                        while !throttled.load(Ordering::Relaxed) {
+                            let Some(&i) = pending.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                                break;
+                            };
+                            match self.submit_numbered(i, chunks.len(), &chunk_name(i), chunks[i], report) {
+                                Ok(result) => done.push((i, result)),
+                                Err(e) if e.is_throttled() => {
+                                    log::warn!("上传第 {} 块时被限流（{}），改为逐块上传", i + 1, e);
+                                    throttled.store(true, Ordering::Relaxed);
+                                }
+                                Err(e) => return Err(e),
+                            }
+                        }
+                        Ok(done)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|_| Err(Error::Other("上传线程异常退出".to_string()))))
+                .collect::<Result<Vec<_>>>()
+        })?;
+        for (i, result) in finished.into_iter().flatten() {
+            results[i] = Some(result);
+        }
+        
+        // 被限流或未领取的分块逐块上传
+        for (i, slot) in results.iter_mut().enumerate().filter(|(_, r)| r.is_none()) {
+            log::info!("正在上传第 {}/{} 块", i + 1, chunks.len());
+            *slot = Some(self.submit_numbered(i, chunks.len(), &chunk_name(i), chunks[i], report)?);
+        }
+        
+        if let Some(state) = &self.state {
+            if let Err(e) = state.clear(chunks) {
+                log::warn!("清除核对进度失败: {}", e);
+            }
+        }
+        
+        let results: Vec<CheckResult> = results.into_iter().flatten().collect();
+        Ok(CheckResult::merge(&results))
+    }
+    
+    /// 上传第 `i` 块（从 0 开始），`report` 时发出进度事件；使用核对进度时记录该块的结果
+    fn submit_numbered(&self, i: usize, total: usize, chunk_name: &str, words: &[&str], report: bool) -> Result<CheckResult> {
+        self.emit_if(report, CheckEvent::ChunkStarted { index: i + 1, total, words: words.len() });
+        let offline_checks = self.offline_checks.load(Ordering::Relaxed);
+        let result = self.submit_chunk(chunk_name, words)?;
+        // 离线词表的结果不保存，下次运行时重新上传
+        if let (Some(state), true) = (&self.state, self.offline_checks.load(Ordering::Relaxed) == offline_checks) {
+            if let Err(e) = state.record(words, &result) {
+                log::warn!("保存核对进度失败: {}", e);
+            }
+        }
+        self.emit_if(report, CheckEvent::ChunkFinished { index: i + 1, total, recognized: result.recognized_count });
+        Ok(result)
+    }
+    
+    /// 上传一块单词，响应缺少单词时拆分重传
+    fn submit_chunk(&self, chunk_name: &str, words: &[&str]) -> Result<CheckResult> {
+        let result = self.submit_content(chunk_name, words.join("\n").as_bytes(), None)?;
+        self.complete_chunk(chunk_name, words, result)
+    }
+    
+    /// 响应缺少单词且块足够大时拆成两半重新上传（见 [`split_truncated`]），否则返回已有结果
+    fn complete_chunk(&self, chunk_name: &str, words: &[&str], mut result: CheckResult) -> Result<CheckResult> {
+        let Some(halves) = split_truncated(chunk_name, words, &mut result) else {
+            return Ok(result);
+        };
+        let results = halves
+            .iter()
+            .map(|(name, half)| self.submit_chunk(name, half))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(CheckResult::merge(&results))
+    }
+    
+    /// 上传一份单词内容并处理验证码，`file_path` 为无头浏览器回退时上传的文件
+    fn submit_content(&self, file_name: &str, file_content: &[u8], file_path: Option<&Path>) -> Result<CheckResult> {
+        let mut captcha: Option<(String, Option<String>)> = None;
+        let mut attempts = 0;
+        
+        let data_body = loop {
+            let data_body = match self.submit(file_name, file_content, captcha.as_ref()) {
+                Ok(data_body) => data_body,
+                Err(e) if captcha.is_none() => match self.fallback_submit(file_path, e) {
+                    Ok(data_body) => data_body,
+                    Err(e) => return self.offline_check(file_name, file_content, e),
+                },
+                Err(e) => return Err(e),
+            };
+            
+            let Some(image_url) = data_body.captcha_image_url(&self.transport.endpoint()) else {
+                break data_body;
+            };
+            
+            attempts += 1;
+            if attempts > MAX_CAPTCHA_ATTEMPTS {
+                return Err(Error::CaptchaFailed { attempts: MAX_CAPTCHA_ATTEMPTS });
+            }
+            
+            let challenge = CaptchaChallenge {
+                image_path: self.save_captcha_image(&image_url),
+                image_url,
+                key: data_body.captcha_key(),
+            };
+            
+            log::warn!("不背单词要求输入验证码: {}", challenge.image_url);
+            
+            let token = self
+                .captcha_solver
+                .as_ref()
+                .and_then(|solve| solve(&challenge))
+                .ok_or_else(|| Error::CaptchaRequired { image_url: challenge.image_url.clone() })?;
+            
+            captcha = Some((token, challenge.key));
+        };
+        
+        Ok(data_body.into_check_result())
+    }
+    
+    /// 无法连接时用离线词表核对，否则原样返回错误
+    fn offline_check(&self, file_name: &str, file_content: &[u8], err: Error) -> Result<CheckResult> {
+        match &self.offline {
+            Some(dictionary) if err.is_unreachable() => {
+                log::warn!("无法连接不背单词（{}），{} 改用离线词表核对", err, file_name);
+                self.offline_checks.fetch_add(1, Ordering::Relaxed);
+                Ok(dictionary.check_content(file_content))
+            }
+            _ => Err(err),
+        }
+    }
+    
+    /// 提交单词文件，`captcha` 为 (验证码, 验证码标识)
+    fn submit(
+        &self,
+        file_name: &str,
+        file_content: &[u8],
+        captcha: Option<&(String, Option<String>)>,
+    ) -> Result<DataBody> {
+        let request = SubmitRequest {
+            file_name,
+            content: file_content,
+            captcha: captcha.map(|(token, _)| token.as_str()),
+            captcha_key: captcha.and_then(|(_, key)| key.as_deref()),
+        };
+        
+        self.wait_turn();
+        let body = self.transport.submit(&request)?;
+        parse_response(&body)
+    }
+    
+    /// 使用无头浏览器重新提交，未启用时原样返回错误
+    #[cfg(feature = "headless")]
+    fn fallback_submit(&self, file_path: Option<&Path>, err: Error) -> Result<DataBody> {
+        let (Some(file_path), true) = (file_path, self.headless_fallback) else {
+            return Err(err);
+        };
+        
+        log::warn!("直接上传失败（{}），改用无头浏览器提交", err);
+        let body = crate::headless::HeadlessSubmitter::new().submit_file(file_path)?;
+        parse_response(&body)
+    }
+    
+    #[cfg(not(feature = "headless"))]
+    fn fallback_submit(&self, _file_path: Option<&Path>, err: Error) -> Result<DataBody> {
+        Err(err)
+    }
+    
+    /// 等到距上一次上传满最小间隔后再返回，并记录本次上传的时间
+    fn wait_turn(&self) {
+        let mut last_submit = self.last_submit.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(wait) = last_submit.and_then(|last| self.politeness_delay.checked_sub(last.elapsed())) {
+            log::debug!("等待 {:?} 后上传", wait);
+            thread::sleep(wait);
+        }
+        *last_submit = Some(Instant::now());
+    }
+    
+    /// 下载验证码图片到临时目录，失败时返回 None
+    fn save_captcha_image(&self, image_url: &str) -> Option<PathBuf> {
+        let bytes = self
+            .transport
+            .fetch(image_url)
+            .map_err(|e| log::warn!("下载验证码图片失败: {}", e))
+            .ok()?;
+        
+        let path = captcha_image_path();
+        match fs::write(&path, &bytes) {
+            Ok(()) => Some(path),
+            Err(e) => {
+                log::warn!("保存验证码图片失败: {}", e);
+                None
+            }
+        }
+    }
+    
+    /// 直接核对单词列表，上传内容在内存中生成，不创建临时文件
+    ///
+    /// 无头浏览器回退需要上传文件，只在 [`check_words_file`](Self::check_words_file) 中可用
+    pub fn check_words(&self, words: &[String]) -> Result<CheckResult> {
+        self.check("words.txt", words.iter().map(String::as_str), None)
+    }
+    
+    /// 核对 Word 结构体列表
+    pub fn check_word_structs(&self, words: &[Word]) -> Result<CheckResult> {
+        let word_list: Vec<String> = words.iter().map(|w| w.word.clone()).collect();
+        self.check_words(&word_list)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+    
+    #[test]
+    #[cfg(feature = "http")]
+    fn test_checker_creation() {
+        let checker = BBDCChecker::new();
+        assert!(checker.is_ok());
+    }
+    
+    #[test]
+    fn test_check_with_mock_transport() {
+        let checker = BBDCChecker::with_transport(MockTransport::new(["apple", "banana"]));
+        let words = vec!["apple".to_string(), "banan".to_string()];
+        
+        let result = checker.check_words(&words).unwrap();
+        
+        assert_eq!(result.recognized_words, vec!["apple"]);
+        assert_eq!(result.unrecognized_words, vec!["banan"]);
+        assert_eq!(result.total_count, 2);
+    }
+    
+    #[test]
+    fn test_duplicates_removed_before_submission() {
+        let checker = BBDCChecker::with_transport(MockTransport::new(["apple"]));
+        let words: Vec<String> = ["apple", " Apple", "", "banan", "apple "].iter().map(|w| w.to_string()).collect();
+        
+        let result = checker.check_words(&words).unwrap();
+        
+        assert_eq!(result.recognized_words, ["apple"]);
+        assert_eq!(result.total_count, 2);
+        assert_eq!(result.removed_count, 3);
+        assert!(matches!(checker.check_words(&[" ".to_string()]), Err(Error::Empty(_))));
+    }
+    
+    #[test]
+    fn test_unrecognized_words_rechecked_as_variants() {
+        let words: Vec<String> = ["Apples", "happily", "xyzzy"].iter().map(|w| w.to_string()).collect();
+        let checker = BBDCChecker::with_transport(MockTransport::new(["apple", "happy"]))
+            .with_politeness_delay(Duration::ZERO)
+            .with_variant_recheck(true);
+        
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let checker = checker.with_progress(move |event| recorded.lock().unwrap().push(event.clone()));
+        let result = checker.check_words(&words).unwrap();
+        
+        let events = events.lock().unwrap().clone();
+        assert_eq!(events.iter().filter(|e| matches!(e, CheckEvent::Started { .. })).count(), 1);
+        assert!(matches!(events.last(), Some(CheckEvent::Variants { words: 2, .. })));
+        assert_eq!(result.unrecognized_count, 3);
+        assert_eq!(result.variants.len(), 2);
+        assert_eq!(result.variants["Apples"], "apple");
+        assert_eq!(result.variants["happily"], "happy");
+        
+        let result = checker.with_variant_recheck(false).check_words(&words).unwrap();
+        assert!(result.variants.is_empty());
+    }
+    
+    #[test]
+    fn test_chunked_check_falls_back_to_serial() {
+        /// 第一次提交返回 429，之后交给模拟传输层
+        struct Throttled(MockTransport, AtomicUsize);
+        
+        impl Transport for Throttled {
+            fn submit(&self, request: &SubmitRequest<'_>) -> Result<String> {
+                if self.1.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err(bbdc_core::ApiError::new(429, "请求过于频繁", Vec::new(), "").into());
+                }
+                self.0.submit(request)
+            }
+            
+            fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+                self.0.fetch(url)
+            }
+        }
+        
+        let words: Vec<String> = (0..10).map(|i| format!("word{}", i)).collect();
+        let checker = BBDCChecker::with_transport(Throttled(MockTransport::new(&words[..7]), AtomicUsize::new(0)))
+            .with_chunk_size(3)
+            .with_concurrency(3)
+            .unwrap()
+            .with_politeness_delay(Duration::ZERO);
+        
+        let path = std::env::temp_dir().join("bbdc_chunked_check.txt");
+        fs::write(&path, words.join("\n")).unwrap();
+        let result = checker.check_words_file(&path).unwrap();
+        let _ = fs::remove_file(&path);
+        
+        assert_eq!(result.total_count, 10);
+        assert_eq!(result.recognized_words, &words[..7]);
+        assert_eq!(result.unrecognized_words, &words[7..]);
+    }
+    
+    #[test]
+    fn test_concurrency_out_of_range() {
+        let checker = || BBDCChecker::with_transport(MockTransport::new(["abandon"]));
+        assert_eq!(checker().concurrency, 1);
+        assert!(matches!(checker().with_concurrency(0), Err(Error::Parse(_))));
+        assert!(matches!(checker().with_concurrency(4), Err(Error::Parse(_))));
+        assert_eq!(checker().with_concurrency(3).unwrap().concurrency, 3);
+    }
+    
+    #[test]
+    fn test_progress_events() {
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let words: Vec<String> = (0..5).map(|i| format!("word{}", i)).collect();
+        let checker = BBDCChecker::with_transport(MockTransport::new(&words[..4]))
+            .with_chunk_size(3)
+            .with_concurrency(1)
+            .unwrap()
+            .with_politeness_delay(Duration::ZERO)
+            .with_progress(move |event| recorded.lock().unwrap().push(event.clone()));
+        checker.check_words(&words).unwrap();
+        
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events[0], CheckEvent::Started { words: 5, chunks: 2 });
+        assert_eq!(events[1], CheckEvent::ChunkStarted { index: 1, total: 2, words: 3 });
+        assert_eq!(events[4], CheckEvent::ChunkFinished { index: 2, total: 2, recognized: 1 });
+        assert_eq!(events[5], CheckEvent::Finished { recognized: 4, total: 5 });
+    }
+    
+    #[test]
+    fn test_truncated_chunk_is_resubmitted() {
+        /// 模拟服务端截断：只返回每次提交的前 60 个单词
+        struct Truncating(MockTransport);
+        
+        impl Transport for Truncating {
+            fn submit(&self, request: &SubmitRequest<'_>) -> Result<String> {
+                let content = String::from_utf8_lossy(request.content);
+                let kept: Vec<&str> = content.lines().take(60).collect();
+                let kept = kept.join("\n");
+                self.0.submit(&SubmitRequest { content: kept.as_bytes(), ..*request })
+            }
+            
+            fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+                self.0.fetch(url)
+            }
+        }
+        
+        let words: Vec<String> = (0..200).map(|i| format!("word{}", i)).collect();
+        let checker = BBDCChecker::with_transport(Truncating(MockTransport::new(&words)))
+            .with_chunk_size(100)
+            .with_politeness_delay(Duration::ZERO);
+        let result = checker.check_words(&words).unwrap();
+        
+        assert_eq!(result.total_count, 200);
+        assert_eq!(result.recognized_words, words);
+    }
+    
+    #[test]
+    fn test_min_interval_between_submissions() {
+        let words: Vec<String> = (0..8).map(|i| format!("word{}", i)).collect();
+        let checker = BBDCChecker::with_transport(MockTransport::new(&words))
+            .with_chunk_size(2)
+            .with_concurrency(3)
+            .unwrap()
+            .with_politeness_delay(Duration::from_millis(20));
+        
+        let started = Instant::now();
+        let result = checker.check_words(&words).unwrap();
+        assert_eq!(result.recognized_count, 8);
+        // 4 块共 3 个间隔，并发上传也不能缩短
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+    
+    #[test]
+    fn test_cached_words_are_not_resubmitted() {
+        /// 所有提交都失败，用于确认没有上传
+        struct Unreachable;
+        
+        impl Transport for Unreachable {
+            fn submit(&self, _request: &SubmitRequest<'_>) -> Result<String> {
+                Err(bbdc_core::ApiError::new(503, "服务暂不可用", Vec::new(), "").into())
+            }
+            
+            fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+                Err(Error::Other(url.to_string()))
+            }
+        }
+        
+        let cache_path = std::env::temp_dir().join(format!("bbdc_checker_cache_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&cache_path);
+        let words = vec!["apple".to_string(), "banan".to_string()];
+        
+        let checker = BBDCChecker::with_transport(MockTransport::new(["apple"]))
+            .with_cache(CheckCache::open(&cache_path).unwrap())
+            .with_politeness_delay(Duration::ZERO);
+        assert_eq!(checker.check_words(&words).unwrap().recognized_count, 1);
+        
+        let checker = BBDCChecker::with_transport(Unreachable).with_cache(CheckCache::open(&cache_path).unwrap());
+        let result = checker.check_words(&words[..1]).unwrap();
+        assert_eq!(result.recognized_words, ["apple"]);
+        assert_eq!(checker.cache().unwrap().hits(), 1);
+        assert!(checker.check_words(&words).is_err());
+        let _ = fs::remove_file(&cache_path);
+    }
+    
+    #[test]
+    fn test_interrupted_check_resumes() {
+        /// 前 `self.1` 次提交交给模拟传输层，之后的提交都失败
+        struct Interrupted(MockTransport, usize, AtomicUsize);
+        
+        impl Transport for Interrupted {
+            fn submit(&self, request: &SubmitRequest<'_>) -> Result<String> {
+                if self.2.fetch_add(1, Ordering::SeqCst) >= self.1 {
+                    return Err(Error::Other("连接中断".to_string()));
+                }
+                self.0.submit(request)
+            }
+            
+            fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+                self.0.fetch(url)
+            }
+        }
+        
+        let state_path = std::env::temp_dir().join(format!("bbdc_checker_state_{}.json", std::process::id()));
+        let _ = fs::remove_file(&state_path);
+        let words: Vec<String> = (0..9).map(|i| format!("word{}", i)).collect();
+        let checker = |allowed: usize| {
+            BBDCChecker::with_transport(Interrupted(MockTransport::new(&words[..7]), allowed, AtomicUsize::new(0)))
+                .with_state(CheckState::open(&state_path).unwrap())
+                .with_chunk_size(3)
+                .with_concurrency(1)
+                .unwrap()
+                .with_politeness_delay(Duration::ZERO)
+        };
+        
+        assert!(checker(2).check_words(&words).is_err());
+        assert_eq!(CheckState::open(&state_path).unwrap().len(), 2);
+        
+        // 只剩最后一块需要上传
+        let result = checker(1).check_words(&words).unwrap();
+        assert_eq!(result.recognized_words, &words[..7]);
+        assert_eq!(result.unrecognized_words, &words[7..]);
+        assert_eq!(CheckState::open(&state_path).unwrap().len(), 0);
+        
+        // 单词列表没有变化，不再上传；强制核对时重新上传
+        assert_eq!(checker(0).check_words(&words).unwrap().recognized_words, &words[..7]);
+        assert!(checker(0).with_skip_unchanged(false).check_words(&words).is_err());
+        assert!(checker(0).with_variant_recheck(true).check_words(&words).is_err());
+        let _ = fs::remove_file(&state_path);
+    }
+    
+    #[test]
+    fn test_parse_captcha_challenge() {
+        let json = r#"{"data_body":{"captchaUrl":"/captcha.jpg","captchaKey":"k1"}}"#;
+        let body = parse_response(json).unwrap();
+        
+        assert_eq!(body.captcha_url.as_deref(), Some("/captcha.jpg"));
+        assert_eq!(body.captcha_key.as_deref(), Some("k1"));
+        assert!(body.know_list.is_empty());
+    }
+    
+    #[test]
+    fn test_unexpected_response_keeps_body() {
+        let html = format!("<html><title>访问验证</title>{}</html>", "x".repeat(1000));
+        let Err(Error::BadResponse { message, body }) = parse_response(&html) else {
+            panic!("HTML 响应应返回 Error::BadResponse");
+        };
+        assert!(message.contains("不是 JSON"));
+        assert!(body.starts_with("<html><title>访问验证</title>"));
+        assert_eq!(body.chars().count(), bbdc_core::ApiError::MAX_BODY_CHARS + 1);
+        
+        let e = parse_response(r#"{"result_code":403}"#).err().unwrap();
+        assert!(matches!(e, Error::EmptyDataBody { .. }), "缺少 data_body 应返回 Error::EmptyDataBody");
+        assert!(e.to_string().contains(r#"响应正文: {"result_code":403}"#));
+    }
+    
+    #[test]
+    fn test_captcha_and_missing_file_errors() {
+        struct Captcha;
+        impl Transport for Captcha {
+            fn submit(&self, _request: &SubmitRequest<'_>) -> Result<String> {
+                Ok(r#"{"data_body":{"captchaUrl":"/captcha.jpg"}}"#.to_string())
+            }
+            
+            fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+                Err(Error::Other(url.to_string()))
+            }
+            
+            fn endpoint(&self) -> BbdcEndpoint {
+                BbdcEndpoint { submit_url: "http://mirror.test/submit".to_string(), referer: "http://mirror.test/book".to_string() }
+            }
+        }
+        
+        let checker = || BBDCChecker::with_transport(Captcha).with_politeness_delay(Duration::ZERO);
+        let words = ["apple".to_string()];
+        let result = checker().check_words(&words);
+        assert!(matches!(result, Err(Error::CaptchaRequired { ref image_url }) if image_url == "http://mirror.test/captcha.jpg"));
+        let calls = std::sync::Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let result = checker()
+            .with_captcha_solver(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Some("1234".to_string())
+            })
+            .check_words(&words);
+        assert!(matches!(result, Err(Error::CaptchaFailed { attempts: MAX_CAPTCHA_ATTEMPTS })));
+        assert_eq!(calls.load(Ordering::SeqCst), MAX_CAPTCHA_ATTEMPTS);
+        let result = checker().check_words_file("bbdc_missing_words.txt");
+        assert!(matches!(result, Err(Error::FileRead(ref e)) if e.kind() == std::io::ErrorKind::NotFound));
+    }
+    
+    #[test]
+    fn test_api_errors_by_status() {
+        let error = |status: u16| Error::from(bbdc_core::ApiError::new(status, "不背单词拒绝了上传", Vec::new(), ""));
+        assert!(matches!(error(429), Error::RateLimited(_)));
+        assert!(error(429).is_throttled());
+        assert!(matches!(error(403), Error::Forbidden(_)));
+        assert!(!error(403).is_throttled());
+        assert!(matches!(error(502), Error::Api(ref e) if e.status == 502));
+    }
+    
+    #[test]
+    fn test_lenient_response_shape() {
+        let json = r#"{"dataBody":{"know_list":["apple"," banana "],"UnknowList":"banan,,appel"}}"#;
+        let result = parse_response(json).unwrap().into_check_result();
+        assert_eq!(result.recognized_words, ["apple", "banana"]);
+        assert_eq!(result.unrecognized_words, ["banan", "appel"]);
+        
+        let Err(Error::EmptyDataBody { fields, .. }) = parse_response(r#"{"data_body":{"known":"apple","unknown":""}}"#)
+        else {
+            panic!("找不到单词列表应返回 Error::EmptyDataBody");
+        };
+        assert_eq!(fields, ["known", "unknown"]);
+        assert!(matches!(parse_response(r#"{"data_body":{"knowList":42}}"#), Err(Error::BadResponse { .. })));
+    }
+}
+
@@ -0,0 +1,122 @@
+//! 核对结果缓存模块
+//!
+//! 记录不背单词已识别的单词，再次核对时直接视为识别成功，不再上传。只缓存识别成功的
+//! 单词：未识别的单词之后可能被更正或被不背单词收录，每次都重新核对
+
+use bbdc_core::{CheckResult, EnvLoader, Error, Result};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// 核对结果缓存，文件每行一个已识别的单词（小写），可以手动编辑
+#[derive(Debug)]
+pub struct CheckCache {
+    path: PathBuf,
+    recognized: Mutex<HashSet<String>>,
+    /// 本次运行命中缓存的单词数
+    hits: AtomicUsize,
+}
+
+impl CheckCache {
+    /// 默认缓存文件：`BBDC_CHECK_CACHE`，否则为 `~/.bbdc_word_tool/check_cache.txt`
+    pub fn open_default() -> Result<Self> {
+        let path = EnvLoader::get_optional("BBDC_CHECK_CACHE")
+            .map(PathBuf::from)
+            .or_else(|| EnvLoader::data_dir().map(|dir| dir.join("check_cache.txt")))
+            .ok_or_else(|| Error::Other("无法确定核对缓存位置，请设置 BBDC_CHECK_CACHE".to_string()))?;
+        Self::open(path)
+    }
+
+    /// 打开缓存文件，文件不存在时为空缓存
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let recognized: HashSet<String> = match fs::read_to_string(&path) {
+            Ok(content) => content.lines().map(|w| w.trim().to_lowercase()).filter(|w| !w.is_empty()).collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashSet::new(),
+            Err(e) => return Err(e.into()),
+        };
+        log::debug!("核对缓存 {:?}: {} 个单词", path, recognized.len());
+        Ok(Self { path, recognized: Mutex::new(recognized), hits: AtomicUsize::new(0) })
+    }
+
+    /// 缓存文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 缓存的单词数
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 本次运行命中缓存的单词数
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// 把单词分为缓存中已识别的和需要上传的
+    pub fn partition<'a>(&self, words: &[&'a str]) -> (Vec<&'a str>, Vec<&'a str>) {
+        let recognized = self.lock();
+        let (cached, pending): (Vec<&str>, Vec<&str>) =
+            words.iter().partition(|w| recognized.contains(&w.trim().to_lowercase()));
+        self.hits.fetch_add(cached.len(), Ordering::Relaxed);
+        (cached, pending)
+    }
+
+    /// 记录核对结果中识别成功的单词
+    pub fn record(&self, result: &CheckResult) -> Result<()> {
+        let mut recognized = self.lock();
+        let new: Vec<String> = result
+            .recognized_words
+            .iter()
+            .map(|w| w.trim().to_lowercase())
+            .filter(|w| !w.is_empty() && recognized.insert(w.clone()))
+            .collect();
+        if new.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        for word in &new {
+            writeln!(file, "{}", word)?;
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashSet<String>> {
+        self.recognized.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip() {
+        let path = std::env::temp_dir().join(format!("bbdc_check_cache_test_{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let cache = CheckCache::open(&path).unwrap();
+        let result = CheckResult::from_lists(["Abandon"], ["abilty"]);
+        cache.record(&result).unwrap();
+
+        let cache = CheckCache::open(&path).unwrap();
+        let (cached, pending) = cache.partition(&["abandon", "abilty", "abroad"]);
+        let _ = fs::remove_file(&path);
+        assert_eq!(cached, ["abandon"]);
+        assert_eq!(pending, ["abilty", "abroad"]);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,191 @@
+//! 核对进度模块
+//!
+//! 分块核对大文件时，把每个已完成分块的核对结果写入状态文件。核对中断（网络错误、
+//! 放弃验证码、Ctrl+C）后重新运行，内容相同的分块直接使用保存的结果，只上传未完成的分块。
+//! 全部分块完成后删除对应的记录。
+//!
+//! 状态文件同时保存最近几次成功核对的完整结果，按上传的单词列表（去重后）和影响结果的核对设置的
+//! SHA-256 索引，单词列表和设置都没有变化时可以直接使用上次的结果，不再调用接口
+
+use bbdc_core::{CheckResult, EnvLoader, Error, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 最多保存的完整核对结果数，超过时删除最早的
+const MAX_LISTS: usize = 20;
+
+/// 状态文件内容
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateFile {
+    /// 分块内容的 SHA-256 → 该块的核对结果
+    #[serde(default)]
+    chunks: BTreeMap<String, CheckResult>,
+    /// 成功核对过的单词列表，按核对时间先后排列
+    #[serde(default)]
+    lists: Vec<ListRecord>,
+}
+
+/// 一次成功核对的单词列表及其结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ListRecord {
+    /// 单词列表与核对设置的 SHA-256
+    hash: String,
+    result: CheckResult,
+}
+
+/// 分块核对进度与最近的核对结果
+#[derive(Debug)]
+pub struct CheckState {
+    path: PathBuf,
+    state: Mutex<StateFile>,
+}
+
+impl CheckState {
+    /// 默认状态文件：`BBDC_CHECK_STATE`，否则为 `~/.bbdc_word_tool/check_state.json`
+    pub fn open_default() -> Result<Self> {
+        let path = EnvLoader::get_optional("BBDC_CHECK_STATE")
+            .map(PathBuf::from)
+            .or_else(|| EnvLoader::data_dir().map(|dir| dir.join("check_state.json")))
+            .ok_or_else(|| Error::Other("无法确定核对进度文件位置，请设置 BBDC_CHECK_STATE".to_string()))?;
+        Self::open(path)
+    }
+
+    /// 打开状态文件，文件不存在或已损坏（写入时被中断）时为空
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let state: StateFile = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+                log::warn!("核对进度文件 {:?} 无法解析，重新开始: {}", path, e);
+                StateFile::default()
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => StateFile::default(),
+            Err(e) => return Err(e.into()),
+        };
+        log::debug!("核对进度 {:?}: {} 个已完成的分块，{} 个核对结果", path, state.chunks.len(), state.lists.len());
+        Ok(Self { path, state: Mutex::new(state) })
+    }
+
+    /// 状态文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// 保存的未完成分块数
+    pub fn len(&self) -> usize {
+        self.lock().chunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// 内容相同的分块上次完成时的核对结果
+    pub fn get(&self, words: &[&str]) -> Option<CheckResult> {
+        self.lock().chunks.get(&words_key(words)).cloned()
+    }
+
+    /// 记录一个已完成的分块并立即写入状态文件
+    pub fn record(&self, words: &[&str], result: &CheckResult) -> Result<()> {
+        let mut state = self.lock();
+        state.chunks.insert(words_key(words), result.clone());
+        self.save(&state)
+    }
+
+    /// 核对全部完成后删除这些分块的记录，没有剩余记录时删除状态文件
+    pub fn clear(&self, finished: &[&[&str]]) -> Result<()> {
+        let mut state = self.lock();
+        let before = state.chunks.len();
+        for words in finished {
+            state.chunks.remove(&words_key(words));
+        }
+        if state.chunks.len() == before {
+            return Ok(());
+        }
+        self.save(&state)
+    }
+
+    /// 相同单词列表在相同设置下上次成功核对的结果
+    ///
+    /// `settings` 描述影响核对结果的设置（如是否用其他写法重新核对），设置不同时不使用上次的结果
+    pub fn get_list(&self, words: &[&str], settings: &str) -> Option<CheckResult> {
+        let hash = list_key(words, settings);
+        self.lock().lists.iter().find(|record| record.hash == hash).map(|record| record.result.clone())
+    }
+
+    /// 记录单词列表在 `settings` 下成功核对的结果，只保留最近的 20 个
+    pub fn record_list(&self, words: &[&str], settings: &str, result: &CheckResult) -> Result<()> {
+        let hash = list_key(words, settings);
+        let mut state = self.lock();
+        state.lists.retain(|record| record.hash != hash);
+        state.lists.push(ListRecord { hash, result: result.clone() });
+        let excess = state.lists.len().saturating_sub(MAX_LISTS);
+        state.lists.drain(..excess);
+        self.save(&state)
+    }
+
+    /// 先写入临时文件再替换，避免中断时留下不完整的状态文件
+    fn save(&self, state: &StateFile) -> Result<()> {
+        if state.chunks.is_empty() && state.lists.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_string(state)?)?;
+        fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, StateFile> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// 分块的键：上传内容（每行一个单词）的 SHA-256
+fn words_key(words: &[&str]) -> String {
+    format!("{:x}", Sha256::digest(words.join("\n").as_bytes()))
+}
+
+/// 单词列表的键：核对设置与上传内容的 SHA-256
+fn list_key(words: &[&str], settings: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(settings.as_bytes());
+    hasher.update([0]);
+    hasher.update(words.join("\n").as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_round_trip() {
+        let path = std::env::temp_dir().join(format!("bbdc_check_state_test_{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let chunk: &[&str] = &["abandon", "abilty"];
+        let result = CheckResult::from_lists(["abandon"], ["abilty"]);
+
+        CheckState::open(&path).unwrap().record(chunk, &result).unwrap();
+        let state = CheckState::open(&path).unwrap();
+        assert_eq!(state.get(chunk).unwrap().recognized_words, ["abandon"]);
+        assert!(state.get(&["abandon"]).is_none());
+
+        state.record_list(chunk, "variants", &result).unwrap();
+        state.clear(&[chunk]).unwrap();
+        assert!(state.is_empty());
+        let state = CheckState::open(&path).unwrap();
+        assert_eq!(state.get_list(chunk, "variants").unwrap().total_count, 2);
+        assert!(state.get_list(chunk, "").is_none());
+        assert!(state.get_list(&["abandon"], "variants").is_none());
+        let _ = fs::remove_file(&path);
+    }
+}
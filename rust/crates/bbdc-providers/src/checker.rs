@@ -0,0 +1,127 @@
+//! 核对后端模块
+//!
+//! [`Checker`] 是单词识别后端的统一接口：[`BBDCChecker`] 上传到不背单词核对，
+//! [`OfflineDictionary`] 按本地词表核对。CLI 和任务队列通过 [`CheckBackend`] 选择后端，
+//! 接入有道、欧路等其他服务时实现 [`Checker`] 即可使用同一套流程
+
+use crate::bbdc_checker::dedup_words;
+use crate::{BBDCChecker, OfflineDictionary};
+use bbdc_core::{CheckResult, EnvLoader, Error, Result, Word};
+use std::fs;
+use std::path::Path;
+
+/// 单词识别后端
+pub trait Checker: Send + Sync {
+    /// 后端名称，用于输出
+    fn name(&self) -> &str;
+
+    /// 核对单词列表
+    fn check_words(&self, words: &[String]) -> Result<CheckResult>;
+
+    /// 核对单词文件（每行一个单词）
+    fn check_words_file(&self, path: &Path) -> Result<CheckResult> {
+        let content = fs::read_to_string(path)?;
+        if content.trim().is_empty() {
+            return Err(Error::Empty(format!("{:?} 是空文件，无需核对", path)));
+        }
+        let words: Vec<String> = content.lines().map(str::to_string).collect();
+        self.check_words(&words)
+    }
+
+    /// 核对 Word 结构体列表
+    fn check_word_structs(&self, words: &[Word]) -> Result<CheckResult> {
+        let words: Vec<String> = words.iter().map(|w| w.word.clone()).collect();
+        self.check_words(&words)
+    }
+}
+
+impl Checker for BBDCChecker {
+    fn name(&self) -> &str {
+        "不背单词"
+    }
+
+    fn check_words(&self, words: &[String]) -> Result<CheckResult> {
+        BBDCChecker::check_words(self, words)
+    }
+
+    fn check_words_file(&self, path: &Path) -> Result<CheckResult> {
+        BBDCChecker::check_words_file(self, path)
+    }
+}
+
+impl Checker for OfflineDictionary {
+    fn name(&self) -> &str {
+        "本地词表"
+    }
+
+    /// 与 [`BBDCChecker`] 相同，核对前去掉空行和重复的单词
+    fn check_words(&self, words: &[String]) -> Result<CheckResult> {
+        let (words, removed) = dedup_words(words.iter().map(String::as_str));
+        if words.is_empty() {
+            return Err(Error::Empty("没有需要核对的单词".to_string()));
+        }
+        let mut result = self.check_content(words.join("\n").as_bytes());
+        result.removed_count = removed;
+        Ok(result)
+    }
+}
+
+/// 核对后端
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum CheckBackend {
+    /// 上传到不背单词核对
+    #[default]
+    Bbdc,
+    /// 按 `BBDC_OFFLINE_WORDLIST` 指定的本地词表核对，不访问网络
+    Wordlist,
+}
+
+impl CheckBackend {
+    /// 读取 `BBDC_CHECKER`（`bbdc` 或 `wordlist`），未设置时为不背单词
+    pub fn from_env() -> Result<Self> {
+        match EnvLoader::get_optional("BBDC_CHECKER").as_deref().map(str::trim) {
+            None | Some("") | Some("bbdc") => Ok(Self::Bbdc),
+            Some("wordlist") => Ok(Self::Wordlist),
+            Some(other) => Err(Error::EnvVar(format!("BBDC_CHECKER 不支持 {}，可选 bbdc、wordlist", other))),
+        }
+    }
+
+    /// 按环境变量创建核对器：不背单词见 [`BBDCChecker::from_env`]，本地词表读取 `BBDC_OFFLINE_WORDLIST`
+    #[cfg(feature = "http")]
+    pub fn checker_from_env(self) -> Result<Box<dyn Checker>> {
+        match self {
+            Self::Bbdc => Ok(Box::new(BBDCChecker::from_env()?)),
+            Self::Wordlist => Ok(Box::new(Self::wordlist_from_env()?)),
+        }
+    }
+
+    /// 读取 `BBDC_OFFLINE_WORDLIST` 指定的本地词表
+    pub fn wordlist_from_env() -> Result<OfflineDictionary> {
+        let path = EnvLoader::get_optional("BBDC_OFFLINE_WORDLIST")
+            .ok_or_else(|| Error::EnvVar("使用本地词表核对时需要用 BBDC_OFFLINE_WORDLIST 指定词表".to_string()))?;
+        OfflineDictionary::load(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::MockTransport;
+
+    #[test]
+    fn test_backends_share_interface() {
+        let words: Vec<String> = ["apple", "Apple", "banan"].iter().map(|w| w.to_string()).collect();
+        let backends: Vec<Box<dyn Checker>> = vec![
+            Box::new(BBDCChecker::with_transport(MockTransport::new(["apple"]))),
+            Box::new(OfflineDictionary::parse("apple\n")),
+        ];
+
+        for backend in &backends {
+            let result = backend.check_words(&words).unwrap();
+            assert_eq!(result.recognized_words, ["apple"], "{}", backend.name());
+            assert_eq!(result.unrecognized_words, ["banan"], "{}", backend.name());
+            assert_eq!(result.removed_count, 1, "{}", backend.name());
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! 主题聚类模块
+//!
+//! 按词向量把单词分成若干语义主题（球面 k-means），每个主题以最接近中心的单词命名
+
+use crate::embeddings::cosine;
+
+/// 一个主题
+#[derive(Debug, Clone)]
+pub struct Cluster {
+    /// 主题名称：最接近主题中心的单词
+    pub label: String,
+    /// 成员在输入中的下标，按输入顺序排列
+    pub members: Vec<usize>,
+}
+
+/// 聚类结果
+#[derive(Debug, Clone, Default)]
+pub struct Clustering {
+    /// 按成员数从多到少排列
+    pub clusters: Vec<Cluster>,
+    /// 没有词向量、无法归类的单词下标
+    pub unassigned: Vec<usize>,
+}
+
+/// 最大迭代次数
+const MAX_ITERATIONS: usize = 100;
+
+/// 默认主题数：约为 `sqrt(n / 2)`
+pub fn default_cluster_count(words: usize) -> usize {
+    ((words as f64 / 2.0).sqrt().round() as usize).max(1)
+}
+
+/// 把单词聚成 `k` 个主题
+///
+/// `vectors` 与 `words` 一一对应；初始中心按最远点选取，结果是确定的
+pub fn cluster(words: &[String], vectors: &[Option<Vec<f32>>], k: usize) -> Clustering {
+    let mut unassigned = Vec::new();
+    let mut points: Vec<(usize, Vec<f32>)> = Vec::new();
+    for (i, vector) in vectors.iter().enumerate().take(words.len()) {
+        match vector.as_deref().and_then(normalized) {
+            Some(v) => points.push((i, v)),
+            None => unassigned.push(i),
+        }
+    }
+    if points.is_empty() {
+        return Clustering { clusters: Vec::new(), unassigned };
+    }
+
+    let k = k.clamp(1, points.len());
+    let mut centroids = vec![points[0].1.clone()];
+    while centroids.len() < k {
+        let farthest = points
+            .iter()
+            .min_by(|a, b| nearest(&centroids, &a.1).1.total_cmp(&nearest(&centroids, &b.1).1))
+            .map(|(_, v)| v.clone())
+            .unwrap_or_default();
+        centroids.push(farthest);
+    }
+
+    let mut assignment = vec![usize::MAX; points.len()];
+    for _ in 0..MAX_ITERATIONS {
+        let next: Vec<usize> = points.iter().map(|(_, v)| nearest(&centroids, v).0).collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+
+        for (c, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0; centroid.len()];
+            for ((_, v), _) in points.iter().zip(&assignment).filter(|(_, &a)| a == c) {
+                sum.iter_mut().zip(v).for_each(|(s, x)| *s += x);
+            }
+            if let Some(mean) = normalized(&sum) {
+                *centroid = mean;
+            }
+        }
+    }
+
+    let mut clusters: Vec<Cluster> = centroids
+        .iter()
+        .enumerate()
+        .filter_map(|(c, centroid)| {
+            let members: Vec<&(usize, Vec<f32>)> =
+                points.iter().zip(&assignment).filter(|(_, &a)| a == c).map(|(p, _)| p).collect();
+            let (label_index, _) = members
+                .iter()
+                .max_by(|a, b| cosine(&a.1, centroid).total_cmp(&cosine(&b.1, centroid)))?;
+            Some(Cluster {
+                label: words[*label_index].clone(),
+                members: members.iter().map(|(i, _)| *i).collect(),
+            })
+        })
+        .collect();
+    clusters.sort_by_key(|c| std::cmp::Reverse(c.members.len()));
+
+    Clustering { clusters, unassigned }
+}
+
+/// 最相似的中心及相似度
+fn nearest(centroids: &[Vec<f32>], v: &[f32]) -> (usize, f32) {
+    centroids
+        .iter()
+        .map(|c| cosine(c, v))
+        .enumerate()
+        .fold((0, f32::MIN), |best, (i, sim)| if sim > best.1 { (i, sim) } else { best })
+}
+
+/// 单位化，零向量返回 None
+fn normalized(v: &[f32]) -> Option<Vec<f32>> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    (norm > 0.0).then(|| v.iter().map(|x| x / norm).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cluster_topics() {
+        let words: Vec<String> = ["inflation", "biology", "tariff", "cell", "unknown", "gene"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let vectors = vec![
+            Some(vec![1.0, 0.1]),
+            Some(vec![0.1, 1.0]),
+            Some(vec![0.9, 0.0]),
+            Some(vec![0.0, 0.8]),
+            None,
+            Some(vec![0.2, 1.0]),
+        ];
+
+        let result = cluster(&words, &vectors, 2);
+        assert_eq!(result.unassigned, [4]);
+        assert_eq!(result.clusters.len(), 2);
+        assert_eq!(result.clusters[0].members, [1, 3, 5]);
+        assert_eq!(result.clusters[1].members, [0, 2]);
+        assert_eq!(default_cluster_count(50), 5);
+    }
+}
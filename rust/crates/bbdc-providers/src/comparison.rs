@@ -0,0 +1,176 @@
+//! 模型对比模块
+//!
+//! 用多个 LLM 模型更正同一组单词，统计各模型的建议是否一致以及 token 用量，
+//! 便于选出足够好且最便宜的模型
+
+use crate::llm_corrector::{CorrectionResult, LLMCorrector, TokenUsage};
+use bbdc_core::Confidence;
+use serde::Serialize;
+
+/// 单个模型的运行结果
+#[derive(Debug, Clone)]
+pub struct ModelRun {
+    pub model: String,
+    /// 与输入单词一一对应的更正结果
+    pub results: Vec<CorrectionResult>,
+    pub usage: TokenUsage,
+}
+
+impl ModelRun {
+    /// 用指定模型更正一组单词；调用失败的单词视为没有建议
+    pub fn run(llm: &LLMCorrector, words: &[String]) -> Self {
+        let before = llm.token_usage();
+        let results = words
+            .iter()
+            .map(|word| {
+                llm.correct_word(word, "").unwrap_or_else(|e| {
+                    log::warn!("⚠️  {} 更正 {} 失败: {}", llm.model(), word, e);
+                    CorrectionResult {
+                        success: false,
+                        original: word.clone(),
+                        corrected: word.clone(),
+                        confidence: Confidence::None,
+                        reason: e.to_string(),
+                    }
+                })
+            })
+            .collect();
+        let after = llm.token_usage();
+
+        Self {
+            model: llm.model().to_string(),
+            results,
+            usage: TokenUsage {
+                prompt_tokens: after.prompt_tokens - before.prompt_tokens,
+                completion_tokens: after.completion_tokens - before.completion_tokens,
+            },
+        }
+    }
+}
+
+/// 单个模型的汇总
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelSummary {
+    pub model: String,
+    /// 给出更正建议的单词数
+    pub suggested: usize,
+    pub usage: TokenUsage,
+}
+
+/// 一个单词在各模型下的建议
+#[derive(Debug, Clone, Serialize)]
+pub struct WordComparison {
+    pub original: String,
+    /// 按模型顺序排列的建议，None 表示该模型没有给出更正
+    pub suggestions: Vec<Option<String>>,
+    /// 所有模型的建议是否一致
+    pub agreed: bool,
+}
+
+/// 对比报告
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonReport {
+    pub models: Vec<ModelSummary>,
+    pub words: Vec<WordComparison>,
+    /// 建议一致的单词数
+    pub agreed: usize,
+    /// 建议一致的比例
+    pub agreement: f64,
+}
+
+impl ComparisonReport {
+    /// 各模型建议不一致的单词
+    pub fn divergences(&self) -> impl Iterator<Item = &WordComparison> {
+        self.words.iter().filter(|w| !w.agreed)
+    }
+}
+
+/// 汇总多个模型在同一组单词上的结果
+pub fn compare(runs: &[ModelRun]) -> ComparisonReport {
+    let word_count = runs.iter().map(|run| run.results.len()).min().unwrap_or(0);
+
+    let words: Vec<WordComparison> = (0..word_count)
+        .map(|i| {
+            let suggestions: Vec<Option<String>> = runs.iter().map(|run| suggestion(&run.results[i])).collect();
+            let agreed = suggestions.windows(2).all(|pair| same_suggestion(&pair[0], &pair[1]));
+            WordComparison {
+                original: runs[0].results[i].original.clone(),
+                suggestions,
+                agreed,
+            }
+        })
+        .collect();
+
+    let models = runs
+        .iter()
+        .map(|run| ModelSummary {
+            model: run.model.clone(),
+            suggested: run.results.iter().filter(|r| suggestion(r).is_some()).count(),
+            usage: run.usage,
+        })
+        .collect();
+
+    let agreed = words.iter().filter(|w| w.agreed).count();
+    let agreement = if words.is_empty() { 0.0 } else { agreed as f64 / words.len() as f64 };
+
+    ComparisonReport { models, words, agreed, agreement }
+}
+
+/// 更正结果中与原词不同的建议
+fn suggestion(result: &CorrectionResult) -> Option<String> {
+    let corrected = result.corrected.trim();
+    if result.success && !corrected.is_empty() && !corrected.eq_ignore_ascii_case(result.original.trim()) {
+        Some(corrected.to_string())
+    } else {
+        None
+    }
+}
+
+fn same_suggestion(a: &Option<String>, b: &Option<String>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.eq_ignore_ascii_case(b),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(model: &str, corrections: &[(&str, &str)], total_tokens: u64) -> ModelRun {
+        ModelRun {
+            model: model.to_string(),
+            results: corrections
+                .iter()
+                .map(|(original, corrected)| CorrectionResult {
+                    success: true,
+                    original: original.to_string(),
+                    corrected: corrected.to_string(),
+                    confidence: Confidence::High,
+                    reason: String::new(),
+                })
+                .collect(),
+            usage: TokenUsage { prompt_tokens: total_tokens, completion_tokens: 0 },
+        }
+    }
+
+    #[test]
+    fn test_agreement_and_divergences() {
+        let runs = [
+            run("small", &[("acess", "access"), ("abundnat", "abundance"), ("colour", "colour")], 100),
+            run("large", &[("acess", "Access"), ("abundnat", "abundant"), ("colour", "colour")], 400),
+        ];
+
+        let report = compare(&runs);
+        assert_eq!(report.agreed, 2);
+        assert!((report.agreement - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(report.models[0].suggested, 2);
+        assert_eq!(report.models[1].usage.total(), 400);
+
+        let divergences: Vec<_> = report.divergences().collect();
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].original, "abundnat");
+        assert_eq!(divergences[0].suggestions[1].as_deref(), Some("abundant"));
+    }
+}
@@ -0,0 +1,385 @@
+//! 词向量模块
+//!
+//! 为单词提供语义向量，供主题聚类、近义词检测等功能使用。词向量来源实现
+//! [`Embedder`]，内置：
+//! - OpenAI 兼容的嵌入接口（需要 `llm` 特性）：SiliconFlow、OpenAI，以及本地运行的
+//!   嵌入服务（Ollama、text-embeddings-inference 等，可加载 ONNX 模型），见 [`EmbeddingProvider`]
+//! - 离线 fastText `.vec` 文本格式词向量
+//!
+//! [`CachedEmbedder`] 把接口返回的向量缓存在磁盘上，重复处理同一批单词时不再请求接口
+
+use bbdc_core::{EnvLoader, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 词向量来源
+pub trait Embedder {
+    /// 模型标识，用于区分缓存
+    fn model(&self) -> &str;
+
+    /// 返回与输入一一对应的向量，没有向量的单词为 None
+    fn embed(&self, words: &[String]) -> Result<Vec<Option<Vec<f32>>>>;
+}
+
+/// 余弦相似度，任一向量为零向量时返回 0
+pub fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denom = norm(a) * norm(b);
+    if denom == 0.0 {
+        0.0
+    } else {
+        dot / denom
+    }
+}
+
+/// 离线 fastText 词向量（`.vec` 文本格式）
+pub struct FastTextVectors {
+    vectors: HashMap<String, Vec<f32>>,
+}
+
+impl FastTextVectors {
+    /// 读取 `.vec` 文件中 `words` 用到的向量（忽略大小写）
+    ///
+    /// 完整的 fastText 词向量有数 GB，只保留需要的单词以节省内存
+    pub fn load<P: AsRef<Path>>(path: P, words: &[String]) -> Result<Self> {
+        let wanted: HashSet<String> = words.iter().map(|w| w.to_lowercase()).collect();
+        let reader = BufReader::new(File::open(path)?);
+        let mut vectors = HashMap::new();
+        let mut dimension = None;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let mut parts = line.split_whitespace();
+            let Some(word) = parts.next() else { continue };
+
+            // 首行为 `<单词数> <维度>`
+            if i == 0 && parts.clone().count() == 1 && word.parse::<usize>().is_ok() {
+                continue;
+            }
+
+            let key = word.to_lowercase();
+            if !wanted.contains(&key) || vectors.contains_key(&key) {
+                continue;
+            }
+
+            let vector = parts
+                .map(str::parse::<f32>)
+                .collect::<std::result::Result<Vec<f32>, _>>()
+                .map_err(|e| Error::Parse(format!("词向量第 {} 行: {}", i + 1, e)))?;
+            if *dimension.get_or_insert(vector.len()) != vector.len() {
+                return Err(Error::Parse(format!("词向量第 {} 行维度不一致", i + 1)));
+            }
+            vectors.insert(key, vector);
+
+            if vectors.len() == wanted.len() {
+                break;
+            }
+        }
+
+        log::info!("从词向量文件读取到 {}/{} 个单词", vectors.len(), wanted.len());
+        Ok(Self { vectors })
+    }
+}
+
+impl Embedder for FastTextVectors {
+    fn model(&self) -> &str {
+        "fasttext"
+    }
+
+    fn embed(&self, words: &[String]) -> Result<Vec<Option<Vec<f32>>>> {
+        Ok(words.iter().map(|w| self.vectors.get(&w.to_lowercase()).cloned()).collect())
+    }
+}
+
+/// 嵌入接口服务商
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub enum EmbeddingProvider {
+    /// SiliconFlow（`SILICONFLOW_API_KEY`、`SILICONFLOW_EMBEDDING_URL`、`SILICONFLOW_EMBEDDING_MODEL`）
+    #[default]
+    #[cfg_attr(feature = "clap", value(name = "siliconflow"))]
+    SiliconFlow,
+    /// OpenAI（`OPENAI_API_KEY`、`OPENAI_EMBEDDING_URL`、`OPENAI_EMBEDDING_MODEL`）
+    #[cfg_attr(feature = "clap", value(name = "openai"))]
+    OpenAI,
+    /// 本地 OpenAI 兼容服务（`BBDC_EMBEDDING_URL`、`BBDC_EMBEDDING_MODEL`，可选 `BBDC_EMBEDDING_API_KEY`）
+    Local,
+}
+
+/// 接口地址、密钥和模型
+#[derive(Debug, Clone)]
+pub struct EmbeddingEndpoint {
+    pub url: String,
+    pub api_key: Option<String>,
+    pub model: String,
+}
+
+impl EmbeddingProvider {
+    /// 从环境变量读取接口配置，云端服务商缺少密钥时返回错误
+    pub fn endpoint(self) -> Result<EmbeddingEndpoint> {
+        let (key_var, url_var, default_url, model_var, default_model) = match self {
+            EmbeddingProvider::SiliconFlow => (
+                "SILICONFLOW_API_KEY",
+                "SILICONFLOW_EMBEDDING_URL",
+                "https://api.siliconflow.cn/v1/embeddings",
+                "SILICONFLOW_EMBEDDING_MODEL",
+                "BAAI/bge-m3",
+            ),
+            EmbeddingProvider::OpenAI => (
+                "OPENAI_API_KEY",
+                "OPENAI_EMBEDDING_URL",
+                "https://api.openai.com/v1/embeddings",
+                "OPENAI_EMBEDDING_MODEL",
+                "text-embedding-3-small",
+            ),
+            EmbeddingProvider::Local => (
+                "BBDC_EMBEDDING_API_KEY",
+                "BBDC_EMBEDDING_URL",
+                "http://127.0.0.1:11434/v1/embeddings",
+                "BBDC_EMBEDDING_MODEL",
+                "nomic-embed-text",
+            ),
+        };
+
+        let api_key = EnvLoader::get_optional(key_var);
+        if api_key.is_none() && self != EmbeddingProvider::Local {
+            return Err(Error::EnvVar(format!(
+                "词向量需要 {}，或改用本地服务、离线 fastText 词向量",
+                key_var
+            )));
+        }
+
+        Ok(EmbeddingEndpoint {
+            url: EnvLoader::get(url_var, Some(default_url))?,
+            api_key,
+            model: EnvLoader::get(model_var, Some(default_model))?,
+        })
+    }
+}
+
+#[cfg(feature = "llm")]
+pub use remote::HttpEmbedder;
+
+#[cfg(feature = "llm")]
+mod remote {
+    use super::{Embedder, EmbeddingEndpoint, EmbeddingProvider};
+    use bbdc_core::{Error, Result};
+    use reqwest::blocking::Client;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    /// 每次请求的单词数
+    const BATCH_SIZE: usize = 32;
+
+    /// OpenAI 兼容的嵌入接口（`POST {"model", "input": [...]}`）
+    pub struct HttpEmbedder {
+        client: Client,
+        endpoint: EmbeddingEndpoint,
+    }
+
+    #[derive(Deserialize)]
+    struct EmbeddingResponse {
+        data: Vec<EmbeddingData>,
+    }
+
+    #[derive(Deserialize)]
+    struct EmbeddingData {
+        index: usize,
+        embedding: Vec<f32>,
+    }
+
+    impl HttpEmbedder {
+        /// 使用指定接口
+        pub fn new(endpoint: EmbeddingEndpoint) -> Result<Self> {
+            let client = Client::builder()
+                .timeout(std::time::Duration::from_secs(60))
+                .build()?;
+            Ok(Self { client, endpoint })
+        }
+
+        /// 按服务商从环境变量创建
+        pub fn from_provider(provider: EmbeddingProvider) -> Result<Self> {
+            Self::new(provider.endpoint()?)
+        }
+    }
+
+    impl Embedder for HttpEmbedder {
+        fn model(&self) -> &str {
+            &self.endpoint.model
+        }
+
+        fn embed(&self, words: &[String]) -> Result<Vec<Option<Vec<f32>>>> {
+            let mut vectors = Vec::with_capacity(words.len());
+
+            for batch in words.chunks(BATCH_SIZE) {
+                let mut request = self
+                    .client
+                    .post(&self.endpoint.url)
+                    .json(&json!({ "model": self.endpoint.model, "input": batch }));
+                if let Some(api_key) = &self.endpoint.api_key {
+                    request = request.header("Authorization", format!("Bearer {}", api_key));
+                }
+
+                let response = request.send()?;
+                if !response.status().is_success() {
+                    return Err(Error::Other(format!("词向量 API 请求失败: HTTP {}", response.status())));
+                }
+
+                let mut batch_vectors = vec![None; batch.len()];
+                for data in response.json::<EmbeddingResponse>()?.data {
+                    if let Some(slot) = batch_vectors.get_mut(data.index) {
+                        *slot = Some(data.embedding);
+                    }
+                }
+                vectors.extend(batch_vectors);
+            }
+
+            Ok(vectors)
+        }
+    }
+}
+
+/// 缓存文件中的一行
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    word: String,
+    vector: Vec<f32>,
+}
+
+/// 带磁盘缓存的词向量来源
+///
+/// 每个模型一个 JSONL 缓存文件，只对缓存中没有的单词调用内部来源
+pub struct CachedEmbedder<E> {
+    inner: E,
+    path: PathBuf,
+    cache: Mutex<HashMap<String, Vec<f32>>>,
+}
+
+impl<E: Embedder> CachedEmbedder<E> {
+    /// 缓存目录：`BBDC_EMBEDDINGS_CACHE`，否则为 `~/.bbdc_word_tool/embeddings`
+    pub fn open_default(inner: E) -> Result<Self> {
+        let dir = EnvLoader::get_optional("BBDC_EMBEDDINGS_CACHE")
+            .map(PathBuf::from)
+            .or_else(|| EnvLoader::data_dir().map(|dir| dir.join("embeddings")))
+            .ok_or_else(|| Error::Other("无法确定词向量缓存位置，请设置 BBDC_EMBEDDINGS_CACHE".to_string()))?;
+        Self::open(inner, dir)
+    }
+
+    /// 使用指定的缓存目录
+    pub fn open<P: AsRef<Path>>(inner: E, dir: P) -> Result<Self> {
+        let file_name: String = inner
+            .model()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .collect();
+        let path = dir.as_ref().join(format!("{}.jsonl", file_name));
+
+        let mut cache = HashMap::new();
+        if let Ok(file) = File::open(&path) {
+            for line in BufReader::new(file).lines() {
+                if let Ok(entry) = serde_json::from_str::<CacheEntry>(&line?) {
+                    cache.insert(entry.word, entry.vector);
+                }
+            }
+        }
+        log::debug!("词向量缓存 {:?}: {} 个单词", path, cache.len());
+
+        Ok(Self { inner, path, cache: Mutex::new(cache) })
+    }
+
+    /// 缓存文件路径
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl<E: Embedder> Embedder for CachedEmbedder<E> {
+    fn model(&self) -> &str {
+        self.inner.model()
+    }
+
+    fn embed(&self, words: &[String]) -> Result<Vec<Option<Vec<f32>>>> {
+        let mut cache = self.cache.lock().map_err(|_| Error::Other("词向量缓存被锁定".to_string()))?;
+
+        let mut missing: Vec<String> = words.iter().filter(|w| !cache.contains_key(*w)).cloned().collect();
+        missing.sort();
+        missing.dedup();
+
+        if !missing.is_empty() {
+            log::info!("词向量缓存命中 {}/{}，请求 {} 个单词", words.len() - missing.len(), words.len(), missing.len());
+            let vectors = self.inner.embed(&missing)?;
+
+            if let Some(parent) = self.path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+            for (word, vector) in missing.into_iter().zip(vectors) {
+                if let Some(vector) = vector {
+                    writeln!(file, "{}", serde_json::to_string(&CacheEntry { word: word.clone(), vector: vector.clone() })?)?;
+                    cache.insert(word, vector);
+                }
+            }
+        }
+
+        Ok(words.iter().map(|w| cache.get(w).cloned()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_load_fasttext_subset() {
+        let path = std::env::temp_dir().join(format!("bbdc_vectors_test_{}.vec", std::process::id()));
+        std::fs::write(&path, "3 2\neconomy 1.0 0.0\nbiology 0.0 1.0\nunused 0.5 0.5\n").unwrap();
+
+        let words = vec!["Economy".to_string(), "biology".to_string(), "missing".to_string()];
+        let vectors = FastTextVectors::load(&path, &words).unwrap().embed(&words).unwrap();
+        assert_eq!(vectors[0].as_deref(), Some(&[1.0, 0.0][..]));
+        assert!(vectors[2].is_none());
+        assert_eq!(cosine(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+
+        std::fs::remove_file(path).ok();
+    }
+
+    struct CountingEmbedder {
+        requested: Cell<usize>,
+    }
+
+    impl Embedder for CountingEmbedder {
+        fn model(&self) -> &str {
+            "org/test-model"
+        }
+
+        fn embed(&self, words: &[String]) -> Result<Vec<Option<Vec<f32>>>> {
+            self.requested.set(self.requested.get() + words.len());
+            Ok(words.iter().map(|w| Some(vec![w.len() as f32])).collect())
+        }
+    }
+
+    #[test]
+    fn test_disk_cache() {
+        let dir = std::env::temp_dir().join(format!("bbdc_embeddings_cache_{}", std::process::id()));
+        let words = vec!["cell".to_string(), "gene".to_string(), "cell".to_string()];
+
+        let cached = CachedEmbedder::open(CountingEmbedder { requested: Cell::new(0) }, &dir).unwrap();
+        assert_eq!(cached.embed(&words).unwrap()[2].as_deref(), Some(&[4.0][..]));
+        assert_eq!(cached.inner.requested.get(), 2);
+        assert!(cached.path().ends_with("org_test-model.jsonl"));
+
+        let reopened = CachedEmbedder::open(CountingEmbedder { requested: Cell::new(0) }, &dir).unwrap();
+        reopened.embed(&words).unwrap();
+        assert_eq!(reopened.inner.requested.get(), 0);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,238 @@
+//! 更正质量评测模块
+//!
+//! 用带标注的「拼错 → 正确」数据集运行更正流程，统计不同置信度阈值下的
+//! 精确率、召回率和自动采纳准确率，便于客观比较配置改动
+
+use crate::llm_corrector::CorrectionResult;
+use bbdc_core::{Confidence, Error, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// 一条标注样本
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabeledPair {
+    pub original: String,
+    /// 正确拼写；与原词相同表示原词无需更正
+    pub expected: String,
+    #[serde(default)]
+    pub meaning: String,
+}
+
+/// 数据集中的一行，兼容 `corrections export -f eval` 的输出
+#[derive(Deserialize)]
+struct DatasetLine {
+    original: String,
+    expected: Option<String>,
+    suggested: Option<String>,
+    accepted: Option<bool>,
+    #[serde(default)]
+    meaning: String,
+}
+
+/// 读取数据集
+///
+/// 支持两种格式：
+/// - JSONL：`{"original": "acess", "expected": "access", "meaning": "通道"}`，
+///   也接受 `corrections export -f eval` 的输出（只使用已采纳的记录）
+/// - TSV：每行 `拼错<TAB>正确[<TAB>释义]`
+pub fn load_dataset<P: AsRef<Path>>(path: P) -> Result<Vec<LabeledPair>> {
+    let content = fs::read_to_string(path)?;
+    let mut pairs = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if line.starts_with('{') {
+            let parsed: DatasetLine = serde_json::from_str(line)
+                .map_err(|e| Error::Parse(format!("数据集第 {} 行: {}", i + 1, e)))?;
+            let expected = match (parsed.expected, parsed.suggested, parsed.accepted) {
+                (Some(expected), _, _) => expected,
+                (None, Some(suggested), Some(true)) => suggested,
+                _ => continue,
+            };
+            pairs.push(LabeledPair { original: parsed.original, expected, meaning: parsed.meaning });
+        } else {
+            let mut cols = line.split('\t').map(str::trim);
+            match (cols.next(), cols.next()) {
+                (Some(original), Some(expected)) if !original.is_empty() && !expected.is_empty() => {
+                    pairs.push(LabeledPair {
+                        original: original.to_string(),
+                        expected: expected.to_string(),
+                        meaning: cols.next().unwrap_or_default().to_string(),
+                    });
+                }
+                _ => return Err(Error::Parse(format!("数据集第 {} 行格式错误: {}", i + 1, line))),
+            }
+        }
+    }
+
+    Ok(pairs)
+}
+
+/// 单条样本的评测结果
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalCase {
+    pub original: String,
+    pub expected: String,
+    /// 更正流程给出的建议，没有建议时为 None
+    pub suggested: Option<String>,
+    pub confidence: Confidence,
+}
+
+/// 某个置信度阈值下的指标
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalMetrics {
+    /// 只自动采纳不低于该置信度的建议
+    pub min_confidence: Confidence,
+    /// 自动采纳的建议数
+    pub applied: usize,
+    /// 采纳且正确的建议数
+    pub correct: usize,
+    /// 需要更正的样本数
+    pub needs_correction: usize,
+    pub precision: f64,
+    pub recall: f64,
+    /// 采纳后最终拼写正确的样本比例（含无需更正的样本）
+    pub accuracy: f64,
+}
+
+/// 评测报告
+#[derive(Debug, Clone, Serialize)]
+pub struct EvalReport {
+    pub total: usize,
+    pub cases: Vec<EvalCase>,
+    pub metrics: Vec<EvalMetrics>,
+}
+
+impl EvalReport {
+    /// 某个阈值下的指标
+    pub fn at(&self, min_confidence: Confidence) -> Option<&EvalMetrics> {
+        self.metrics.iter().find(|m| m.min_confidence == min_confidence)
+    }
+}
+
+/// 对数据集运行更正流程并统计各阈值下的指标
+///
+/// `correct` 接收原词和释义，返回更正结果；调用失败的样本视为没有建议
+pub fn evaluate<F>(pairs: &[LabeledPair], mut correct: F) -> EvalReport
+where
+    F: FnMut(&str, &str) -> Result<CorrectionResult>,
+{
+    let cases: Vec<EvalCase> = pairs
+        .iter()
+        .map(|pair| {
+            let (suggested, confidence) = match correct(&pair.original, &pair.meaning) {
+                Ok(result) if result.success && !same_word(&result.corrected, &pair.original) => {
+                    (Some(result.corrected), result.confidence)
+                }
+                Ok(result) => (None, result.confidence),
+                Err(e) => {
+                    log::warn!("⚠️  更正 {} 失败: {}", pair.original, e);
+                    (None, Confidence::None)
+                }
+            };
+            EvalCase {
+                original: pair.original.clone(),
+                expected: pair.expected.clone(),
+                suggested,
+                confidence,
+            }
+        })
+        .collect();
+
+    let metrics = [Confidence::Low, Confidence::Medium, Confidence::High]
+        .into_iter()
+        .map(|threshold| metrics_at(&cases, threshold))
+        .collect();
+
+    EvalReport { total: cases.len(), cases, metrics }
+}
+
+fn metrics_at(cases: &[EvalCase], min_confidence: Confidence) -> EvalMetrics {
+    let mut applied = 0;
+    let mut correct = 0;
+    let mut final_correct = 0;
+    let mut needs_correction = 0;
+
+    for case in cases {
+        if !same_word(&case.original, &case.expected) {
+            needs_correction += 1;
+        }
+        let final_word = match &case.suggested {
+            Some(suggested) if case.confidence >= min_confidence => {
+                applied += 1;
+                if same_word(suggested, &case.expected) {
+                    correct += 1;
+                }
+                suggested
+            }
+            _ => &case.original,
+        };
+        if same_word(final_word, &case.expected) {
+            final_correct += 1;
+        }
+    }
+
+    EvalMetrics {
+        min_confidence,
+        applied,
+        correct,
+        needs_correction,
+        precision: ratio(correct, applied),
+        recall: ratio(correct, needs_correction),
+        accuracy: ratio(final_correct, cases.len()),
+    }
+}
+
+fn same_word(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+fn ratio(part: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        part as f64 / total as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_per_threshold() {
+        let pairs: Vec<LabeledPair> = [("acess", "access"), ("abundnat", "abundant"), ("colour", "colour")]
+            .iter()
+            .map(|(o, e)| LabeledPair { original: o.to_string(), expected: e.to_string(), meaning: String::new() })
+            .collect();
+
+        let report = evaluate(&pairs, |word, _| {
+            let (corrected, confidence) = match word {
+                "acess" => ("access", Confidence::High),
+                "abundnat" => ("abundance", Confidence::Low),
+                _ => ("color", Confidence::Medium),
+            };
+            Ok(CorrectionResult {
+                success: true,
+                original: word.to_string(),
+                corrected: corrected.to_string(),
+                confidence,
+                reason: String::new(),
+            })
+        });
+
+        let low = report.at(Confidence::Low).unwrap();
+        assert_eq!((low.applied, low.correct, low.needs_correction), (3, 1, 2));
+        assert!((low.accuracy - 1.0 / 3.0).abs() < 1e-9);
+
+        let high = report.at(Confidence::High).unwrap();
+        assert_eq!((high.applied, high.correct), (1, 1));
+        assert_eq!(high.precision, 1.0);
+        assert!((high.accuracy - 2.0 / 3.0).abs() < 1e-9);
+    }
+}
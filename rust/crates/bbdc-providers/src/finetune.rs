@@ -0,0 +1,81 @@
+//! 微调数据导出模块
+//!
+//! 把更正记录中的决定转换为 JSONL 数据集，用于微调或评测本地模型
+
+use crate::llm_corrector::{LLMCorrector, SYSTEM_PROMPT};
+use bbdc_core::corrections::CorrectionRecord;
+use bbdc_core::Result;
+use serde_json::json;
+use std::io::Write;
+
+/// 数据集格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum DatasetFormat {
+    /// 对话格式微调数据，只包含已采纳的更正
+    Chat,
+    /// 评测数据，包含所有已确认的决定
+    Eval,
+}
+
+/// 导出数据集，返回写入的行数；未确认的记录不会导出
+pub fn export_dataset<W: Write>(
+    records: &[CorrectionRecord],
+    format: DatasetFormat,
+    mut writer: W,
+) -> Result<usize> {
+    let mut written = 0;
+
+    for record in records {
+        let line = match (format, record.accepted) {
+            (DatasetFormat::Chat, Some(true)) => json!({
+                "messages": [
+                    { "role": "system", "content": SYSTEM_PROMPT },
+                    { "role": "user", "content": LLMCorrector::correction_prompt(&record.original, &record.meaning) },
+                    { "role": "assistant", "content": json!({
+                        "corrected": record.corrected,
+                        "confidence": "high",
+                        "reason": record.reason,
+                    }).to_string() },
+                ]
+            }),
+            (DatasetFormat::Eval, Some(accepted)) => json!({
+                "original": record.original,
+                "meaning": record.meaning,
+                "suggested": record.corrected,
+                "accepted": accepted,
+                "source": record.source,
+            }),
+            _ => continue,
+        };
+
+        writeln!(writer, "{}", line)?;
+        written += 1;
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bbdc_core::Confidence;
+
+    #[test]
+    fn test_export_only_decided_records() {
+        let mut accepted = CorrectionRecord::new("acess", "access", Confidence::High);
+        accepted.accepted = Some(true);
+        let mut rejected = CorrectionRecord::new("colour", "color", Confidence::Low);
+        rejected.accepted = Some(false);
+        let pending = CorrectionRecord::new("abundnat", "abundant", Confidence::Medium);
+        let records = [accepted, rejected, pending];
+
+        let mut chat = Vec::new();
+        assert_eq!(export_dataset(&records, DatasetFormat::Chat, &mut chat).unwrap(), 1);
+        let line: serde_json::Value = serde_json::from_slice(&chat).unwrap();
+        assert!(line["messages"][2]["content"].as_str().unwrap().contains("access"));
+
+        let mut eval = Vec::new();
+        assert_eq!(export_dataset(&records, DatasetFormat::Eval, &mut eval).unwrap(), 2);
+    }
+}
@@ -0,0 +1,209 @@
+//! 无头浏览器提交模块
+//!
+//! 当接口不再接受直接的 multipart 上传时，驱动 bbdc.cn 的上传页面完成核对（需启用 `headless` 特性）
+
+use crate::auth::{self, CookieStore};
+use crate::transport::url_path;
+use base64::Engine;
+use bbdc_core::{EnvLoader, Error, Result};
+use chromiumoxide::browser::{Browser, BrowserConfig};
+use chromiumoxide::cdp::browser_protocol::dom::SetFileInputFilesParams;
+use chromiumoxide::cdp::browser_protocol::network::{
+    CookieParam, EventLoadingFailed, EventLoadingFinished, EventResponseReceived, GetResponseBodyParams,
+    SetCookiesParams,
+};
+use futures::StreamExt;
+use std::path::Path;
+use std::time::Duration;
+
+/// 无头浏览器提交器
+pub struct HeadlessSubmitter {
+    page_url: String,
+    /// 上传接口路径，用于从页面流量中识别核对结果
+    submit_path: String,
+    chrome_path: Option<String>,
+    timeout: Duration,
+}
+
+impl HeadlessSubmitter {
+    /// 创建提交器
+    ///
+    /// 可通过 `CHROME_PATH` 指定浏览器可执行文件，上传页面和上传接口见 [`BbdcEndpoint::from_env`](crate::BbdcEndpoint::from_env)
+    pub fn new() -> Self {
+        let endpoint = crate::transport::BbdcEndpoint::from_env();
+        Self {
+            submit_path: endpoint.submit_path().to_string(),
+            page_url: endpoint.referer,
+            chrome_path: EnvLoader::get_optional("CHROME_PATH"),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// 通过上传页面提交单词文件，返回接口的原始响应
+    pub fn submit_file(&self, file_path: &Path) -> Result<String> {
+        let file_path = file_path
+            .canonicalize()
+            .map_err(|e| Error::Other(format!("无法定位单词文件: {}", e)))?;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?;
+
+        runtime.block_on(self.submit_async(&file_path))
+    }
+
+    async fn submit_async(&self, file_path: &Path) -> Result<String> {
+        let mut builder = BrowserConfig::builder();
+        if let Some(path) = &self.chrome_path {
+            builder = builder.chrome_executable(path);
+        }
+        let config = builder.build().map_err(Error::Other)?;
+
+        log::info!("启动无头浏览器...");
+        let (mut browser, mut handler) = Browser::launch(config).await.map_err(cdp_error)?;
+        let handler_task = tokio::spawn(async move {
+            while let Some(event) = handler.next().await {
+                if event.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let result = self.drive_upload(&browser, file_path).await;
+
+        let _ = browser.close().await;
+        let _ = browser.wait().await;
+        handler_task.abort();
+
+        result
+    }
+
+    /// 打开上传页面，选择文件并等待接口响应
+    ///
+    /// 打开页面前写入 [`CookieStore`] 中的登录态，与直接上传使用同一会话
+    async fn drive_upload(&self, browser: &Browser, file_path: &Path) -> Result<String> {
+        let page = browser.new_page("about:blank").await.map_err(cdp_error)?;
+        let cookies: Vec<CookieParam> = CookieStore::load_header()
+            .map(|header| auth::parse_header(&header))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|cookie| {
+                let mut param = CookieParam::new(cookie.name, cookie.value);
+                param.url = Some(self.page_url.clone());
+                param
+            })
+            .collect();
+        if !cookies.is_empty() {
+            log::debug!("写入 {} 个 Cookie", cookies.len());
+            page.execute(SetCookiesParams::new(cookies)).await.map_err(cdp_error)?;
+        }
+
+        let mut responses = page
+            .event_listener::<EventResponseReceived>()
+            .await
+            .map_err(cdp_error)?;
+        let mut finished = page
+            .event_listener::<EventLoadingFinished>()
+            .await
+            .map_err(cdp_error)?;
+        let mut failed = page
+            .event_listener::<EventLoadingFailed>()
+            .await
+            .map_err(cdp_error)?;
+        page.goto(self.page_url.as_str()).await.map_err(cdp_error)?;
+
+        let input = page
+            .find_element("input[type=file]")
+            .await
+            .map_err(|e| Error::Other(format!("上传页面中没有找到文件输入框: {}", e)))?;
+
+        page.execute(
+            SetFileInputFilesParams::builder()
+                .file(file_path.to_string_lossy())
+                .backend_node_id(input.backend_node_id)
+                .build()
+                .map_err(Error::Other)?,
+        )
+        .await
+        .map_err(cdp_error)?;
+
+        // 页面在 change 事件中触发上传
+        page.evaluate(
+            "document.querySelector('input[type=file]')\
+             .dispatchEvent(new Event('change', { bubbles: true }))",
+        )
+        .await
+        .map_err(cdp_error)?;
+
+        log::info!("已通过页面选择文件，等待核对结果...");
+
+        let request_id = tokio::time::timeout(self.timeout, async {
+            while let Some(event) = responses.next().await {
+                if url_path(&event.response.url) == self.submit_path {
+                    return Some(event.request_id.clone());
+                }
+            }
+            None
+        })
+        .await
+        .map_err(|_| Error::Other("等待上传页面响应超时".to_string()))?
+        .ok_or_else(|| Error::Other("上传页面没有发出核对请求".to_string()))?;
+
+        // 响应头先于响应体到达，等响应体接收完毕再读取
+        tokio::time::timeout(self.timeout, async {
+            loop {
+                tokio::select! {
+                    Some(event) = finished.next() => if event.request_id == request_id {
+                        return Ok(());
+                    },
+                    Some(event) = failed.next() => if event.request_id == request_id {
+                        return Err(Error::Other(format!("核对请求失败: {}", event.error_text)));
+                    },
+                    else => return Err(Error::Other("上传页面已关闭".to_string())),
+                }
+            }
+        })
+        .await
+        .map_err(|_| Error::Other("等待核对结果超时".to_string()))??;
+
+        let body = page
+            .execute(GetResponseBodyParams::new(request_id))
+            .await
+            .map_err(cdp_error)?;
+
+        decode_body(&body.result.body, body.result.base64_encoded)
+    }
+}
+
+impl Default for HeadlessSubmitter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 响应体按 `base64_encoded` 标记解码为文本
+fn decode_body(body: &str, base64_encoded: bool) -> Result<String> {
+    if !base64_encoded {
+        return Ok(body.to_string());
+    }
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(body)
+        .map_err(|e| Error::bad_response(format!("响应体不是有效的 base64: {}", e), body))?;
+    String::from_utf8(bytes).map_err(|e| Error::bad_response(format!("响应体不是 UTF-8 文本: {}", e), body))
+}
+
+fn cdp_error(e: chromiumoxide::error::CdpError) -> Error {
+    Error::Other(format!("无头浏览器错误: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_body() {
+        assert_eq!(decode_body("{\"data_code\":0}", false).unwrap(), "{\"data_code\":0}");
+        assert_eq!(decode_body("eyJkYXRhX2NvZGUiOjB9", true).unwrap(), "{\"data_code\":0}");
+        assert!(decode_body("not base64!", true).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+//! BBDC Word Tool 外部服务后端
+//!
+//! 在 `bbdc-core` 的提取结果之上提供不背单词核对、LLM 自动更正和 Mineru PDF 转换，
+//! 各外部服务的配置与可用性由 [`services`] 统一报告
+//!
+//! 作为库使用时的完整流程见 `examples/` 目录：
+//! - `extract_and_check.rs`：提取单词并用 [`MockTransport`] 离线核对
+//! - `custom_pipeline.rs`：在提取结果上自定义过滤并生成报告
+//!
+//! 可选特性：
+//! - `http`（默认）：不背单词接口核对与登录态
+//! - `pdf`（默认）：Mineru PDF 转换
+//! - `llm`（默认）：LLM 自动更正
+//! - `clap`：为公开枚举派生 `clap::ValueEnum`
+//! - `schema`：为公开类型派生 `schemars::JsonSchema`
+//! - `browser-cookies`：从本地浏览器导入 Cookie
+//! - `headless`：无头浏览器上传
+//! - `async`：异步核对器 [`AsyncBBDCChecker`]
+
+#[cfg(feature = "http")]
+pub mod auth;
+pub mod transport;
+pub mod bbdc_checker;
+pub mod offline;
+pub mod check_cache;
+pub mod check_state;
+pub mod checker;
+#[cfg(feature = "async")]
+pub mod async_checker;
+#[cfg(feature = "llm")]
+pub mod llm_corrector;
+#[cfg(feature = "llm")]
+pub mod finetune;
+#[cfg(feature = "llm")]
+pub mod evaluation;
+#[cfg(feature = "llm")]
+pub mod comparison;
+pub mod embeddings;
+pub mod clustering;
+pub mod synonyms;
+#[cfg(feature = "pdf")]
+pub mod pdf_processor;
+#[cfg(feature = "headless")]
+pub mod headless;
+pub mod services;
+
+// 重新导出常用类型
+#[cfg(feature = "http")]
+pub use auth::{Browser, CookieStore};
+#[cfg(feature = "http")]
+pub use transport::HttpTransport;
+pub use transport::{BbdcEndpoint, MockTransport, Timeouts, Transport};
+pub use bbdc_checker::{BBDCChecker, CaptchaChallenge, CheckEvent};
+pub use offline::OfflineDictionary;
+pub use check_cache::CheckCache;
+pub use check_state::CheckState;
+pub use checker::{CheckBackend, Checker};
+#[cfg(feature = "async")]
+pub use async_checker::AsyncBBDCChecker;
+#[cfg(feature = "async")]
+pub use transport::{AsyncHttpTransport, AsyncTransport};
+pub use services::{Health, Service, ServiceStatus};
+pub use bbdc_core::CheckResult;
+#[cfg(feature = "llm")]
+pub use llm_corrector::{LLMCorrector, Confidence, CorrectionResult, TokenUsage};
+#[cfg(feature = "llm")]
+pub use finetune::{export_dataset, DatasetFormat};
+pub use embeddings::{CachedEmbedder, Embedder, EmbeddingProvider, FastTextVectors};
+#[cfg(feature = "llm")]
+pub use embeddings::HttpEmbedder;
+#[cfg(feature = "pdf")]
+pub use pdf_processor::MineruClient;
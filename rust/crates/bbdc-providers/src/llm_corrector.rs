@@ -2,10 +2,15 @@
 //! 
 //! 使用 SiliconFlow API 自动更正拼写错误的单词
 
-use crate::{Error, Result, EnvLoader};
+pub use bbdc_core::Confidence;
+use bbdc_core::{Error, Result, EnvLoader, Word};
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 系统提示词
+pub const SYSTEM_PROMPT: &str = "你是一个专业的英语单词拼写检查助手。你的任务是识别和修正英语单词中的拼写错误。只返回JSON格式的结果。";
 
 /// LLM 更正器
 pub struct LLMCorrector {
@@ -13,15 +18,65 @@ pub struct LLMCorrector {
     api_key: Option<String>,
     base_url: String,
     model: String,
+    prompt_tokens: AtomicU64,
+    completion_tokens: AtomicU64,
+    strict: bool,
+}
+
+/// 累计的 token 用量
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+impl TokenUsage {
+    /// 总 token 数
+    pub fn total(&self) -> u64 {
+        self.prompt_tokens + self.completion_tokens
+    }
+}
+
+/// 解析 LLM 返回的置信度，忽略大小写，无法识别时视为低置信度
+fn confidence_from_llm(value: &str) -> Confidence {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "high" | "高" => Confidence::High,
+        "medium" | "中" => Confidence::Medium,
+        "none" => Confidence::None,
+        _ => Confidence::Low,
+    }
+}
+
+/// 取出 LLM 响应中的 JSON（可能包含在代码块中）
+fn extract_json(content: &str) -> &str {
+    let content = content.trim();
+    if content.contains("```json") {
+        content
+            .split("```json")
+            .nth(1)
+            .and_then(|s| s.split("```").next())
+            .unwrap_or(content)
+            .trim()
+    } else if content.contains("```") {
+        content
+            .split("```")
+            .nth(1)
+            .and_then(|s| s.split("```").next())
+            .unwrap_or(content)
+            .trim()
+    } else {
+        content
+    }
 }
 
 /// 更正结果
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 pub struct CorrectionResult {
     pub success: bool,
     pub original: String,
     pub corrected: String,
-    pub confidence: String,
+    pub confidence: Confidence,
     pub reason: String,
 }
 
@@ -46,6 +101,8 @@ pub struct CandidatesResult {
 #[derive(Debug, Deserialize)]
 struct ApiResponse {
     choices: Vec<Choice>,
+    #[serde(default)]
+    usage: Option<TokenUsage>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -66,6 +123,16 @@ struct LLMCorrectionResponse {
     reason: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct LLMDifficultyResponse {
+    difficulty: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct LLMPhoneticResponse {
+    ipa: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct LLMCandidatesResponse {
     candidates: Vec<CandidateInfo>,
@@ -107,9 +174,24 @@ impl LLMCorrector {
             api_key,
             base_url,
             model,
+            prompt_tokens: AtomicU64::new(0),
+            completion_tokens: AtomicU64::new(0),
+            strict: false,
         })
     }
     
+    /// 使用指定模型，覆盖 `SILICONFLOW_MODEL`
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+    
+    /// 设置严格模式：响应无法解析时返回错误，而不是记录警告后跳过或从文本中猜测
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+    
     /// 检查 LLM 功能是否启用
     pub fn is_enabled(&self) -> bool {
         self.api_key.is_some()
@@ -122,12 +204,32 @@ impl LLMCorrector {
                 success: false,
                 original: word.to_string(),
                 corrected: word.to_string(),
-                confidence: "none".to_string(),
+                confidence: Confidence::None,
                 reason: "LLM功能未启用".to_string(),
             });
         }
         
-        let prompt = format!(
+        let prompt = Self::correction_prompt(word, meaning);
+        let response = self.call_llm(&prompt)?;
+        self.parse_correction_response(word, &response)
+    }
+    
+    /// 模型名称
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+    
+    /// 到目前为止 API 返回的 token 用量
+    pub fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            prompt_tokens: self.prompt_tokens.load(Ordering::Relaxed),
+            completion_tokens: self.completion_tokens.load(Ordering::Relaxed),
+        }
+    }
+    
+    /// 单词更正使用的用户提示词，导出微调数据时也使用同一提示词
+    pub fn correction_prompt(word: &str, meaning: &str) -> String {
+        format!(
             r#"请检查以下英语单词是否有拼写错误，如果有错误请给出正确的拼写。
 
 原始单词: {}
@@ -145,10 +247,100 @@ impl LLMCorrector {
 
 只返回JSON，不要有其他内容。"#,
             word, meaning
+        )
+    }
+    
+    /// 评估单词对指定水平（如 `CET-4`）学习者的难度
+    ///
+    /// 返回 1（最简单）到 5（最难）的评分；LLM 未启用或响应无法解析时返回 None
+    pub fn rate_difficulty(&self, word: &str, meaning: &str, level: &str) -> Result<Option<u8>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        
+        let prompt = format!(
+            r#"请评估英语单词对于{}水平的学习者有多难。
+
+单词: {}
+中文释义: {}
+
+请以JSON格式返回结果，包含字段：
+- difficulty: 难度评分，1 到 5 的整数（1 表示该水平学习者早已掌握，5 表示远超该水平）
+
+示例输出：
+{{"difficulty": 3}}
+
+只返回JSON，不要有其他内容。"#,
+            level, word, meaning
         );
         
         let response = self.call_llm(&prompt)?;
-        self.parse_correction_response(word, &response)
+        let difficulty = serde_json::from_str::<LLMDifficultyResponse>(extract_json(&response))
+            .ok()
+            .map(|resp| resp.difficulty.clamp(1, 5));
+        if difficulty.is_none() {
+            if self.strict {
+                return Err(Error::Parse(format!("无法解析 {} 的难度评分: {}", word, response.trim())));
+            }
+            log::warn!("⚠️  无法解析 {} 的难度评分: {}", word, response.trim());
+        }
+        Ok(difficulty)
+    }
+    
+    /// 为一组单词评分，写入 [`Word::difficulty`](bbdc_core::Word::difficulty)，返回成功评分的数量
+    ///
+    /// 单个单词评分失败时记录警告并继续；严格模式下返回错误
+    pub fn rate_words(&self, words: &mut [Word], level: &str) -> Result<usize> {
+        let mut rated = 0;
+        for word in words.iter_mut() {
+            match self.rate_difficulty(&word.word, &word.meaning, level) {
+                Ok(Some(difficulty)) => {
+                    word.difficulty = Some(difficulty);
+                    rated += 1;
+                }
+                Ok(None) => {}
+                Err(e) if self.strict => return Err(e),
+                Err(e) => log::warn!("⚠️  评估 {} 的难度失败: {}", word.word, e),
+            }
+        }
+        Ok(rated)
+    }
+    
+    /// 生成单词的美式 IPA 音标（不含斜线）
+    ///
+    /// LLM 未启用或响应无法解析时返回 None
+    pub fn phonetic(&self, word: &str) -> Result<Option<String>> {
+        if !self.is_enabled() {
+            return Ok(None);
+        }
+        
+        let prompt = format!(
+            r#"请给出英语单词或词组的美式 IPA 音标。
+
+单词: {}
+
+请以JSON格式返回结果，包含字段：
+- ipa: IPA 音标，标出重音，不要包含斜线或方括号
+
+示例输出：
+{{"ipa": "əˈbændən"}}
+
+只返回JSON，不要有其他内容。"#,
+            word
+        );
+        
+        let response = self.call_llm(&prompt)?;
+        let ipa = serde_json::from_str::<LLMPhoneticResponse>(extract_json(&response))
+            .ok()
+            .map(|resp| resp.ipa.trim().trim_matches(|c| matches!(c, '/' | '[' | ']')).to_string())
+            .filter(|ipa| !ipa.is_empty());
+        if ipa.is_none() {
+            if self.strict {
+                return Err(Error::Parse(format!("无法解析 {} 的音标: {}", word, response.trim())));
+            }
+            log::warn!("⚠️  无法解析 {} 的音标: {}", word, response.trim());
+        }
+        Ok(ipa)
     }
     
     /// 生成候选词
@@ -207,7 +399,7 @@ impl LLMCorrector {
             "messages": [
                 {
                     "role": "system",
-                    "content": "你是一个专业的英语单词拼写检查助手。你的任务是识别和修正英语单词中的拼写错误。只返回JSON格式的结果。"
+                    "content": SYSTEM_PROMPT
                 },
                 {
                     "role": "user",
@@ -234,6 +426,10 @@ impl LLMCorrector {
         }
         
         let api_response: ApiResponse = response.json()?;
+        if let Some(usage) = api_response.usage {
+            self.prompt_tokens.fetch_add(usage.prompt_tokens, Ordering::Relaxed);
+            self.completion_tokens.fetch_add(usage.completion_tokens, Ordering::Relaxed);
+        }
         
         api_response
             .choices
@@ -246,33 +442,20 @@ impl LLMCorrector {
     fn parse_correction_response(&self, original: &str, content: &str) -> Result<CorrectionResult> {
         let content = content.trim();
         
-        // 提取 JSON（可能包含在代码块中）
-        let json_content = if content.contains("```json") {
-            content
-                .split("```json")
-                .nth(1)
-                .and_then(|s| s.split("```").next())
-                .unwrap_or(content)
-                .trim()
-        } else if content.contains("```") {
-            content
-                .split("```")
-                .nth(1)
-                .and_then(|s| s.split("```").next())
-                .unwrap_or(content)
-                .trim()
-        } else {
-            content
-        };
+        let json_content = extract_json(content);
         
         match serde_json::from_str::<LLMCorrectionResponse>(json_content) {
             Ok(resp) => Ok(CorrectionResult {
                 success: true,
                 original: original.to_string(),
                 corrected: resp.corrected,
-                confidence: resp.confidence,
+                confidence: confidence_from_llm(&resp.confidence),
                 reason: resp.reason,
             }),
+            Err(e) if self.strict => Err(Error::Parse(format!(
+                "无法解析 LLM 对 {} 的更正响应（{}）: {}",
+                original, e, content
+            ))),
             Err(_) => {
                 // 尝试从文本中提取单词
                 let words: Vec<&str> = content.split_whitespace().collect();
@@ -281,7 +464,7 @@ impl LLMCorrector {
                         success: true,
                         original: original.to_string(),
                         corrected: word.trim_matches(|c: char| !c.is_alphabetic()).to_string(),
-                        confidence: "low".to_string(),
+                        confidence: Confidence::Low,
                         reason: "从响应中提取的单词".to_string(),
                     })
                 } else {
@@ -289,7 +472,7 @@ impl LLMCorrector {
                         success: false,
                         original: original.to_string(),
                         corrected: original.to_string(),
-                        confidence: "none".to_string(),
+                        confidence: Confidence::None,
                         reason: "无法解析LLM响应".to_string(),
                     })
                 }
@@ -301,24 +484,7 @@ impl LLMCorrector {
     fn parse_candidates_response(&self, original: &str, content: &str) -> Result<CandidatesResult> {
         let content = content.trim();
         
-        // 提取 JSON
-        let json_content = if content.contains("```json") {
-            content
-                .split("```json")
-                .nth(1)
-                .and_then(|s| s.split("```").next())
-                .unwrap_or(content)
-                .trim()
-        } else if content.contains("```") {
-            content
-                .split("```")
-                .nth(1)
-                .and_then(|s| s.split("```").next())
-                .unwrap_or(content)
-                .trim()
-        } else {
-            content
-        };
+        let json_content = extract_json(content);
         
         match serde_json::from_str::<LLMCandidatesResponse>(json_content) {
             Ok(resp) => {
@@ -339,6 +505,10 @@ impl LLMCorrector {
                     reason: "success".to_string(),
                 })
             }
+            Err(e) if self.strict => Err(Error::Parse(format!(
+                "无法解析 LLM 对 {} 的候选词响应（{}）: {}",
+                original, e, content
+            ))),
             Err(e) => Ok(CandidatesResult {
                 success: false,
                 original: original.to_string(),
@@ -349,12 +519,6 @@ impl LLMCorrector {
     }
 }
 
-impl Default for LLMCorrector {
-    fn default() -> Self {
-        Self::new().expect("创建 LLMCorrector 失败")
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,5 +528,13 @@ mod tests {
         let corrector = LLMCorrector::new();
         assert!(corrector.is_ok());
     }
+    
+    #[test]
+    fn test_confidence_from_llm() {
+        assert_eq!(confidence_from_llm("High"), Confidence::High);
+        assert_eq!(confidence_from_llm(" medium "), Confidence::Medium);
+        assert_eq!(confidence_from_llm("unsure"), Confidence::Low);
+        assert!(Confidence::High > Confidence::Medium);
+        assert_eq!(serde_json::to_string(&Confidence::None).unwrap(), "\"none\"");
+    }
 }
-
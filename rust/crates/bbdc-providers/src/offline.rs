@@ -0,0 +1,93 @@
+//! 离线核对模块
+//!
+//! 无法连接不背单词时，按本地词典的单词表近似核对：在词表中的单词视为识别成功，
+//! 返回与在线核对相同的 [`CheckResult`]。词表较大，不随程序分发，可以使用
+//! ECDICT（`ecdict.csv`，取第一列）或 SCOWL（每行一个单词）
+
+use bbdc_core::{CheckResult, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// 离线词表
+#[derive(Debug, Clone, Default)]
+pub struct OfflineDictionary {
+    /// 单词（小写）
+    words: HashSet<String>,
+}
+
+impl OfflineDictionary {
+    /// 解析词表：每行取第一个逗号或制表符之前的内容作为单词，去掉两端的引号；
+    /// 空行、`#` 开头的注释和 ECDICT 的表头 `word` 被忽略
+    pub fn parse(content: &str) -> Self {
+        let words = content
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .filter_map(|line| line.split([',', '\t']).next())
+            .map(|word| word.trim().trim_matches('"').trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect::<HashSet<_>>();
+        let mut dictionary = Self { words };
+        if content.starts_with("word,") {
+            dictionary.words.remove("word");
+        }
+        dictionary
+    }
+
+    /// 读取词表文件，见 [`parse`](Self::parse)
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let dictionary = Self::parse(&fs::read_to_string(path)?);
+        log::info!("离线词表包含 {} 个单词", dictionary.len());
+        Ok(dictionary)
+    }
+
+    /// 单词是否在词表中（不区分大小写）
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.trim().to_lowercase())
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// 核对单词文件内容（每行一个单词）
+    pub fn check_content(&self, content: &[u8]) -> CheckResult {
+        let content = String::from_utf8_lossy(content);
+        let (recognized_words, unrecognized_words): (Vec<String>, Vec<String>) = content
+            .lines()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .partition(|w| self.contains(w));
+
+        CheckResult::from_lists(recognized_words, unrecognized_words)
+    }
+
+    /// 核对单词列表
+    pub fn check_words(&self, words: &[String]) -> CheckResult {
+        self.check_content(words.join("\n").as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ecdict_and_scowl() {
+        let ecdict = OfflineDictionary::parse("word,phonetic,definition\n\"'hood\",hʊd,n. 街区\nabandon,ə'bændən,vt. 放弃\n");
+        assert_eq!(ecdict.len(), 2);
+        assert!(ecdict.contains("Abandon"));
+        assert!(!ecdict.contains("word"));
+
+        let scowl = OfflineDictionary::parse("# SCOWL size 50\nability\nabroad\n");
+        let result = scowl.check_words(&["ability".to_string(), "abilty".to_string()]);
+        assert_eq!(result.recognized_words, ["ability"]);
+        assert_eq!(result.unrecognized_words, ["abilty"]);
+        assert_eq!(result.total_count, 2);
+    }
+}
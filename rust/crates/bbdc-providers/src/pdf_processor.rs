@@ -2,7 +2,8 @@
 //! 
 //! 通过 Mineru API 将 PDF 转换为 Markdown
 
-use crate::{Error, Result, EnvLoader};
+use bbdc_core::{Error, Result, EnvLoader};
+use bbdc_core::humanize::{format_duration, format_size, Locale};
 use reqwest::blocking::{Client, multipart};
 use serde::Deserialize;
 use std::fs;
@@ -97,7 +98,7 @@ impl MineruClient {
         // 3. 下载结果
         log::info!("📥 正在下载结果...");
         let zip_data = self.download_result(&result_url)?;
-        log::info!("✅ 下载完成");
+        log::info!("✅ 下载完成（{}）", format_size(zip_data.len() as u64, Locale::current()));
         
         // 4. 解压并提取 markdown
         let output_dir = output_dir
@@ -228,7 +229,7 @@ impl MineruClient {
             }
             
             if attempt % 6 == 0 {
-                log::info!("已等待 {} 分钟...", attempt / 6);
+                log::info!("已等待 {}...", format_duration(Duration::from_secs(attempt * 10), Locale::current()));
             }
         }
         
@@ -251,39 +252,24 @@ impl MineruClient {
     
     /// 解压并提取 markdown 文件
     fn extract_markdown(&self, zip_data: &[u8], output_dir: &Path) -> Result<PathBuf> {
-        use zip::ZipArchive;
-        use std::io::Cursor;
-        
-        let reader = Cursor::new(zip_data);
-        let mut archive = ZipArchive::new(reader)
-            .map_err(|e| Error::Other(format!("解压失败: {}", e)))?;
+        let entries = read_archive(zip_data)?;
         
         fs::create_dir_all(output_dir)?;
         
         let mut markdown_files = Vec::new();
         
         // 解压所有文件
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i)
-                .map_err(|e| Error::Other(format!("读取压缩文件失败: {}", e)))?;
-            
-            let file_name = file.name().to_string();
-            
-            if file.is_dir() {
-                continue;
-            }
-            
-            let output_path = output_dir.join(&file_name);
+        for (name, content) in entries {
+            let output_path = output_dir.join(&name);
             
             if let Some(parent) = output_path.parent() {
                 fs::create_dir_all(parent)?;
             }
             
-            let mut output_file = fs::File::create(&output_path)?;
-            std::io::copy(&mut file, &mut output_file)?;
+            fs::write(&output_path, content)?;
             
             // 记录 markdown 文件
-            if file_name.ends_with(".md") {
+            if name.extension().is_some_and(|ext| ext == "md") {
                 markdown_files.push(output_path);
             }
         }
@@ -296,9 +282,82 @@ impl MineruClient {
     }
 }
 
-impl Default for MineruClient {
-    fn default() -> Self {
-        Self::new().expect("创建 MineruClient 失败")
+/// 解压后允许的最大总字节数，防止压缩炸弹
+const MAX_EXTRACTED_BYTES: u64 = 512 * 1024 * 1024;
+
+/// 读取压缩包中的所有文件
+///
+/// 返回相对路径和内容。绝对路径、包含 `..` 等会逃出解压目录的条目被跳过，
+/// 解压总量超过 [`MAX_EXTRACTED_BYTES`] 时返回错误
+pub fn read_archive(zip_data: &[u8]) -> Result<Vec<(PathBuf, Vec<u8>)>> {
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+    
+    let mut archive = ZipArchive::new(Cursor::new(zip_data))
+        .map_err(|e| Error::Other(format!("解压失败: {}", e)))?;
+    
+    let mut entries = Vec::new();
+    let mut remaining = MAX_EXTRACTED_BYTES;
+    
+    for i in 0..archive.len() {
+        let file = archive.by_index(i)
+            .map_err(|e| Error::Other(format!("读取压缩文件失败: {}", e)))?;
+        
+        if file.is_dir() {
+            continue;
+        }
+        
+        let Some(name) = file.enclosed_name().map(Path::to_path_buf) else {
+            log::warn!("⚠️  跳过不安全的压缩包路径: {:?}", file.name());
+            continue;
+        };
+        
+        // 多读一个字节，用来判断是否超出上限
+        let mut content = Vec::new();
+        file.take(remaining + 1)
+            .read_to_end(&mut content)
+            .map_err(|e| Error::Other(format!("读取压缩文件失败: {}", e)))?;
+        if content.len() as u64 > remaining {
+            return Err(Error::Other("压缩包解压后过大".to_string()));
+        }
+        remaining -= content.len() as u64;
+        
+        entries.push((name, content));
     }
+    
+    Ok(entries)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    
+    #[test]
+    fn test_read_archive_skips_unsafe_paths() {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::FileOptions::default();
+        for name in ["../evil.md", "/etc/evil.md", "out/unit1.md"] {
+            writer.start_file(name, options).unwrap();
+            writer.write_all(b"<table></table>").unwrap();
+        }
+        let zip_data = writer.finish().unwrap().into_inner();
+        
+        let entries = read_archive(&zip_data).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].0, PathBuf::from("out/unit1.md"));
+        
+        assert!(read_archive(b"not a zip").is_err());
+    }
+    
+    #[test]
+    fn test_fuzz_seeds_do_not_panic() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/../../fuzz/seeds/read_archive");
+        for entry in fs::read_dir(dir).unwrap() {
+            let zip_data = fs::read(entry.unwrap().path()).unwrap();
+            if let Ok(entries) = read_archive(&zip_data) {
+                assert!(entries.iter().all(|(name, _)| name.is_relative()));
+            }
+        }
+    }
+}
@@ -0,0 +1,323 @@
+//! 外部服务状态模块
+//!
+//! 不背单词、SiliconFlow、Mineru 等外部服务实现统一的 [`Service`] 接口，报告是否已配置、
+//! 能否访问以及剩余额度。`env` 命令、守护进程的 `/status` 接口以及服务不可用时的降级处理
+//! （[`available`]）都读取这里的状态，新增服务时只需实现该接口并加入 [`all`]
+
+use serde::Serialize;
+use std::fmt;
+
+/// 外部服务
+pub trait Service: Send + Sync {
+    /// 显示名称
+    fn name(&self) -> &str;
+
+    /// 必需的配置（密钥、令牌等）是否已设置，不访问网络
+    fn is_configured(&self) -> bool;
+
+    /// 访问服务检查是否可用
+    fn check_health(&self) -> Health;
+
+    /// 查询剩余额度，服务不提供或查询失败时为 None
+    fn quota(&self) -> Option<Quota> {
+        None
+    }
+}
+
+/// 服务可用性
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", content = "reason", rename_all = "snake_case")]
+pub enum Health {
+    Healthy,
+    /// 缺少必需的配置
+    Unconfigured,
+    /// 无法访问或认证失败，附带原因
+    Unhealthy(String),
+}
+
+impl Health {
+    pub fn is_healthy(&self) -> bool {
+        matches!(self, Health::Healthy)
+    }
+}
+
+impl fmt::Display for Health {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Health::Healthy => f.write_str("可用"),
+            Health::Unconfigured => f.write_str("未配置"),
+            Health::Unhealthy(reason) => write!(f, "不可用（{}）", reason),
+        }
+    }
+}
+
+/// 剩余额度
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Quota {
+    pub remaining: f64,
+    /// 单位，如 `元`
+    pub unit: String,
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}", self.remaining, self.unit)
+    }
+}
+
+/// 一个服务的状态快照
+#[derive(Debug, Clone, Serialize)]
+pub struct ServiceStatus {
+    pub name: String,
+    pub configured: bool,
+    /// 没有探测时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<Health>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quota: Option<Quota>,
+}
+
+impl ServiceStatus {
+    /// 只读取配置，不访问网络
+    pub fn configured(service: &dyn Service) -> Self {
+        Self {
+            name: service.name().to_string(),
+            configured: service.is_configured(),
+            health: None,
+            quota: None,
+        }
+    }
+
+    /// 访问服务检查可用性并查询额度，未配置的服务不访问
+    pub fn probe(service: &dyn Service) -> Self {
+        let mut status = Self::configured(service);
+        if !status.configured {
+            status.health = Some(Health::Unconfigured);
+            return status;
+        }
+        let health = service.check_health();
+        if health.is_healthy() {
+            status.quota = service.quota();
+        }
+        status.health = Some(health);
+        status
+    }
+}
+
+/// 降级策略：服务已配置且可用时返回 true；否则记录警告并返回 false，由调用方跳过 `purpose`
+pub fn available(service: &dyn Service, purpose: &str) -> bool {
+    let health = if service.is_configured() {
+        service.check_health()
+    } else {
+        Health::Unconfigured
+    };
+    if !health.is_healthy() {
+        log::warn!("⚠️  {} {}，跳过{}", service.name(), health, purpose);
+    }
+    health.is_healthy()
+}
+
+/// 当前构建支持的所有外部服务，按环境变量配置
+pub fn all() -> Vec<Box<dyn Service>> {
+    let services: Vec<Box<dyn Service>> = vec![
+        #[cfg(feature = "http")]
+        Box::new(http::BbdcService::from_env()),
+        #[cfg(feature = "llm")]
+        Box::new(http::SiliconFlowService::from_env()),
+        #[cfg(feature = "pdf")]
+        Box::new(http::MineruService::from_env()),
+    ];
+    services
+}
+
+#[cfg(feature = "http")]
+pub use http::BbdcService;
+#[cfg(feature = "llm")]
+pub use http::SiliconFlowService;
+#[cfg(feature = "pdf")]
+pub use http::MineruService;
+
+#[cfg(feature = "http")]
+mod http {
+    use super::{Health, Service};
+    #[cfg(feature = "llm")]
+    use super::Quota;
+    #[cfg(any(feature = "llm", feature = "pdf"))]
+    use bbdc_core::EnvLoader;
+    use reqwest::blocking::{Client, RequestBuilder};
+    use std::time::Duration;
+
+    /// 探测请求的超时时间
+    const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+    fn probe_client() -> Client {
+        Client::builder().timeout(PROBE_TIMEOUT).build().unwrap_or_default()
+    }
+
+    /// 发送探测请求：401/403 视为认证失败，5xx 和网络错误视为不可用，其余视为可用
+    fn probe(request: RequestBuilder) -> Health {
+        match request.send() {
+            Ok(response) => match response.status().as_u16() {
+                401 | 403 => Health::Unhealthy("认证失败，请检查密钥".to_string()),
+                status @ 500..=599 => Health::Unhealthy(format!("HTTP {}", status)),
+                _ => Health::Healthy,
+            },
+            Err(e) => Health::Unhealthy(e.to_string()),
+        }
+    }
+
+    /// 不背单词：核对接口无需登录，登录后可以使用账号下的词书
+    pub struct BbdcService {
+        client: Client,
+    }
+
+    impl BbdcService {
+        pub fn from_env() -> Self {
+            Self { client: probe_client() }
+        }
+    }
+
+    impl Service for BbdcService {
+        fn name(&self) -> &str {
+            "不背单词"
+        }
+
+        fn is_configured(&self) -> bool {
+            true
+        }
+
+        fn check_health(&self) -> Health {
+            // 模拟核对不访问不背单词
+            if EnvLoader::exists(crate::transport::MOCK_CHECK_ENV) {
+                return Health::Healthy;
+            }
+            probe(self.client.get(crate::transport::BbdcEndpoint::from_env().referer))
+        }
+    }
+
+    /// SiliconFlow：LLM 自动更正、难度评估和词向量
+    #[cfg(feature = "llm")]
+    pub struct SiliconFlowService {
+        client: Client,
+        api_key: Option<String>,
+        /// 接口根地址，如 `https://api.siliconflow.cn/v1`
+        api_root: String,
+    }
+
+    #[cfg(feature = "llm")]
+    impl SiliconFlowService {
+        /// 读取 `SILICONFLOW_API_KEY`，接口地址由 `SILICONFLOW_BASE_URL` 推出
+        pub fn from_env() -> Self {
+            let base_url = EnvLoader::get_optional("SILICONFLOW_BASE_URL")
+                .unwrap_or_else(|| "https://api.siliconflow.cn/v1/chat/completions".to_string());
+            Self {
+                client: probe_client(),
+                api_key: EnvLoader::get_optional("SILICONFLOW_API_KEY"),
+                api_root: base_url.trim_end_matches("/chat/completions").to_string(),
+            }
+        }
+
+        fn get(&self, path: &str) -> RequestBuilder {
+            self.client
+                .get(format!("{}{}", self.api_root, path))
+                .bearer_auth(self.api_key.as_deref().unwrap_or_default())
+        }
+    }
+
+    #[cfg(feature = "llm")]
+    impl Service for SiliconFlowService {
+        fn name(&self) -> &str {
+            "SiliconFlow"
+        }
+
+        fn is_configured(&self) -> bool {
+            self.api_key.is_some()
+        }
+
+        fn check_health(&self) -> Health {
+            probe(self.get("/models"))
+        }
+
+        /// 账户余额（`/user/info` 中的 `totalBalance`）
+        fn quota(&self) -> Option<Quota> {
+            let body: serde_json::Value = self.get("/user/info").send().ok()?.json().ok()?;
+            let balance = &body["data"]["totalBalance"];
+            let remaining = balance.as_f64().or_else(|| balance.as_str()?.parse().ok())?;
+            Some(Quota { remaining, unit: "元".to_string() })
+        }
+    }
+
+    /// Mineru：PDF 转 Markdown
+    #[cfg(feature = "pdf")]
+    pub struct MineruService {
+        client: Client,
+        api_token: Option<String>,
+        base_url: String,
+    }
+
+    #[cfg(feature = "pdf")]
+    impl MineruService {
+        /// 读取 `MINERU_API_TOKEN` 和 `MINERU_BASE_URL`
+        pub fn from_env() -> Self {
+            Self {
+                client: probe_client(),
+                api_token: EnvLoader::get_optional("MINERU_API_TOKEN"),
+                base_url: EnvLoader::get_optional("MINERU_BASE_URL")
+                    .unwrap_or_else(|| "https://mineru.net/api/v4".to_string()),
+            }
+        }
+    }
+
+    #[cfg(feature = "pdf")]
+    impl Service for MineruService {
+        fn name(&self) -> &str {
+            "Mineru"
+        }
+
+        fn is_configured(&self) -> bool {
+            self.api_token.is_some()
+        }
+
+        /// 接口根地址能够访问、令牌未被拒绝即视为可用
+        fn check_health(&self) -> Health {
+            probe(self.client.get(&self.base_url).bearer_auth(self.api_token.as_deref().unwrap_or_default()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Offline;
+
+    impl Service for Offline {
+        fn name(&self) -> &str {
+            "离线服务"
+        }
+
+        fn is_configured(&self) -> bool {
+            true
+        }
+
+        fn check_health(&self) -> Health {
+            Health::Unhealthy("网络不可达".to_string())
+        }
+
+        fn quota(&self) -> Option<Quota> {
+            panic!("不可用的服务不应查询额度")
+        }
+    }
+
+    #[test]
+    fn test_probe_and_degrade() {
+        let status = ServiceStatus::probe(&Offline);
+        assert!(status.configured);
+        assert_eq!(status.health, Some(Health::Unhealthy("网络不可达".to_string())));
+        assert!(!available(&Offline, "自动核对"));
+
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["health"]["state"], "unhealthy");
+        assert!(json.get("quota").is_none());
+    }
+}
@@ -0,0 +1,120 @@
+//! 近义词检测模块
+//!
+//! 按词向量的余弦相似度找出词书中意思相近的单词对，可以合并或互相标注，
+//! 减少大型汇总词书中的冗余
+
+use crate::embeddings::cosine;
+use bbdc_core::Word;
+use serde::Serialize;
+
+/// 默认相似度阈值
+pub const DEFAULT_THRESHOLD: f32 = 0.85;
+
+/// 一对近义词，`first` 在词书中位于 `second` 之前
+#[derive(Debug, Clone, Serialize)]
+pub struct SimilarPair {
+    pub first: usize,
+    pub second: usize,
+    pub similarity: f32,
+}
+
+/// 找出相似度不低于 `threshold` 的单词对，按相似度从高到低排列
+///
+/// 拼写相同（忽略大小写）的单词不算近义词
+pub fn similar_pairs(words: &[Word], vectors: &[Option<Vec<f32>>], threshold: f32) -> Vec<SimilarPair> {
+    let mut pairs = Vec::new();
+    for (i, a) in vectors.iter().enumerate().take(words.len()) {
+        let Some(a) = a else { continue };
+        for (j, b) in vectors.iter().enumerate().take(words.len()).skip(i + 1) {
+            let Some(b) = b else { continue };
+            if words[i].word.eq_ignore_ascii_case(&words[j].word) {
+                continue;
+            }
+            let similarity = cosine(a, b);
+            if similarity >= threshold {
+                pairs.push(SimilarPair { first: i, second: j, similarity });
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+    pairs
+}
+
+/// 合并近义词：保留先出现的单词，把后者的释义追加到其释义后并移除后者
+///
+/// 返回被移除的单词数
+pub fn merge_similar(words: &mut Vec<Word>, pairs: &[SimilarPair]) -> usize {
+    let mut removed = vec![false; words.len()];
+    for pair in pairs {
+        if removed[pair.first] || removed[pair.second] {
+            continue;
+        }
+        removed[pair.second] = true;
+
+        let (keep, other) = (pair.first, pair.second);
+        let addition = match words[other].meaning.trim() {
+            "" => format!("（近义：{}）", words[other].word),
+            meaning => format!("（近义：{} {}）", words[other].word, meaning),
+        };
+        words[keep].meaning.push_str(&addition);
+    }
+
+    let mut index = 0;
+    words.retain(|_| {
+        index += 1;
+        !removed[index - 1]
+    });
+    removed.iter().filter(|&&r| r).count()
+}
+
+/// 在释义后互相标注近义词，不移除单词
+pub fn annotate_similar(words: &mut [Word], pairs: &[SimilarPair]) {
+    for pair in pairs {
+        let first = words[pair.first].word.clone();
+        let second = words[pair.second].word.clone();
+        words[pair.first].meaning.push_str(&format!("（近义：{}）", second));
+        words[pair.second].meaning.push_str(&format!("（近义：{}）", first));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word: &str, meaning: &str) -> Word {
+        Word {
+            number: String::new(),
+            word: word.to_string(),
+            meaning: meaning.to_string(),
+            phonetic: None,
+            example: None,
+            pos: Vec::new(),
+            line_number: None,
+            page: None,
+            frequency: None,
+            difficulty: None,
+            level: None,
+            chapter: None,
+            source: None,
+        }
+    }
+
+    #[test]
+    fn test_find_and_merge_synonyms() {
+        let mut words = vec![word("big", "大的"), word("cell", "细胞"), word("large", "大的"), word("huge", "")];
+        let vectors = vec![
+            Some(vec![1.0, 0.0]),
+            Some(vec![0.0, 1.0]),
+            Some(vec![0.95, 0.1]),
+            Some(vec![0.9, 0.2]),
+        ];
+
+        let pairs = similar_pairs(&words, &vectors, 0.9);
+        assert_eq!(pairs.len(), 3);
+        assert_eq!((pairs[0].first, pairs[0].second), (0, 2));
+
+        assert_eq!(merge_similar(&mut words, &pairs), 2);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].meaning, "大的（近义：large 大的）（近义：huge）");
+    }
+}
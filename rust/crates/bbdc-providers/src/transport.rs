@@ -0,0 +1,495 @@
+//! 传输层模块
+//!
+//! 把核对器与网络请求解耦：`HttpTransport` 访问真实接口，
+//! `MockTransport` 按本地单词表离线生成响应，用于示例、测试和试运行；
+//! 启用 `async` 特性时另有异步的 [`AsyncTransport`]，供 [`AsyncBBDCChecker`](crate::AsyncBBDCChecker) 使用
+
+use bbdc_core::{Error, Result};
+#[cfg(feature = "http")]
+use bbdc_core::ApiError;
+#[cfg(feature = "http")]
+use crate::CookieStore;
+#[cfg(feature = "http")]
+use reqwest::blocking::{multipart, Client};
+#[cfg(feature = "async")]
+use futures::future::BoxFuture;
+use serde_json::json;
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// 一次单词文件提交
+#[derive(Debug, Clone, Copy)]
+pub struct SubmitRequest<'a> {
+    pub file_name: &'a str,
+    pub content: &'a [u8],
+    /// 用户输入的验证码
+    pub captcha: Option<&'a str>,
+    /// 服务端下发的验证码标识
+    pub captcha_key: Option<&'a str>,
+}
+
+/// 设置后核对使用 [`MockTransport`]，不访问不背单词：`all` 为全部识别成功，否则为单词表文件路径
+pub const MOCK_CHECK_ENV: &str = "BBDC_MOCK_CHECK";
+
+/// 默认的单词文件上传地址
+pub const DEFAULT_SUBMIT_URL: &str = "https://bbdc.cn/lexis/book/file/submit";
+
+/// 默认的上传页面，作为请求的 Referer
+pub const DEFAULT_REFERER: &str = "https://bbdc.cn/lexis_book_index";
+
+/// 浏览器的 User-Agent
+#[cfg(feature = "http")]
+const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36";
+
+/// 上传时附带的请求头，与网页上传一致（Origin、Referer 取自 [`BbdcEndpoint`]）
+#[cfg(feature = "http")]
+const SUBMIT_HEADERS: [(&str, &str); 3] = [
+    ("Accept", "application/json, text/javascript, */*; q=0.01"),
+    ("Accept-Language", "zh-CN,zh;q=0.9,en;q=0.8"),
+    ("X-Requested-With", "XMLHttpRequest"),
+];
+
+/// 核对请求的超时设置
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// 建立连接的超时
+    pub connect: Duration,
+    /// 整个请求（含上传和读取响应）的超时，大文件在慢速网络上需要调大
+    pub request: Duration,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self { connect: Duration::from_secs(10), request: Duration::from_secs(120) }
+    }
+}
+
+impl Timeouts {
+    /// 读取 `BBDC_CONNECT_TIMEOUT_SECS`、`BBDC_REQUEST_TIMEOUT_SECS`，未设置或无法解析时使用默认值
+    pub fn from_env() -> Self {
+        let env_secs = |key: &str| {
+            bbdc_core::EnvLoader::get_optional(key)
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|&secs| secs > 0)
+                .map(Duration::from_secs)
+        };
+        let default = Self::default();
+        Self {
+            connect: env_secs("BBDC_CONNECT_TIMEOUT_SECS").unwrap_or(default.connect),
+            request: env_secs("BBDC_REQUEST_TIMEOUT_SECS").unwrap_or(default.request),
+        }
+    }
+}
+
+/// 不背单词接口地址，可以指向镜像、代理或测试环境
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BbdcEndpoint {
+    /// 单词文件上传地址
+    pub submit_url: String,
+    /// 上传页面，作为请求的 Referer，无头浏览器回退也打开这个页面
+    pub referer: String,
+}
+
+impl Default for BbdcEndpoint {
+    fn default() -> Self {
+        Self { submit_url: DEFAULT_SUBMIT_URL.to_string(), referer: DEFAULT_REFERER.to_string() }
+    }
+}
+
+impl BbdcEndpoint {
+    /// 读取 `BBDC_SUBMIT_URL`、`BBDC_REFERER`，未设置时使用不背单词官网的地址
+    pub fn from_env() -> Self {
+        let env = |key: &str, default: &str| {
+            bbdc_core::EnvLoader::get_optional(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| default.to_string())
+        };
+        Self { submit_url: env("BBDC_SUBMIT_URL", DEFAULT_SUBMIT_URL), referer: env("BBDC_REFERER", DEFAULT_REFERER) }
+    }
+
+    /// 上传页面的源（协议、主机和端口），作为 Origin 请求头
+    pub fn origin(&self) -> &str {
+        let host_start = self.referer.find("://").map_or(0, |i| i + 3);
+        match self.referer[host_start..].find('/') {
+            Some(i) => &self.referer[..host_start + i],
+            None => &self.referer,
+        }
+    }
+
+    /// 上传地址的路径（不含查询参数），无头浏览器回退据此从页面流量中识别核对结果
+    pub fn submit_path(&self) -> &str {
+        url_path(&self.submit_url)
+    }
+
+    /// 把服务端返回的相对地址（如验证码图片 `/captcha.jpg`）补全为绝对地址
+    pub fn resolve(&self, url: &str) -> String {
+        if url.starts_with('/') {
+            format!("{}{}", self.origin(), url)
+        } else {
+            url.to_string()
+        }
+    }
+}
+
+/// 网址的路径部分（不含协议、主机、查询参数和片段），没有路径时为 `/`
+pub(crate) fn url_path(url: &str) -> &str {
+    let rest = url.find("://").map_or(url, |i| &url[i + 3..]);
+    let path = rest.find('/').map_or("", |i| &rest[i..]);
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+/// 核对请求的传输层
+pub trait Transport: Send + Sync {
+    /// 提交单词文件，返回响应正文
+    fn submit(&self, request: &SubmitRequest<'_>) -> Result<String>;
+
+    /// 下载附加资源（如验证码图片）
+    fn fetch(&self, url: &str) -> Result<Vec<u8>>;
+
+    /// 请求的接口地址，用于补全服务端返回的相对地址；默认为不背单词官网
+    fn endpoint(&self) -> BbdcEndpoint {
+        BbdcEndpoint::default()
+    }
+}
+
+/// 访问不背单词接口的 HTTP 传输层
+#[cfg(feature = "http")]
+pub struct HttpTransport {
+    client: Client,
+    endpoint: BbdcEndpoint,
+    cookie: Option<String>,
+}
+
+#[cfg(feature = "http")]
+impl HttpTransport {
+    /// 创建 HTTP 传输层，接口地址和超时见 [`BbdcEndpoint::from_env`]、[`Timeouts::from_env`]
+    pub fn new() -> Result<Self> {
+        Self::with_timeouts(Timeouts::from_env())
+    }
+
+    /// 使用指定超时创建 HTTP 传输层
+    pub fn with_timeouts(timeouts: Timeouts) -> Result<Self> {
+        let client = Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(timeouts.connect)
+            .timeout(timeouts.request)
+            .build()?;
+
+        Ok(Self {
+            client,
+            endpoint: BbdcEndpoint::from_env(),
+            cookie: CookieStore::load_header(),
+        })
+    }
+
+    /// 使用指定的接口地址
+    pub fn with_endpoint(mut self, endpoint: BbdcEndpoint) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+}
+
+#[cfg(feature = "http")]
+impl Transport for HttpTransport {
+    fn submit(&self, request: &SubmitRequest<'_>) -> Result<String> {
+        // 构建 multipart 表单
+        let mut form = multipart::Form::new().part(
+            "file",
+            multipart::Part::bytes(request.content.to_vec())
+                .file_name(request.file_name.to_string())
+                .mime_str("text/plain")?,
+        );
+
+        if let Some(token) = request.captcha {
+            form = form.text("captcha", token.to_string());
+        }
+        if let Some(key) = request.captcha_key {
+            form = form.text("captchaKey", key.to_string());
+        }
+
+        // 发送请求
+        let mut builder = self
+            .client
+            .post(&self.endpoint.submit_url)
+            .header("Origin", self.endpoint.origin())
+            .header("Referer", &self.endpoint.referer);
+        for (name, value) in SUBMIT_HEADERS {
+            builder = builder.header(name, value);
+        }
+
+        if let Some(cookie) = &self.cookie {
+            builder = builder.header("Cookie", cookie);
+        }
+
+        let response = builder.multipart(form).send()?;
+
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let headers = header_pairs(response.headers());
+            let body = response.text().unwrap_or_default();
+            return Err(ApiError::new(status, "不背单词拒绝了上传", headers, &body).into());
+        }
+
+        Ok(response.text()?)
+    }
+
+    fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+        let response = self
+            .client
+            .get(url)
+            .header("Referer", &self.endpoint.referer)
+            .send()?
+            .error_for_status()?;
+
+        Ok(response.bytes()?.to_vec())
+    }
+
+    fn endpoint(&self) -> BbdcEndpoint {
+        self.endpoint.clone()
+    }
+}
+
+/// 把响应头转换为 (名称, 值) 列表，用于 [`ApiError`]
+#[cfg(feature = "http")]
+fn header_pairs(headers: &reqwest::header::HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| (name.to_string(), String::from_utf8_lossy(value.as_bytes()).into_owned()))
+        .collect()
+}
+
+/// 异步的核对请求传输层
+#[cfg(feature = "async")]
+pub trait AsyncTransport: Send + Sync {
+    /// 提交单词文件，返回响应正文
+    fn submit<'a>(&'a self, request: SubmitRequest<'a>) -> BoxFuture<'a, Result<String>>;
+
+    /// 下载附加资源（如验证码图片）
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<Vec<u8>>>;
+
+    /// 请求的接口地址，见 [`Transport::endpoint`]
+    fn endpoint(&self) -> BbdcEndpoint {
+        BbdcEndpoint::default()
+    }
+}
+
+/// 以非阻塞 reqwest 客户端访问不背单词接口的传输层，请求与 [`HttpTransport`] 相同
+#[cfg(feature = "async")]
+pub struct AsyncHttpTransport {
+    client: reqwest::Client,
+    endpoint: BbdcEndpoint,
+    cookie: Option<String>,
+}
+
+#[cfg(feature = "async")]
+impl AsyncHttpTransport {
+    /// 创建异步 HTTP 传输层，接口地址和超时见 [`BbdcEndpoint::from_env`]、[`Timeouts::from_env`]
+    pub fn new() -> Result<Self> {
+        Self::with_timeouts(Timeouts::from_env())
+    }
+
+    /// 使用指定超时创建异步 HTTP 传输层
+    pub fn with_timeouts(timeouts: Timeouts) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(timeouts.connect)
+            .timeout(timeouts.request)
+            .build()?;
+
+        Ok(Self {
+            client,
+            endpoint: BbdcEndpoint::from_env(),
+            cookie: CookieStore::load_header(),
+        })
+    }
+
+    /// 使用指定的接口地址
+    pub fn with_endpoint(mut self, endpoint: BbdcEndpoint) -> Self {
+        self.endpoint = endpoint;
+        self
+    }
+}
+
+#[cfg(feature = "async")]
+impl AsyncTransport for AsyncHttpTransport {
+    fn submit<'a>(&'a self, request: SubmitRequest<'a>) -> BoxFuture<'a, Result<String>> {
+        Box::pin(async move {
+            let mut form = reqwest::multipart::Form::new().part(
+                "file",
+                reqwest::multipart::Part::bytes(request.content.to_vec())
+                    .file_name(request.file_name.to_string())
+                    .mime_str("text/plain")?,
+            );
+            if let Some(token) = request.captcha {
+                form = form.text("captcha", token.to_string());
+            }
+            if let Some(key) = request.captcha_key {
+                form = form.text("captchaKey", key.to_string());
+            }
+
+            let mut builder = self
+                .client
+                .post(&self.endpoint.submit_url)
+                .header("Origin", self.endpoint.origin())
+                .header("Referer", &self.endpoint.referer);
+            for (name, value) in SUBMIT_HEADERS {
+                builder = builder.header(name, value);
+            }
+            if let Some(cookie) = &self.cookie {
+                builder = builder.header("Cookie", cookie);
+            }
+
+            let response = builder.multipart(form).send().await?;
+            if !response.status().is_success() {
+                let status = response.status().as_u16();
+                let headers = header_pairs(response.headers());
+                let body = response.text().await.unwrap_or_default();
+                return Err(ApiError::new(status, "不背单词拒绝了上传", headers, &body).into());
+            }
+            Ok(response.text().await?)
+        })
+    }
+
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<Vec<u8>>> {
+        Box::pin(async move {
+            let response = self.client.get(url).header("Referer", &self.endpoint.referer).send().await?.error_for_status()?;
+            Ok(response.bytes().await?.to_vec())
+        })
+    }
+
+    fn endpoint(&self) -> BbdcEndpoint {
+        self.endpoint.clone()
+    }
+}
+
+/// 离线模拟的传输层
+///
+/// 提交的单词在已知单词表中（不区分大小写）即视为识别成功
+pub struct MockTransport {
+    /// 已知单词（小写），None 时全部视为识别成功
+    known: Option<HashSet<String>>,
+}
+
+impl MockTransport {
+    /// 由已知单词创建
+    pub fn new<I, S>(known: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            known: Some(
+                known
+                    .into_iter()
+                    .map(|w| w.as_ref().trim().to_lowercase())
+                    .filter(|w| !w.is_empty())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// 所有单词都识别成功，用于试运行整个流程
+    pub fn accept_all() -> Self {
+        Self { known: None }
+    }
+
+    /// 按 [`MOCK_CHECK_ENV`] 创建，未设置时返回 None
+    pub fn from_env() -> Option<Result<Self>> {
+        let value = bbdc_core::EnvLoader::get_optional(MOCK_CHECK_ENV)?;
+        Some(match value.trim() {
+            "all" => Ok(Self::accept_all()),
+            path => Self::from_file(path),
+        })
+    }
+
+    /// 从单词表文件（每行一个单词）创建
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(Self::new(content.lines()))
+    }
+}
+
+impl Transport for MockTransport {
+    fn submit(&self, request: &SubmitRequest<'_>) -> Result<String> {
+        let content = String::from_utf8_lossy(request.content);
+        let (known, unknown): (Vec<&str>, Vec<&str>) = content
+            .lines()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .partition(|w| self.known.as_ref().map_or(true, |known| known.contains(&w.to_lowercase())));
+
+        let body = json!({
+            "result_code": 200,
+            "data_body": {
+                "knowList": known.join(","),
+                "unknowList": unknown.join(","),
+            }
+        });
+
+        Ok(body.to_string())
+    }
+
+    fn fetch(&self, url: &str) -> Result<Vec<u8>> {
+        Err(Error::Other(format!("模拟传输层不支持下载: {}", url)))
+    }
+}
+
+/// 模拟传输层没有 I/O，异步提交直接返回同步结果
+#[cfg(feature = "async")]
+impl AsyncTransport for MockTransport {
+    fn submit<'a>(&'a self, request: SubmitRequest<'a>) -> BoxFuture<'a, Result<String>> {
+        Box::pin(futures::future::ready(Transport::submit(self, &request)))
+    }
+
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<Vec<u8>>> {
+        Box::pin(futures::future::ready(Transport::fetch(self, url)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_transport_partition() {
+        let transport = MockTransport::new(["hello", "World"]);
+        let request = SubmitRequest {
+            file_name: "words.txt",
+            content: b"hello\nworld\nhelo\n",
+            captcha: None,
+            captcha_key: None,
+        };
+
+        let body: serde_json::Value = serde_json::from_str(&Transport::submit(&transport, &request).unwrap()).unwrap();
+
+        assert_eq!(body["data_body"]["knowList"], "hello,world");
+        assert_eq!(body["data_body"]["unknowList"], "helo");
+
+        let body: serde_json::Value = serde_json::from_str(&Transport::submit(&MockTransport::accept_all(), &request).unwrap()).unwrap();
+        assert_eq!(body["data_body"]["knowList"], "hello,world,helo");
+    }
+
+    #[test]
+    fn test_endpoint_origin() {
+        assert_eq!(BbdcEndpoint::default().origin(), "https://bbdc.cn");
+        assert_eq!(BbdcEndpoint::default().resolve("/captcha.jpg"), "https://bbdc.cn/captcha.jpg");
+
+        let mirror = BbdcEndpoint {
+            submit_url: "http://127.0.0.1:8080/lexis/book/file/submit".to_string(),
+            referer: "http://127.0.0.1:8080".to_string(),
+        };
+        assert_eq!(mirror.origin(), "http://127.0.0.1:8080");
+        assert_eq!(BbdcEndpoint::default().submit_path(), "/lexis/book/file/submit");
+        assert_eq!(url_path("https://mirror.test/api/submit?v=2"), "/api/submit");
+        assert_eq!(url_path("https://mirror.test"), "/");
+        assert_eq!(mirror.resolve("https://cdn.example.com/c.jpg"), "https://cdn.example.com/c.jpg");
+    }
+}
@@ -0,0 +1,13 @@
+//! 用任意文本驱动 Markdown/HTML 表格提取，覆盖畸形实体和错乱的表格嵌套
+
+#![no_main]
+
+use bbdc_core::WordExtractor;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+    let _ = WordExtractor::new(true, true).extract_from_markdown(content);
+});
@@ -0,0 +1,15 @@
+//! 用任意字节驱动 Mineru 结果压缩包的解析，覆盖恶意条目名和损坏的压缩数据
+
+#![no_main]
+
+use bbdc_providers::pdf_processor::read_archive;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(entries) = read_archive(data) {
+        for (name, _) in entries {
+            assert!(name.is_relative());
+            assert!(!name.components().any(|c| matches!(c, std::path::Component::ParentDir)));
+        }
+    }
+});